@@ -0,0 +1,261 @@
+//! Benchmarks calculating the access of a guild command against a seeded guild.
+//!
+//! Compares the previous path, which ran two queries for every access category checked plus one
+//! for the command rules, against fetching the access controls with [`GuildAccess::fetch`] in a
+//! single query, and against a hit in the [`AccessCache`].
+//!
+//! Requires `DATABASE_URL` to point to a database the bench can create schemas in. The guild is
+//! seeded in a throwaway schema that is dropped afterwards, so no existing data is touched. Run
+//! with `cargo bench -p lyra --bench access`.
+
+#[expect(dead_code)]
+#[path = "../src/component/config/access/calculator.rs"]
+mod calculator;
+
+use std::{env, process, str::FromStr, sync::Arc};
+
+use criterion::{
+    BenchmarkGroup, Criterion, criterion_group, criterion_main, measurement::WallTime,
+};
+use itertools::Itertools;
+use sqlx::{
+    Pool, Postgres, migrate,
+    postgres::{PgConnectOptions, PgPoolOptions},
+};
+use tokio::{runtime::Runtime, task::JoinSet};
+use twilight_model::id::{
+    Id,
+    marker::{ChannelMarker, GuildMarker, RoleMarker, UserMarker},
+};
+
+use self::calculator::{
+    AccessCache, AccessCategoryFlag, AccessCategoryFlags, CalculatorBuilder, GuildAccess,
+};
+
+const GUILD_ID: Id<GuildMarker> = Id::new(1);
+const USER_ID: Id<UserMarker> = Id::new(2);
+const ROLE_IDS: [Id<RoleMarker>; 3] = [Id::new(3), Id::new(4), Id::new(5)];
+const TEXT_CHANNEL_ID: Id<ChannelMarker> = Id::new(6);
+const CATEGORY_CHANNEL_ID: Id<ChannelMarker> = Id::new(7);
+const COMMAND: &str = "queue export";
+
+async fn seed(db: &Pool<Postgres>) -> Result<(), sqlx::Error> {
+    let guild = GUILD_ID.get().cast_signed();
+    sqlx::query(
+        "INSERT INTO guild_configs (id, usr_access, rol_access, tch_access, cch_access)
+        VALUES ($1, false, true, true, false);",
+    )
+    .bind(guild)
+    .execute(db)
+    .await?;
+    for (table, ids) in [
+        ("usr_access", Vec::from([8, 9])),
+        ("rol_access", Vec::from([ROLE_IDS[0].get().cast_signed()])),
+        (
+            "tch_access",
+            Vec::from([TEXT_CHANNEL_ID.get().cast_signed()]),
+        ),
+        ("cch_access", Vec::from([10])),
+    ] {
+        sqlx::query(&format!(
+            "INSERT INTO {table} (guild, id) SELECT $1, * FROM UNNEST($2::bigint[]);"
+        ))
+        .bind(guild)
+        .bind(ids)
+        .execute(db)
+        .await?;
+    }
+    sqlx::query(
+        "INSERT INTO command_access (guild, command, category, id, allow)
+        VALUES ($1, 'queue', 2, $2, true), ($1, 'play', 1, 8, false);",
+    )
+    .bind(guild)
+    .bind(ROLE_IDS[0].get().cast_signed())
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+/// The categories checked and the IDs checked against each of them.
+fn checks() -> [(AccessCategoryFlag, Vec<i64>); 4] {
+    [
+        (AccessCategoryFlag::Users, Vec::from([USER_ID.get()])),
+        (AccessCategoryFlag::Roles, ROLE_IDS.map(Id::get).into()),
+        (
+            AccessCategoryFlag::TextChannels,
+            Vec::from([TEXT_CHANNEL_ID.get()]),
+        ),
+        (
+            AccessCategoryFlag::CategoryChannels,
+            Vec::from([CATEGORY_CHANNEL_ID.get()]),
+        ),
+    ]
+    .map(|(category, ids)| (category, ids.into_iter().map(u64::cast_signed).collect()))
+}
+
+/// The previous path: two queries for every category checked, plus one for the command rules,
+/// all run concurrently.
+async fn baseline(db: &Pool<Postgres>) -> Result<bool, sqlx::Error> {
+    let guild = GUILD_ID.get().cast_signed();
+    let rules = tokio::spawn({
+        let db = db.clone();
+        async move {
+            sqlx::query_as::<_, (i16, i64, bool)>(
+                "SELECT category, id, allow FROM command_access
+                WHERE guild = $1 AND ($2 = command OR starts_with($2, command || ' '));",
+            )
+            .bind(guild)
+            .bind(COMMAND)
+            .fetch_all(&db)
+            .await
+        }
+    });
+
+    let mut set = JoinSet::new();
+    let mut ids = Vec::from([guild]);
+    for (category, category_ids) in checks() {
+        ids.extend_from_slice(&category_ids);
+        let db = db.clone();
+        set.spawn(async move {
+            let column = category.ident();
+            let where_clause = category_ids
+                .iter()
+                .map(|id| format!("id = {id}"))
+                .join(" OR ");
+            let (in_access_controls,) = sqlx::query_as::<_, (bool,)>(&format!(
+                "SELECT EXISTS (SELECT 1 FROM {column} WHERE guild = $1 AND ({where_clause}))"
+            ))
+            .bind(guild)
+            .fetch_one(&db)
+            .await?;
+            let (mode,) = sqlx::query_as::<_, (Option<bool>,)>(&format!(
+                "SELECT {column} FROM guild_configs WHERE id = $1"
+            ))
+            .bind(guild)
+            .fetch_one(&db)
+            .await?;
+            Ok::<_, sqlx::Error>(mode.is_none_or(|mode| mode == in_access_controls))
+        });
+    }
+    let mut allowed = true;
+    while let Some(res) = set.join_next().await {
+        allowed &= res.expect("task should not panic")?;
+    }
+
+    let rules = rules
+        .await
+        .expect("task should not panic")?
+        .into_iter()
+        .filter_map(|(category, id, allow)| {
+            Some((AccessCategoryFlag::from_i16(category)?, id, allow))
+        })
+        .collect::<Vec<_>>();
+    let command_allowed = [
+        AccessCategoryFlags::MENTIONABLES,
+        AccessCategoryFlags::ALL_CHANNELS,
+    ]
+    .into_iter()
+    .all(|categories| {
+        let mut has_allow_rules = false;
+        let mut allowed = false;
+        for &(_, id, allow) in rules
+            .iter()
+            .filter(|(category, ..)| categories.contains((*category).into()))
+        {
+            has_allow_rules |= allow;
+            if ids.contains(&id) {
+                if !allow {
+                    return false;
+                }
+                allowed = true;
+            }
+        }
+        allowed || !has_allow_rules
+    });
+    Ok(allowed && command_allowed)
+}
+
+fn calculate(access: Arc<GuildAccess>) -> bool {
+    CalculatorBuilder::new(GUILD_ID)
+        .user(USER_ID)
+        .roles(ROLE_IDS.iter())
+        .text_channel(TEXT_CHANNEL_ID)
+        .category_channel(CATEGORY_CHANNEL_ID)
+        .command(COMMAND.into())
+        .build_with(access)
+        .calculate()
+}
+
+fn bench(group: &mut BenchmarkGroup<'_, WallTime>, rt: &Runtime, db: &Pool<Postgres>) {
+    group.bench_function("baseline", |b| {
+        b.to_async(rt).iter(|| async {
+            baseline(db).await.expect("queries should succeed");
+        });
+    });
+    group.bench_function("uncached", |b| {
+        b.to_async(rt).iter(|| async {
+            let access = GuildAccess::fetch(db, GUILD_ID)
+                .await
+                .expect("query should succeed");
+            calculate(Arc::new(access))
+        });
+    });
+    let cache = AccessCache::new(1);
+    group.bench_function("cached", |b| {
+        b.to_async(rt).iter(|| async {
+            let access = GuildAccess::get(&cache, db, GUILD_ID)
+                .await
+                .expect("query should succeed");
+            calculate(access)
+        });
+    });
+}
+
+/// Checks that both paths calculate the same access before benchmarking them.
+async fn check(db: &Pool<Postgres>) -> Result<(), sqlx::Error> {
+    let access = GuildAccess::fetch(db, GUILD_ID).await?;
+    assert_eq!(
+        baseline(db).await?,
+        calculate(Arc::new(access)),
+        "both paths must calculate the same access"
+    );
+    Ok(())
+}
+
+fn access(c: &mut Criterion) {
+    let _ = dotenvy::dotenv();
+    let database_url = env::var("DATABASE_URL").expect("`DATABASE_URL` must be set");
+    let options = PgConnectOptions::from_str(&database_url).expect("`DATABASE_URL` must be valid");
+    let schema = format!("lyra_access_bench_{}", process::id());
+
+    let rt = Runtime::new().expect("runtime should be buildable");
+    let admin = rt
+        .block_on(PgPoolOptions::new().connect_with(options.clone()))
+        .expect("database should be connectable");
+    rt.block_on(sqlx::query(&format!("CREATE SCHEMA {schema};")).execute(&admin))
+        .expect("creating the bench schema should succeed");
+
+    let db = rt
+        .block_on(
+            PgPoolOptions::new()
+                .max_connections(20)
+                .connect_with(options.options([("search_path", &schema)])),
+        )
+        .expect("database should be connectable");
+    let seeded = rt.block_on(async {
+        migrate!("../migrations").run(&db).await?;
+        seed(&db).await?;
+        check(&db).await.map_err(sqlx::migrate::MigrateError::from)
+    });
+    if seeded.is_ok() {
+        bench(&mut c.benchmark_group("access"), &rt, &db);
+    }
+
+    rt.block_on(db.close());
+    rt.block_on(sqlx::query(&format!("DROP SCHEMA {schema} CASCADE;")).execute(&admin))
+        .expect("dropping the bench schema should succeed");
+    seeded.expect("migrating and seeding the bench schema should succeed");
+}
+
+criterion_group!(benches, access);
+criterion_main!(benches);
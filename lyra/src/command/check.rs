@@ -15,12 +15,12 @@ use twilight_model::{
 
 use crate::{
     LavalinkAndGuildIdAware,
-    command::model::CtxKind,
+    command::model::{CommandDataAwareKind, CtxKind},
     component::config::access::CalculatorBuilder,
     core::{
         model::{
-            BotState, DatabaseAware, HttpAware, OwnedBotStateAware, PartialMemberAware,
-            UserIdAware, UserPermissionsAware,
+            BotState, BotStateAware, CacheAware, HttpAware, OwnedBotStateAware, PartialMemberAware,
+            UserDjAware, UserIdAware, UserPermissionsAware,
         },
         statik::application,
         traced,
@@ -29,7 +29,7 @@ use crate::{
         Cache, InVoiceWithSomeoneElse as InVoiceWithSomeoneElseError,
         InVoiceWithoutUser as InVoiceWithoutUserError, NotUsersTrack as NotUsersTrackError,
         UserNotAccessManager as UserNotAccessManagerError, UserNotAllowed as UserNotAllowedError,
-        UserNotDj as UserNotDjError, UserNotStageModerator as UserNotStageModeratorError,
+        UserNotStageModerator as UserNotStageModeratorError,
         command::check::{self, AlternateVoteResponse, PollResolvableError, UserOnlyInError},
     },
     gateway::GuildIdAware,
@@ -66,14 +66,23 @@ pub fn does_user_have_permissions(
 }
 
 #[inline]
-pub fn is_user_dj(ctx: &impl UserPermissionsAware) -> bool {
-    does_user_have_permissions(DJ_PERMISSIONS, ctx)
+pub fn is_user_dj(ctx: &impl UserDjAware) -> bool {
+    ctx.user_is_configured_dj() || does_user_have_permissions(DJ_PERMISSIONS, ctx)
 }
 
-pub fn user_is_dj(ctx: &impl UserPermissionsAware) -> Result<(), UserNotDjError> {
-    if !is_user_dj(ctx) {
-        return Err(UserNotDjError);
+/// Resolves whether the user is a DJ via the guild's DJ settings, for [`is_user_dj`] to consult.
+pub async fn resolve_user_dj(ctx: &mut GuildCtx<impl CtxKind>) -> Result<(), sqlx::Error> {
+    if does_user_have_permissions(DJ_PERMISSIONS, ctx) {
+        return Ok(());
     }
+
+    let config = ctx.bot().guild_configs().get(ctx.guild_id()).await?;
+    let configured_dj = config.dj().is_dj(&ctx.member().roles, || {
+        ctx.cache()
+            .voice_state(ctx.user_id(), ctx.guild_id())
+            .is_some_and(|state| someone_else_in(state.channel_id(), ctx).is_ok_and(|s| !s))
+    });
+    ctx.set_configured_dj(configured_dj);
     Ok(())
 }
 
@@ -95,13 +104,16 @@ pub fn user_is_stage_moderator(
     Ok(())
 }
 
-pub async fn user_allowed_in(ctx: &GuildCtx<impl CtxKind>) -> Result<(), check::UserAllowedError> {
+pub async fn user_allowed_in(
+    ctx: &GuildCtx<impl CommandDataAwareKind>,
+) -> Result<(), check::UserAllowedError> {
     if user_is_access_manager(ctx).is_ok() {
         return Ok(());
     }
 
     let channel = ctx.channel();
-    let mut access_calculator_builder = CalculatorBuilder::new(ctx.guild_id(), ctx.db().clone())
+    let mut access_calculator_builder = CalculatorBuilder::new(ctx.guild_id())
+        .command(ctx.command_name_full())
         .user(ctx.user_id())
         .roles(ctx.member().roles.iter());
     match channel.kind {
@@ -130,7 +142,10 @@ pub async fn user_allowed_in(ctx: &GuildCtx<impl CtxKind>) -> Result<(), check::
         }
     }
 
-    let user_allowed_to_use_commands = access_calculator_builder.build().await?.calculate();
+    let user_allowed_to_use_commands = access_calculator_builder
+        .build(ctx.bot())
+        .await?
+        .calculate();
     if !user_allowed_to_use_commands {
         return Err(UserNotAllowedError.into());
     }
@@ -147,14 +162,16 @@ pub async fn user_allowed_to_use(
     }
 
     let guild_id = ctx.guild_id();
-    let mut access_calculator_builder =
-        CalculatorBuilder::new(guild_id, ctx.db().clone()).voice_channel(channel_id);
+    let mut access_calculator_builder = CalculatorBuilder::new(guild_id).voice_channel(channel_id);
 
     if let Some(parent_id) = channel_parent_id {
         access_calculator_builder = access_calculator_builder.category_channel(parent_id);
     }
 
-    let allowed_to_use_channel = access_calculator_builder.build().await?.calculate();
+    let allowed_to_use_channel = access_calculator_builder
+        .build(ctx.bot())
+        .await?
+        .calculate();
 
     if !allowed_to_use_channel {
         return Err(UserNotAllowedError.into());
@@ -186,14 +203,11 @@ pub fn noone_else_in(
     Ok(())
 }
 
-pub struct PollStarter(#[expect(unused)] PollStarterInfo);
+pub struct PollStarter(PollStarterInfo);
 
 pub struct PollStarterInfo {
-    #[expect(unused)]
     topic: PollTopic,
-    #[expect(unused)]
     error: PollResolvableError,
-    #[expect(unused)]
     in_voice: PartialInVoice,
 }
 
@@ -211,7 +225,6 @@ impl InVoiceWithUserResult<'_> {
 
 pub trait ResolveWithPoll {
     type Error;
-    #[expect(unused)] // TODO: #44
     fn or_else_try_resolve_with(self, topic: PollTopic)
     -> Result<Option<PollStarter>, Self::Error>;
 }
@@ -241,8 +254,32 @@ impl ResolveWithPoll for InVoiceWithUserOnlyResult {
     }
 }
 
+impl ResolveWithPoll for Result<(), check::UsersTrackError> {
+    type Error = Cache;
+
+    fn or_else_try_resolve_with(
+        self,
+        topic: PollTopic,
+    ) -> Result<Option<PollStarter>, Self::Error> {
+        let Err(error) = self else {
+            return Ok(None);
+        };
+
+        match error {
+            check::UsersTrackError::NotUsersTrack(e) => {
+                let in_voice = e.in_voice.clone();
+                Ok(Some(PollStarter(PollStarterInfo {
+                    topic,
+                    error: check::PollResolvableError::NotUsersTrack(e),
+                    in_voice,
+                })))
+            }
+            check::UsersTrackError::Cache(e) => Err(e),
+        }
+    }
+}
+
 pub trait StartPoll: Sized {
-    #[expect(unused)] // TODO: #44
     async fn and_then_start(
         self,
         ctx: &mut GuildCtx<impl RespondWithMessageKind>,
@@ -299,8 +336,8 @@ fn impl_users_track(
     position: NonZeroUsize,
     user_only_in: UserOnlyInError,
 ) -> check::UsersTrackError {
-    let channel_id = match user_only_in {
-        check::UserOnlyInError::InVoiceWithSomeoneElse(e) => e.0.channel_id(),
+    let in_voice = match user_only_in {
+        check::UserOnlyInError::InVoiceWithSomeoneElse(e) => e.0,
         check::UserOnlyInError::Cache(e) => return e.into(),
     };
     let title = track.data().info.corrected_title().into();
@@ -310,7 +347,7 @@ fn impl_users_track(
         requester,
         position,
         title,
-        channel_id,
+        in_voice,
     }
     .into()
 }
@@ -729,7 +766,6 @@ pub fn all_users_track(
 // }
 //
 
-#[expect(unused)]
 async fn handle_poll(
     error: check::PollResolvableError,
     topic: &PollTopic,
@@ -748,7 +784,7 @@ async fn handle_poll(
         let mut s = DefaultHasher::new();
         topic.hash(&mut s);
         if s.finish() == poll.topic_hash() {
-            if is_user_dj(ctx) {
+            if poll.dj_override() && is_user_dj(ctx) {
                 conn.dispatch(Event::AlternateVoteDjCast);
 
                 return Err(check::AnotherPollOngoingError {
@@ -798,7 +834,11 @@ async fn handle_poll(
         .into());
     }
 
-    let resolution = Box::pin(poll::start(topic, ctx, in_voice)).await;
+    let requester = match error {
+        check::PollResolvableError::NotUsersTrack(ref e) => Some(e.requester),
+        _ => None,
+    };
+    let resolution = Box::pin(poll::start(topic, ctx, in_voice, requester)).await;
     ctx.get_conn().reset_poll();
     match resolution? {
         PollResolution::UnanimousWin => Ok(()),
@@ -832,7 +872,7 @@ async fn send_superseded_win_notice(
     interaction_token: String,
     bot: Arc<BotState>,
 ) -> Result<(), twilight_http::Error> {
-    tokio::time::sleep(Duration::from_millis(1000)).await;
+    tokio::time::sleep(Duration::from_secs(1)).await;
 
     bot.http()
         .interaction(application::id())
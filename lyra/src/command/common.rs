@@ -55,10 +55,10 @@ pub enum PlaySource {
 // this will be called more than once: exactly two times in
 // - `PlaySource::create_option()`
 // - `PlaySource::display_names()`
-static DEEZER_ENABLED: LazyLock<bool> = LazyLock::new(|| {
+pub static DEEZER_ENABLED: LazyLock<bool> = LazyLock::new(|| {
     env::var("PLUGINS_LAVASRC_SOURCES_DEEZER").is_ok_and(|v| v.parse::<bool>().is_ok_and(|b| b))
 });
-static SPOTIFY_ENABLED: LazyLock<bool> = LazyLock::new(|| {
+pub static SPOTIFY_ENABLED: LazyLock<bool> = LazyLock::new(|| {
     env::var("PLUGINS_LAVASRC_SOURCES_SPOTIFY").is_ok_and(|v| v.parse::<bool>().is_ok_and(|b| b))
 });
 
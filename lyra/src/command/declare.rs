@@ -9,13 +9,16 @@ use crate::component::{
     config::Config,
     connection::{Join, Leave},
     controller::NowPlaying,
+    debug::Debug,
     misc::{Ping, Uptime},
     playback::{Back, Jump, JumpAutocomplete, PlayPause, Restart, Seek, Skip},
     queue::{
-        AddToQueue, Clear, FairQueue, Move, MoveAutocomplete, Play, PlayAutocomplete, PlayFile,
-        Remove, RemoveAutocomplete, RemoveRange, RemoveRangeAutocomplete, Repeat, Shuffle,
+        AddToQueue, Autoplay, Clear, FairQueue, History, HistoryAutocomplete, Move,
+        MoveAutocomplete, Play, PlayAutocomplete, PlayFile, PlayNext, Playlist,
+        PlaylistAutocomplete, Queue, Remove, RemoveAutocomplete, RemoveRange,
+        RemoveRangeAutocomplete, Repeat, Search, Shuffle,
     },
-    tuning::{Equaliser, Filter, Speed, Volume},
+    tuning::{Effect, Equaliser, EqualiserAutocomplete, Filter, Speed, Volume},
 };
 
 macro_rules! count {
@@ -143,10 +146,11 @@ macro_rules! declare_guild_slash_commands_callback {
 
         impl $crate::command::model::GuildSlashCmdCtx {
             pub async fn execute(
-                self,
+                mut self,
                 data: ::twilight_model::application::interaction::application_command::CommandData,
             ) -> ::std::result::Result<(), $crate::error::command::declare::CommandExecuteError> {
                 $crate::command::check::user_allowed_in(&self).await?;
+                $crate::command::check::resolve_user_dj(&mut self).await?;
 
                 // as there are many guild slash commands,
                 // matching each name branch by branch will have significant impact on runtime performance,
@@ -225,10 +229,11 @@ macro_rules! declare_guild_message_commands_callback {
             // as there aren't many guild message commands,
             // matching each name branch by branch will have insignificant impact on runtime performance.
             pub async fn execute(
-                self,
+                mut self,
                 data: ::twilight_model::application::interaction::application_command::CommandData,
             ) -> ::std::result::Result<(), $crate::error::command::declare::CommandExecuteError> {
                 $crate::command::check::user_allowed_in(&self).await?;
+                $crate::command::check::resolve_user_dj(&mut self).await?;
 
                 match data.name {
                     $(
@@ -322,17 +327,23 @@ declare_slash_commands![
     Config,
     Play,
     PlayFile,
+    Search,
     Repeat,
     Shuffle,
     FairQueue,
+    Autoplay,
     Remove,
     RemoveRange,
     Clear,
     Move,
+    Queue,
+    History,
+    Playlist,
     Volume,
     Filter,
     Speed,
     Equaliser,
+    Effect,
     PlayPause,
     Seek,
     Restart,
@@ -340,6 +351,7 @@ declare_slash_commands![
     Skip,
     Back,
     NowPlaying,
+    Debug,
 ];
 declare_slash_commands_callback![Ping, Uptime];
 declare_guild_slash_commands_callback![
@@ -350,17 +362,23 @@ declare_guild_slash_commands_callback![
     Config,
     Play,
     PlayFile,
+    Search,
     Repeat,
     Shuffle,
     FairQueue,
+    Autoplay,
     Remove,
     RemoveRange,
     Clear,
     Move,
+    Queue,
+    History,
+    Playlist,
     Volume,
     Filter,
     Speed,
     Equaliser,
+    Effect,
     PlayPause,
     Seek,
     Restart,
@@ -368,11 +386,12 @@ declare_guild_slash_commands_callback![
     Skip,
     Back,
     NowPlaying,
+    Debug,
 ];
 
-declare_message_commands![AddToQueue,];
+declare_message_commands![AddToQueue, PlayNext];
 declare_message_commands_callback![];
-declare_guild_message_commands_callback![AddToQueue,];
+declare_guild_message_commands_callback![AddToQueue, PlayNext];
 
 declare_autocomplete![];
 declare_guild_autocomplete![
@@ -381,6 +400,9 @@ declare_guild_autocomplete![
     RemoveRange => RemoveRangeAutocomplete,
     Move => MoveAutocomplete,
     Jump => JumpAutocomplete,
+    History => HistoryAutocomplete,
+    Playlist => PlaylistAutocomplete,
+    Equaliser => EqualiserAutocomplete,
 ];
 
 pub static POPULATED_COMMAND_MAP: OnceLock<HashMap<&'static str, Command>> = OnceLock::new();
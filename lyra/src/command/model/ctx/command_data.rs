@@ -40,6 +40,7 @@ impl<T: CommandDataAwareKind, C: CtxContext> Ctx<T, C> {
             latency,
             sender,
             acknowledged: false,
+            configured_dj: false,
             acknowledgement: Some(acknowledgement),
             kind: PhantomData::<fn(T) -> T>,
             context: PhantomData,
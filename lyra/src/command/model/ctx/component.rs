@@ -37,6 +37,7 @@ impl<C: CtxContext> Ctx<ComponentMarker, C> {
             acknowledgement: Some(acknowledgement),
             data: Some(PartialInteractionData::Component(data)),
             acknowledged: false,
+            configured_dj: false,
             kind: std::marker::PhantomData,
             context: std::marker::PhantomData,
         }
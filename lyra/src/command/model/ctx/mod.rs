@@ -33,7 +33,7 @@ use crate::{
     core::{
         model::{
             BotState, BotStateAware, CacheAware, DatabaseAware, HttpAware, OwnedBotState,
-            OwnedBotStateAware, OwnedHttpAware, PartialMemberAware, UserAware,
+            OwnedBotStateAware, OwnedHttpAware, PartialMemberAware, UserAware, UserDjAware,
             UserPermissionsAware, response::Respond,
         },
         statik::application,
@@ -48,6 +48,7 @@ use super::PartialInteractionData;
 use self::modal::ModalMarker;
 pub use self::{
     autocomplete::{AutocompleteCtx, GuildAutocompleteCtx},
+    command_data::CommandDataAwareKind,
     defer::RespondWithDeferKind,
     followup::FollowupKind,
     menu::{GuildMessageCmdCtx, MessageCmdCtx, UserCmdCtx},
@@ -99,6 +100,7 @@ where
     data: Option<PartialInteractionData>,
     acknowledged: bool,
     acknowledgement: Option<oneshot::Sender<()>>,
+    configured_dj: bool,
     kind: PhantomData<fn(T) -> T>,
     context: PhantomData<fn(C) -> C>,
 }
@@ -118,6 +120,7 @@ impl<C: CtxContext> Ctx<ComponentMarker, C> {
             data: None,
             acknowledged: false,
             acknowledgement: None,
+            configured_dj: self.configured_dj,
             kind: PhantomData::<fn(ComponentModalMarker) -> ComponentModalMarker>,
         }
     }
@@ -134,6 +137,7 @@ impl<A: CmdInnerMarkerKind, C: CtxContext> Ctx<CmdMarker<A>, C> {
             data: None,
             acknowledged: false,
             acknowledgement: None,
+            configured_dj: self.configured_dj,
             kind: PhantomData::<fn(CmdModalMarker) -> CmdModalMarker>,
         }
     }
@@ -187,6 +191,7 @@ impl<T: CtxKind> Ctx<T, GuildMarker> {
             data: self.data,
             acknowledged: self.acknowledged,
             acknowledgement: self.acknowledgement,
+            configured_dj: self.configured_dj,
             kind: self.kind,
             context: PhantomData::<fn(NonGuildMarker) -> NonGuildMarker>,
         }
@@ -238,6 +243,10 @@ impl<T: CtxKind> Ctx<T, GuildMarker> {
         )
     }
 
+    pub const fn set_configured_dj(&mut self, configured_dj: bool) {
+        self.configured_dj = configured_dj;
+    }
+
     pub fn current_voice_state(&'_ self) -> Option<CachedVoiceStateRef<'_>> {
         let user = self.bot().user_id();
         self.cache().voice_state(user, self.guild_id())
@@ -349,3 +358,9 @@ impl<T: CtxKind> UserPermissionsAware for Ctx<T, GuildMarker> {
         self.author_permissions_expected()
     }
 }
+
+impl<T: CtxKind> UserDjAware for Ctx<T, GuildMarker> {
+    fn user_is_configured_dj(&self) -> bool {
+        self.configured_dj
+    }
+}
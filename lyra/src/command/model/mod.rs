@@ -21,9 +21,10 @@ use twilight_model::{
 use crate::error::{CommandResult, command::AutocompleteResult};
 
 pub use self::ctx::{
-    AutocompleteCtx, CmdInnerMarkerKind, CmdMarker, CtxKind, FollowupKind, GuildAutocompleteCtx,
-    GuildComponentCtx, GuildCtx, GuildMessageCmdCtx, GuildModalCtx, GuildSlashCmdCtx,
-    MessageCmdCtx, RespondWithDeferKind, RespondWithMessageKind, SlashCmdCtx, UserCmdCtx,
+    AutocompleteCtx, CmdInnerMarkerKind, CmdMarker, CommandDataAwareKind, CtxKind, FollowupKind,
+    GuildAutocompleteCtx, GuildComponentCtx, GuildCtx, GuildMessageCmdCtx, GuildModalCtx,
+    GuildSlashCmdCtx, MessageCmdCtx, RespondWithDeferKind, RespondWithMessageKind, SlashCmdCtx,
+    UserCmdCtx,
 };
 
 pub trait NonPingInteraction {
@@ -78,7 +79,7 @@ pub struct PartialCommandData {
 impl PartialCommandData {
     pub fn new(data: &CommandData) -> Self {
         Self {
-            name: data.name.to_string().into(),
+            name: data.name.clone().into(),
             target_id: data.target_id,
             resolved: data.resolved.clone(),
             options: data.options.clone().into(),
@@ -4,6 +4,8 @@ use std::{
         hash_map::{DefaultHasher, Entry},
     },
     hash::{Hash, Hasher},
+    num::NonZeroUsize,
+    sync::Arc,
     time::Duration,
 };
 
@@ -32,6 +34,10 @@ use twilight_util::builder::embed::{
 
 use crate::{
     LavalinkAware,
+    component::config::{
+        dj::DjSettings,
+        poll::{PollRules, TopicKind},
+    },
     core::{
         konst::{
             colour,
@@ -48,7 +54,7 @@ use crate::{
         command::poll::{GenerateEmbedError, StartPollError, UpdateEmbedError, WaitForVotesError},
     },
     gateway::GuildIdAware,
-    lavalink::{Event, EventRecvResult},
+    lavalink::{Event, EventRecvResult, Fairness, RepeatMode},
 };
 
 use super::{
@@ -59,28 +65,75 @@ use super::{
 
 #[derive(Hash)]
 pub enum Topic {
-    #[expect(unused)] // TODO: #44
-    Repeat(crate::lavalink::RepeatMode),
+    Skip(Arc<str>, NonZeroUsize),
+    Back,
+    Jump(Arc<str>, NonZeroUsize),
+    Repeat(RepeatMode),
+    Shuffle(bool),
+    FairQueue(Option<Fairness>),
+    Pause(bool),
+    Clear,
 }
 
 impl Topic {
+    pub const fn kind(&self) -> TopicKind {
+        match self {
+            Self::Skip(..) => TopicKind::Skip,
+            Self::Back => TopicKind::Back,
+            Self::Jump(..) => TopicKind::Jump,
+            Self::Repeat(_) => TopicKind::Repeat,
+            Self::Shuffle(_) => TopicKind::Shuffle,
+            Self::FairQueue(_) => TopicKind::FairQueue,
+            Self::Pause(_) => TopicKind::Pause,
+            Self::Clear => TopicKind::Clear,
+        }
+    }
+
     const fn is_voided_by(&self, event: &Event) -> bool {
         match self {
+            Self::Skip(..) | Self::Back => matches!(event, Event::QueueClear | Event::QueueAdvance),
+            Self::Jump(..) => matches!(
+                event,
+                Event::QueueClear | Event::QueueAdvance | Event::QueueReindex
+            ),
             Self::Repeat(_) => matches!(event, Event::QueueClear | Event::QueueRepeat),
+            Self::Shuffle(_) | Self::FairQueue(_) => {
+                matches!(event, Event::QueueClear | Event::QueueReindex)
+            }
+            Self::Pause(_) => matches!(event, Event::QueueClear | Event::PlayerPause),
+            Self::Clear => matches!(event, Event::QueueClear),
         }
     }
 }
 
 impl std::fmt::Display for Topic {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let message = match self {
-            Self::Repeat(mode) => match mode {
-                crate::lavalink::RepeatMode::Off => "Disable repeat for the entire queue",
-                crate::lavalink::RepeatMode::All => "Enable repeat for the entire queue",
-                crate::lavalink::RepeatMode::Track => "Enable repeat for only the current track",
-            },
-        };
-        f.write_str(message)
+        match self {
+            Self::Skip(title, position) => write!(f, "Skip `{title}` (`#{position}`)"),
+            Self::Back => f.write_str("Go back to the previous track"),
+            Self::Jump(title, position) => write!(f, "Jump to `{title}` (`#{position}`)"),
+            Self::Repeat(mode) => f.write_str(match mode {
+                RepeatMode::Off => "Disable repeat for the entire queue",
+                RepeatMode::All => "Enable repeat for the entire queue",
+                RepeatMode::Track => "Enable repeat for only the current track",
+            }),
+            Self::Shuffle(shuffle) => f.write_str(if *shuffle {
+                "Enable shuffle"
+            } else {
+                "Disable shuffle"
+            }),
+            Self::FairQueue(fairness) => f.write_str(match fairness {
+                None => "Disable fair queue",
+                Some(Fairness::TrackCount) => "Balance the queue between requesters by track count",
+                Some(Fairness::PlayTime) => "Balance the queue between requesters by play time",
+            }),
+            Self::Pause(pause) => f.write_str(if *pause {
+                "Pause the current track"
+            } else {
+                "Resume the current track"
+            }),
+            Self::Clear => f.write_str("Clear the queue"),
+        }
     }
 }
 
@@ -88,16 +141,18 @@ impl std::fmt::Display for Topic {
 pub struct Poll {
     topic_hash: u64,
     message: super::util::MessageLinkComponent,
+    dj_override: bool,
 }
 
 impl Poll {
-    fn new(topic: &Topic, message: super::util::MessageLinkComponent) -> Self {
+    fn new(topic: &Topic, message: super::util::MessageLinkComponent, dj_override: bool) -> Self {
         let mut s = DefaultHasher::new();
         topic.hash(&mut s);
 
         Self {
             topic_hash: s.finish(),
             message,
+            dj_override,
         }
     }
 
@@ -105,6 +160,11 @@ impl Poll {
         self.topic_hash
     }
 
+    /// Whether DJs can decide this poll by themselves.
+    pub const fn dj_override(&self) -> bool {
+        self.dj_override
+    }
+
     pub fn message_owned(&self) -> super::util::MessageLinkComponent {
         self.message.clone()
     }
@@ -112,11 +172,15 @@ impl Poll {
 
 struct Voter {
     permissions: Permissions,
+    configured_dj: bool,
 }
 
 impl Voter {
-    const fn new(permissions: Permissions) -> Self {
-        Self { permissions }
+    const fn new(permissions: Permissions, configured_dj: bool) -> Self {
+        Self {
+            permissions,
+            configured_dj,
+        }
     }
 }
 
@@ -126,12 +190,18 @@ impl crate::core::model::UserPermissionsAware for Voter {
     }
 }
 
+impl crate::core::model::UserDjAware for Voter {
+    fn user_is_configured_dj(&self) -> bool {
+        self.configured_dj
+    }
+}
+
 #[derive(Copy, Clone, Debug, const_panic::PanicFmt)]
 pub struct Vote(bool);
 
 impl Vote {
-    const fn value(self) -> isize {
-        if self.0 { 1 } else { -1 }
+    const fn is_upvote(self) -> bool {
+        self.0
     }
 }
 
@@ -146,6 +216,9 @@ impl std::fmt::Display for Vote {
 pub enum VoidingEvent {
     QueueClear,
     QueueRepeat,
+    QueueAdvance,
+    QueueReindex,
+    PlayerPause,
 }
 
 impl VoidingEvent {
@@ -153,6 +226,9 @@ impl VoidingEvent {
         match event {
             Event::QueueClear => Self::QueueClear,
             Event::QueueRepeat => Self::QueueRepeat,
+            Event::QueueAdvance => Self::QueueAdvance,
+            Event::QueueReindex => Self::QueueReindex,
+            Event::PlayerPause => Self::PlayerPause,
             _ => const_panic::concat_panic!("invalid event: ", {}: event),
         }
     }
@@ -180,6 +256,93 @@ pub enum Resolution {
     SupersededLossViaDj,
 }
 
+/// The votes casted to a poll, along with how many votes either side needs to win.
+struct Ballot {
+    votes: HashMap<Id<UserMarker>, Vote>,
+    threshold: usize,
+    double_voter: Option<Id<UserMarker>>,
+}
+
+impl Ballot {
+    fn new(
+        author_id: Id<UserMarker>,
+        users_in_voice: &HashSet<Id<UserMarker>>,
+        requester: Option<Id<UserMarker>>,
+        rules: PollRules,
+    ) -> Self {
+        let double_voter =
+            requester.filter(|r| rules.requester_double() && users_in_voice.contains(r));
+        let total = users_in_voice.len() + usize::from(double_voter.is_some());
+
+        let mut ballot = Self {
+            votes: HashMap::new(),
+            threshold: rules.threshold().votes_needed(total),
+            double_voter,
+        };
+        ballot.votes.insert(author_id, Vote(true));
+        ballot
+    }
+
+    fn weight_of(&self, user_id: Id<UserMarker>) -> usize {
+        if self.double_voter == Some(user_id) {
+            2
+        } else {
+            1
+        }
+    }
+
+    fn tally(&self) -> (usize, usize) {
+        self.votes
+            .iter()
+            .fold((0, 0), |(upvotes, downvotes), (&user_id, vote)| {
+                let weight = self.weight_of(user_id);
+                if vote.is_upvote() {
+                    (upvotes + weight, downvotes)
+                } else {
+                    (upvotes, downvotes + weight)
+                }
+            })
+    }
+
+    fn resolution(&self) -> Option<Resolution> {
+        let (upvotes, downvotes) = self.tally();
+        if upvotes >= self.threshold {
+            return Some(Resolution::UnanimousWin);
+        }
+        if downvotes >= self.threshold {
+            return Some(Resolution::UnanimousLoss);
+        }
+        None
+    }
+
+    fn votes_and_ratios(&self) -> ((usize, usize), (f64, f64, f64)) {
+        let (upvotes, downvotes) = self.tally();
+        // the votes of both sides combined may exceed the threshold without either side winning
+        let total = self.threshold.max(upvotes + downvotes);
+        let votes_left = total - upvotes - downvotes;
+
+        let (total_f64, upvotes_f64, downvotes_f64, votes_left_f64) = (
+            usize_as_f64(total),
+            usize_as_f64(upvotes),
+            usize_as_f64(downvotes),
+            usize_as_f64(votes_left),
+        );
+
+        let upvote_ratio = upvotes_f64 / total_f64;
+        let downvote_ratio = downvotes_f64 / total_f64;
+        let votes_left_ratio = votes_left_f64 / total_f64;
+
+        (
+            (upvotes, downvotes),
+            (upvote_ratio, downvote_ratio, votes_left_ratio),
+        )
+    }
+
+    fn vote_ratios(&self) -> (f64, f64, f64) {
+        self.votes_and_ratios().1
+    }
+}
+
 struct LatentEmbedColours {
     base: [f32; 7],
     upvote: [f32; 7],
@@ -196,21 +359,29 @@ struct WaitForPollActionsContext<'a> {
     components: &'a mut twilight_standby::future::WaitForComponentStream,
     upvote_button_id: String,
     message: super::util::MessageLinkComponent,
+    /// The guild's DJ settings, if DJs can decide this poll by themselves.
+    dj: Option<DjSettings>,
 }
 
-fn handle_interactions(inter: Interaction, upvote_button_id: &String) -> PollAction {
+fn handle_interactions(
+    inter: Interaction,
+    upvote_button_id: &String,
+    dj: Option<&DjSettings>,
+) -> PollAction {
     let user_id = inter.author_id_expected();
 
     let Some(InteractionData::MessageComponent(ref component)) = inter.data else {
         unreachable!()
     };
 
-    let voter_permissions = inter.author_permissions_expected();
+    let voter_is_dj = dj.is_some_and(|settings| {
+        // voters are in voice with everyone else eligible to vote, so they are never alone
+        let configured_dj = settings.is_dj(&inter.member_expected().roles, || false);
+        let voter = Voter::new(inter.author_permissions_expected(), configured_dj);
+        super::check::is_user_dj(&voter)
+    });
 
-    match (
-        super::check::is_user_dj(&Voter::new(voter_permissions)),
-        component.custom_id == *upvote_button_id,
-    ) {
+    match (voter_is_dj, component.custom_id == *upvote_button_id) {
         (true, true) => PollAction::DjUpvote(inter),
         (true, false) => PollAction::DjDownvote(inter),
         (false, upvote) => PollAction::Cast {
@@ -225,15 +396,14 @@ fn generate_embed(
     topic: &Topic,
     author_name: &str,
     author_icon: String,
-    votes: &HashMap<Id<UserMarker>, Vote>,
-    threshold: usize,
+    ballot: &Ballot,
     latent: &LatentEmbedColours,
 ) -> Result<Embed, GenerateEmbedError> {
-    let embed_color = generate_embed_colour(votes, threshold, latent);
+    let embed_color = generate_embed_colour(ballot, latent);
     let embed = EmbedBuilder::new()
         .author(EmbedAuthorBuilder::new(author_name).icon_url(ImageSource::url(author_icon)?))
         .title(format!("{topic}?"))
-        .description(generate_poll_description(votes, threshold))
+        .description(generate_poll_description(ballot))
         .footer(EmbedFooterBuilder::new(
             "Cast your votes via pressing the buttons below",
         ))
@@ -325,12 +495,14 @@ async fn wait_for_poll_actions(
         event = rx.recv() => {
             Ok(match event? {
                 Event::AlternateVoteCast(id) => Some(PollAction::AlternateCast(id.into())),
-                Event::AlternateVoteDjCast => Some(PollAction::AlternateDjCast),
+                Event::AlternateVoteDjCast if ctx.dj.is_some() => Some(PollAction::AlternateDjCast),
                 e if ctx.topic.is_voided_by(&e) => Some(PollAction::Void(VoidingEvent::new(&e))),
                 _ => None
             })
         }
-        Some(inter) = ctx.components.next() => Ok(Some(handle_interactions(inter, &ctx.upvote_button_id))),
+        Some(inter) = ctx.components.next() => {
+            Ok(Some(handle_interactions(inter, &ctx.upvote_button_id, ctx.dj.as_ref())))
+        }
     }
 }
 
@@ -368,49 +540,8 @@ impl EmbedUpdate<'_> {
     }
 }
 
-fn calculate_votes_and_ratios(
-    votes: &HashMap<Id<UserMarker>, Vote>,
-    threshold: usize,
-) -> ((usize, usize), (f64, f64, f64)) {
-    let total_votes = votes.len();
-    let upvotes = votes
-        .values()
-        .copied()
-        .filter(|v| v.value().is_positive())
-        .count();
-    let downvotes = total_votes - upvotes;
-    let votes_left = threshold - total_votes;
-
-    let (threshold_f64, upvotes_f64, downvotes_f64, votes_left_f64) = (
-        usize_as_f64(threshold),
-        usize_as_f64(upvotes),
-        usize_as_f64(downvotes),
-        usize_as_f64(votes_left),
-    );
-
-    let upvote_ratio = upvotes_f64 / threshold_f64;
-    let downvote_ratio = downvotes_f64 / threshold_f64;
-    let votes_left_ratio = votes_left_f64 / threshold_f64;
-
-    (
-        (upvotes, downvotes),
-        (upvote_ratio, downvote_ratio, votes_left_ratio),
-    )
-}
-
-fn calculate_vote_ratios(
-    votes: &HashMap<Id<UserMarker>, Vote>,
-    threshold: usize,
-) -> (f64, f64, f64) {
-    calculate_votes_and_ratios(votes, threshold).1
-}
-
-fn generate_embed_colour(
-    votes: &HashMap<Id<UserMarker>, Vote>,
-    threshold: usize,
-    latent: &LatentEmbedColours,
-) -> [u8; 3] {
-    let (upvote_ratio, downvote_ratio, votes_left_ratio) = calculate_vote_ratios(votes, threshold);
+fn generate_embed_colour(ballot: &Ballot, latent: &LatentEmbedColours) -> [u8; 3] {
+    let (upvote_ratio, downvote_ratio, votes_left_ratio) = ballot.vote_ratios();
     let mut z_mix = [0.0; mixbox::LATENT_SIZE];
     for (i, z) in z_mix.iter_mut().enumerate() {
         *z = votes_left_ratio.mul_add(
@@ -427,15 +558,14 @@ fn generate_embed_colour(
 }
 
 async fn update_embed(
-    votes: &HashMap<Id<UserMarker>, Vote>,
-    threshold: usize,
+    ballot: &Ballot,
     embed_ctx: &UpdatePollEmbedContext,
     updater: EmbedUpdate<'_>,
 ) -> Result<(), UpdateEmbedError> {
-    let embed_color = generate_embed_colour(votes, threshold, &embed_ctx.latent);
+    let embed_color = generate_embed_colour(ballot, &embed_ctx.latent);
     let embed = EmbedBuilder::from(embed_ctx.embed.clone())
         .color(rgb_to_hex(embed_color))
-        .description(generate_poll_description(votes, threshold))
+        .description(generate_poll_description(ballot))
         .validate()?
         .build();
     updater.update_embed(embed).await?;
@@ -446,27 +576,23 @@ pub async fn start(
     topic: &Topic,
     ctx: &mut GuildCtx<impl RespondWithMessageKind>,
     in_voice: &PartialInVoice,
+    requester: Option<Id<UserMarker>>,
 ) -> Result<Resolution, StartPollError> {
+    let config = ctx.bot().guild_configs().get(in_voice.guild_id()).await?;
+    let rules = config.poll_rules(topic.kind());
+    let dj = rules.dj_override().then(|| config.dj().clone());
     let author_name = ctx.guild_display_name();
     let author_icon = ctx.guild_display_avatar_url();
     let embed_latent = generate_latent_embed_colours();
     let (upvote_button_id, row) = generate_upvote_button_id_and_row();
 
     let users_in_voice = get_users_in_voice(ctx, in_voice).await?;
-    let votes = HashMap::from([(ctx.user_id(), Vote(true))]);
-
-    let users_in_voice_plus_1 = usize_as_f64(users_in_voice.len() + 1);
-
-    let threshold = f64_as_isize((users_in_voice_plus_1 / 2.).round()).unsigned_abs();
+    let ballot = Ballot::new(ctx.user_id(), &users_in_voice, requester, rules);
+    if let Some(res) = ballot.resolution() {
+        return Ok(res);
+    }
 
-    let embed = generate_embed(
-        topic,
-        author_name,
-        author_icon,
-        &votes,
-        threshold,
-        &embed_latent,
-    )?;
+    let embed = generate_embed(topic, author_name, author_icon, &ballot, &embed_latent)?;
     let message = super::util::MessageLinkComponent::from(
         ctx.respond()
             .embeds([embed.clone()])
@@ -480,7 +606,7 @@ pub async fn start(
     {
         ctx.lavalink()
             .handle_for(in_voice.guild_id())
-            .set_poll(Poll::new(topic, message.clone()));
+            .set_poll(Poll::new(topic, message.clone(), rules.dj_override()));
     }
     let components = &mut ctx
         .bot()
@@ -497,37 +623,24 @@ pub async fn start(
         components,
         upvote_button_id,
         message,
+        dj,
     };
 
     Ok(Box::pin(wait_for_votes(
         poll_ctx,
         ctx,
         users_in_voice,
-        votes,
-        threshold,
+        ballot,
+        rules.timeout(),
         embed_ctx,
         in_voice,
     ))
     .await?)
 }
 
-fn calculate_vote_resolution(
-    votes: &HashMap<Id<UserMarker>, Vote>,
-    threshold: usize,
-) -> Option<Resolution> {
-    let res = votes.values().copied().map(Vote::value).sum::<isize>();
-    if res.unsigned_abs() == threshold {
-        if res.is_positive() {
-            return Some(Resolution::UnanimousWin);
-        }
-        return Some(Resolution::UnanimousLoss);
-    }
-    None
-}
-
-fn generate_poll_description(votes: &HashMap<Id<UserMarker>, Vote>, threshold: usize) -> String {
-    let ((upvotes, downvotes), (upvote_ratio, downvote_ratio, _)) =
-        calculate_votes_and_ratios(votes, threshold);
+fn generate_poll_description(ballot: &Ballot) -> String {
+    let ((upvotes, downvotes), (upvote_ratio, downvote_ratio, _)) = ballot.votes_and_ratios();
+    let threshold = ballot.threshold;
 
     let ratio_bar_size = usize_as_f64(RATIO_BAR_SIZE);
 
@@ -550,8 +663,8 @@ async fn wait_for_votes(
     mut poll_ctx: WaitForPollActionsContext<'_>,
     ctx: &GuildCtx<impl RespondWithMessageKind>,
     users_in_voice: HashSet<Id<UserMarker>>,
-    mut votes: HashMap<Id<UserMarker>, Vote>,
-    threshold: usize,
+    mut ballot: Ballot,
+    timeout: Duration,
     embed_ctx: UpdatePollEmbedContext,
     in_voice: &PartialInVoice,
 ) -> Result<Resolution, WaitForVotesError> {
@@ -561,9 +674,10 @@ async fn wait_for_votes(
         .subscribe()
         .await
         .expect("in_voice must have connection");
+    let deadline = tokio::time::Instant::now() + timeout;
     loop {
         let poll_stream = wait_for_poll_actions(&mut rx, &mut poll_ctx);
-        match tokio::time::timeout(Duration::from_secs(30), poll_stream).await {
+        match tokio::time::timeout_at(deadline, poll_stream).await {
             Ok(Ok(Some(action))) => match action {
                 PollAction::Cast {
                     user_id,
@@ -577,7 +691,7 @@ async fn wait_for_votes(
                         continue;
                     }
 
-                    match votes.entry(user_id) {
+                    match ballot.votes.entry(user_id) {
                         Entry::Vacant(e) => {
                             e.insert(vote);
                         }
@@ -588,17 +702,11 @@ async fn wait_for_votes(
                         }
                     }
 
-                    if let Some(res) = calculate_vote_resolution(&votes, threshold) {
+                    if let Some(res) = ballot.resolution() {
                         return Ok(res);
                     }
 
-                    update_embed(
-                        &votes,
-                        threshold,
-                        &embed_ctx,
-                        EmbedUpdate::InteractionResponse(i),
-                    )
-                    .await?;
+                    update_embed(&ballot, &embed_ctx, EmbedUpdate::InteractionResponse(i)).await?;
                 }
                 PollAction::AlternateCast(user_id) => {
                     let conn = ctx.lavalink().handle_for(in_voice.guild_id());
@@ -606,7 +714,7 @@ async fn wait_for_votes(
                         conn.dispatch(Event::AlternateVoteCastDenied);
                         continue;
                     }
-                    match votes.entry(user_id) {
+                    match ballot.votes.entry(user_id) {
                         Entry::Vacant(e) => {
                             e.insert(Vote(true));
                         }
@@ -616,13 +724,12 @@ async fn wait_for_votes(
                         }
                     }
 
-                    if let Some(res) = calculate_vote_resolution(&votes, threshold) {
+                    if let Some(res) = ballot.resolution() {
                         return Ok(res);
                     }
 
                     update_embed(
-                        &votes,
-                        threshold,
+                        &ballot,
                         &embed_ctx,
                         EmbedUpdate::Http {
                             client: ctx.http(),
@@ -651,3 +758,50 @@ async fn wait_for_votes(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use twilight_model::id::Id;
+
+    use crate::component::config::poll::{PollRules, Threshold};
+
+    use super::{Ballot, Resolution, Vote};
+
+    #[test]
+    fn ballot_counts_requester_double() {
+        let users = HashSet::from([Id::new(1), Id::new(2), Id::new(3)]);
+        let rules = PollRules::new(Threshold::Majority, true);
+        let mut ballot = Ballot::new(Id::new(1), &users, Some(Id::new(2)), rules);
+
+        // the requester's vote counts double, so there are 4 votes in total
+        assert_eq!(ballot.threshold, 3);
+        assert!(ballot.resolution().is_none());
+
+        ballot.votes.insert(Id::new(2), Vote(false));
+        assert_eq!(ballot.tally(), (1, 2));
+        assert!(ballot.resolution().is_none());
+
+        ballot.votes.insert(Id::new(3), Vote(false));
+        assert!(matches!(
+            ballot.resolution(),
+            Some(Resolution::UnanimousLoss)
+        ));
+    }
+
+    #[test]
+    fn ballot_counts_requester_once_unless_enabled_and_in_voice() {
+        let users = HashSet::from([Id::new(1), Id::new(2), Id::new(3)]);
+
+        let rules = PollRules::new(Threshold::Majority, false);
+        let ballot = Ballot::new(Id::new(1), &users, Some(Id::new(2)), rules);
+        assert_eq!(ballot.threshold, 2);
+        assert_eq!(ballot.weight_of(Id::new(2)), 1);
+
+        let rules = PollRules::new(Threshold::Majority, true);
+        let ballot = Ballot::new(Id::new(1), &users, Some(Id::new(4)), rules);
+        assert_eq!(ballot.threshold, 2);
+        assert_eq!(ballot.weight_of(Id::new(4)), 1);
+    }
+}
@@ -15,7 +15,7 @@ use twilight_model::{
 
 use crate::{
     LavalinkAndGuildIdAware,
-    core::model::{CacheAware, UserIdAware, UserPermissionsAware},
+    core::model::{CacheAware, UserDjAware, UserIdAware, UserPermissionsAware},
     error::{
         Cache, CacheResult, InVoiceWithoutSomeoneElse, NotInVoice, NotPlaying, QueueEmpty,
         Suppressed,
@@ -159,6 +159,7 @@ impl From<CachedVoiceStateRef<'_>> for InVoiceCachedVoiceState {
 pub struct InVoice<'a> {
     state: InVoiceCachedVoiceState,
     author_permissions: Permissions,
+    author_configured_dj: bool,
     pub author_id: Id<UserMarker>,
     pub cache: &'a InMemoryCache,
 }
@@ -199,11 +200,12 @@ pub fn in_voice<T: CtxKind>(ctx: &'_ GuildCtx<T>) -> Result<InVoice<'_>, NotInVo
 impl<'a> InVoice<'a> {
     pub fn new(
         state: InVoiceCachedVoiceState,
-        ctx: &'a (impl UserPermissionsAware + UserIdAware + CacheAware),
+        ctx: &'a (impl UserDjAware + UserIdAware + CacheAware),
     ) -> Self {
         Self {
             state,
             author_permissions: ctx.user_permissions(),
+            author_configured_dj: ctx.user_is_configured_dj(),
             author_id: ctx.user_id(),
             cache: ctx.cache(),
         }
@@ -257,6 +259,12 @@ impl UserPermissionsAware for InVoice<'_> {
     }
 }
 
+impl UserDjAware for InVoice<'_> {
+    fn user_is_configured_dj(&self) -> bool {
+        self.author_configured_dj
+    }
+}
+
 impl GuildIdAware for InVoice<'_> {
     fn guild_id(&self) -> Id<GuildMarker> {
         self.state.guild_id
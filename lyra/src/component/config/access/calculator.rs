@@ -0,0 +1,457 @@
+//! The access controls of guilds and whether they allow a member to use a command.
+//!
+//! This module only depends on external crates, so that `benches/access.rs` can include it.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use bitflags::bitflags;
+use const_str::concat as const_str_concat;
+use itertools::multizip;
+use moka::future::Cache;
+use sqlx::{Pool, Postgres};
+use twilight_model::{
+    channel::ChannelType,
+    id::{
+        Id,
+        marker::{ChannelMarker, GuildMarker, RoleMarker, UserMarker},
+    },
+};
+
+/// The access controls and command rules of a guild.
+pub struct GuildAccess {
+    categories: HashMap<AccessCategoryFlag, CategoryAccess>,
+    command_rules: Vec<CommandRule>,
+}
+
+pub type AccessCache = Cache<Id<GuildMarker>, Arc<GuildAccess>>;
+
+struct CategoryAccess {
+    mode: Option<bool>,
+    ids: HashSet<i64>,
+}
+
+impl CategoryAccess {
+    fn new(mode: Option<bool>, ids: Vec<i64>) -> Self {
+        Self {
+            mode,
+            ids: ids.into_iter().collect(),
+        }
+    }
+}
+
+impl GuildAccess {
+    pub async fn fetch(
+        db: &Pool<Postgres>,
+        guild_id: Id<GuildMarker>,
+    ) -> Result<Self, sqlx::Error> {
+        let rec = sqlx::query!(
+            r#"SELECT
+                usr_access, rol_access, xch_access, tch_access, vch_access, cch_access,
+                ARRAY(SELECT id FROM usr_access WHERE guild = $1) AS "usr_ids!",
+                ARRAY(SELECT id FROM rol_access WHERE guild = $1) AS "rol_ids!",
+                ARRAY(SELECT id FROM xch_access WHERE guild = $1) AS "xch_ids!",
+                ARRAY(SELECT id FROM tch_access WHERE guild = $1) AS "tch_ids!",
+                ARRAY(SELECT id FROM vch_access WHERE guild = $1) AS "vch_ids!",
+                ARRAY(SELECT id FROM cch_access WHERE guild = $1) AS "cch_ids!",
+                rules.commands AS "commands!",
+                rules.categories AS "categories!",
+                rules.ids AS "ids!",
+                rules.allows AS "allows!"
+            FROM guild_configs, LATERAL (
+                SELECT
+                    COALESCE(array_agg(command), '{}') AS commands,
+                    COALESCE(array_agg(category), '{}') AS categories,
+                    COALESCE(array_agg(id), '{}') AS ids,
+                    COALESCE(array_agg(allow), '{}') AS allows
+                FROM command_access WHERE guild = $1
+            ) AS rules
+            WHERE guild_configs.id = $1;"#,
+            guild_id.get().cast_signed(),
+        )
+        .fetch_one(db)
+        .await?;
+
+        let categories = HashMap::from([
+            (
+                AccessCategoryFlag::Users,
+                CategoryAccess::new(rec.usr_access, rec.usr_ids),
+            ),
+            (
+                AccessCategoryFlag::Roles,
+                CategoryAccess::new(rec.rol_access, rec.rol_ids),
+            ),
+            (
+                AccessCategoryFlag::Threads,
+                CategoryAccess::new(rec.xch_access, rec.xch_ids),
+            ),
+            (
+                AccessCategoryFlag::TextChannels,
+                CategoryAccess::new(rec.tch_access, rec.tch_ids),
+            ),
+            (
+                AccessCategoryFlag::VoiceChannels,
+                CategoryAccess::new(rec.vch_access, rec.vch_ids),
+            ),
+            (
+                AccessCategoryFlag::CategoryChannels,
+                CategoryAccess::new(rec.cch_access, rec.cch_ids),
+            ),
+        ]);
+        let command_rules = multizip((rec.commands, rec.categories, rec.ids, rec.allows))
+            .filter_map(|(command, category, id, allow)| {
+                Some(CommandRule {
+                    command: command.into(),
+                    category: AccessCategoryFlag::from_i16(category)?,
+                    id,
+                    allow,
+                })
+            })
+            .collect();
+
+        Ok(Self {
+            categories,
+            command_rules,
+        })
+    }
+
+    /// The cached access controls of a guild, fetching them if they are not cached.
+    pub async fn get(
+        cache: &AccessCache,
+        db: &Pool<Postgres>,
+        guild_id: Id<GuildMarker>,
+    ) -> Result<Arc<Self>, Arc<sqlx::Error>> {
+        cache
+            .try_get_with(guild_id, async {
+                Self::fetch(db, guild_id).await.map(Arc::new)
+            })
+            .await
+    }
+
+    /// Whether `ids` are allowed by the access mode of `category`.
+    ///
+    /// If `ids` is empty, they are considered to be in the access controls if there are any.
+    fn allows(&self, category: AccessCategoryFlag, ids: &[i64]) -> bool {
+        let CategoryAccess {
+            mode,
+            ids: controls,
+        } = &self.categories[&category];
+        mode.is_none_or(|mode| {
+            let in_access_controls = if ids.is_empty() {
+                !controls.is_empty()
+            } else {
+                ids.iter().any(|id| controls.contains(id))
+            };
+            mode == in_access_controls
+        })
+    }
+
+    /// Whether `command` is allowed for a member, with `ids` being the IDs of the member, their
+    /// roles and the channels the command is invoked in.
+    ///
+    /// The rules for the command groups `command` is in also apply. Members and roles are checked
+    /// separately from channels: a matching deny rule denies the command, and otherwise if there
+    /// are any allow rules, one of them must match.
+    fn allows_command(&self, command: &str, ids: &[i64]) -> bool {
+        let rules = self
+            .command_rules
+            .iter()
+            .filter(|r| {
+                command
+                    .strip_prefix(&*r.command)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with(' '))
+            })
+            .collect::<Vec<_>>();
+
+        [
+            AccessCategoryFlags::MENTIONABLES,
+            AccessCategoryFlags::ALL_CHANNELS,
+        ]
+        .into_iter()
+        .all(|categories| {
+            let mut has_allow_rules = false;
+            let mut allowed = false;
+            for rule in rules
+                .iter()
+                .filter(|r| categories.contains(r.category.into()))
+            {
+                has_allow_rules |= rule.allow;
+                if ids.contains(&rule.id) {
+                    if !rule.allow {
+                        return false;
+                    }
+                    allowed = true;
+                }
+            }
+            allowed || !has_allow_rules
+        })
+    }
+}
+
+struct CommandRule {
+    command: Box<str>,
+    category: AccessCategoryFlag,
+    id: i64,
+    allow: bool,
+}
+
+pub struct Calculator {
+    access: Arc<GuildAccess>,
+    checks: Vec<(AccessCategoryFlag, Vec<i64>)>,
+    ids: Vec<i64>,
+    command: Option<Box<str>>,
+}
+
+impl Calculator {
+    pub fn calculate(self) -> bool {
+        self.command
+            .is_none_or(|command| self.access.allows_command(&command, &self.ids))
+            && self
+                .checks
+                .iter()
+                .all(|(category, ids)| self.access.allows(*category, ids))
+    }
+}
+
+pub struct CalculatorBuilder {
+    pub(super) guild_id: Id<GuildMarker>,
+    checks: Vec<(AccessCategoryFlag, Vec<i64>)>,
+    ids: Vec<i64>,
+    command: Option<Box<str>>,
+}
+
+impl CalculatorBuilder {
+    pub fn new(guild_id: Id<GuildMarker>) -> Self {
+        Self {
+            guild_id,
+            checks: Vec::with_capacity(6),
+            // the `@everyone` role shares its ID with the guild
+            ids: vec![guild_id.get().cast_signed()],
+            command: None,
+        }
+    }
+
+    fn query(mut self, category: AccessCategoryFlag, id: i64) -> Self {
+        self.ids.push(id);
+        self.checks.push((category, vec![id]));
+        self
+    }
+
+    pub fn roles<'a>(mut self, role_ids: impl Iterator<Item = &'a Id<RoleMarker>>) -> Self {
+        let role_ids = role_ids
+            .map(|id| id.get().cast_signed())
+            .collect::<Vec<_>>();
+        self.ids.extend_from_slice(&role_ids);
+        self.checks.push((AccessCategoryFlag::Roles, role_ids));
+        self
+    }
+    pub fn user(self, user_id: Id<UserMarker>) -> Self {
+        let id = user_id.get().cast_signed();
+        self.query(AccessCategoryFlag::Users, id)
+    }
+
+    pub fn thread(self, thread_id: Id<ChannelMarker>) -> Self {
+        let id = thread_id.get().cast_signed();
+        self.query(AccessCategoryFlag::Threads, id)
+    }
+
+    pub fn text_channel(self, text_channel_id: Id<ChannelMarker>) -> Self {
+        let id = text_channel_id.get().cast_signed();
+        self.query(AccessCategoryFlag::TextChannels, id)
+    }
+
+    pub fn voice_channel(self, voice_channel_id: Id<ChannelMarker>) -> Self {
+        let id = voice_channel_id.get().cast_signed();
+        self.query(AccessCategoryFlag::VoiceChannels, id)
+    }
+
+    pub fn category_channel(self, category_channel_id: Id<ChannelMarker>) -> Self {
+        let id = category_channel_id.get().cast_signed();
+        self.query(AccessCategoryFlag::CategoryChannels, id)
+    }
+
+    /// Also applies the rules for `command`, the full name of the invoked command.
+    pub fn command(mut self, command: Box<str>) -> Self {
+        self.command = Some(command);
+        self
+    }
+
+    pub fn build_with(self, access: Arc<GuildAccess>) -> Calculator {
+        Calculator {
+            access,
+            checks: self.checks,
+            ids: self.ids,
+            command: self.command,
+        }
+    }
+}
+
+#[repr(u8)]
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+pub enum AccessCategoryFlag {
+    Users = 0b0000_0001,
+    Roles = 0b0000_0010,
+    Threads = 0b0000_0100,
+    TextChannels = 0b0000_1000,
+    VoiceChannels = 0b0001_0000,
+    CategoryChannels = 0b0010_0000,
+}
+
+impl From<AccessCategoryFlag> for AccessCategoryFlags {
+    fn from(value: AccessCategoryFlag) -> Self {
+        Self::from_bits_retain(value as u8)
+    }
+}
+
+impl TryFrom<AccessCategoryFlags> for AccessCategoryFlag {
+    type Error = AccessCategoryFlags;
+
+    fn try_from(value: AccessCategoryFlags) -> Result<Self, Self::Error> {
+        match value.bits() {
+            x if x == Self::Users as u8 => Ok(Self::Users),
+            x if x == Self::Roles as u8 => Ok(Self::Roles),
+            x if x == Self::Threads as u8 => Ok(Self::Threads),
+            x if x == Self::TextChannels as u8 => Ok(Self::TextChannels),
+            x if x == Self::VoiceChannels as u8 => Ok(Self::VoiceChannels),
+            x if x == Self::CategoryChannels as u8 => Ok(Self::CategoryChannels),
+            _ => Err(value),
+        }
+    }
+}
+
+impl AccessCategoryFlag {
+    pub const fn from_i16(value: i16) -> Option<Self> {
+        Some(match value {
+            0b0000_0001 => Self::Users,
+            0b0000_0010 => Self::Roles,
+            0b0000_0100 => Self::Threads,
+            0b0000_1000 => Self::TextChannels,
+            0b0001_0000 => Self::VoiceChannels,
+            0b0010_0000 => Self::CategoryChannels,
+            _ => return None,
+        })
+    }
+
+    pub const fn of_channel(kind: ChannelType) -> Self {
+        match kind {
+            ChannelType::PublicThread
+            | ChannelType::PrivateThread
+            | ChannelType::AnnouncementThread => Self::Threads,
+            ChannelType::GuildVoice | ChannelType::GuildStageVoice => Self::VoiceChannels,
+            ChannelType::GuildCategory => Self::CategoryChannels,
+            _ => Self::TextChannels,
+        }
+    }
+
+    pub const fn ident(self) -> &'static str {
+        const POSTFIX: &str = "_access";
+        macro_rules! concat_postfix {
+            ($postfix:expr) => {
+                const_str_concat!($postfix, POSTFIX)
+            };
+        }
+
+        match self {
+            Self::Users => concat_postfix!("usr"),
+            Self::Roles => concat_postfix!("rol"),
+            Self::Threads => concat_postfix!("xch"),
+            Self::TextChannels => concat_postfix!("tch"),
+            Self::VoiceChannels => concat_postfix!("vch"),
+            Self::CategoryChannels => concat_postfix!("cch"),
+        }
+    }
+}
+
+bitflags! {
+    pub struct AccessCategoryFlags: u8 {
+        const USERS = 0b0000_0001;
+        const ROLES = 0b0000_0010;
+        const THREADS = 0b0000_0100;
+        const TEXT_CHANNELS = 0b0000_1000;
+        const VOICE_CHANNELS = 0b0001_0000;
+        const CATEGORY_CHANNELS = 0b0010_0000;
+
+        const MENTIONABLES = Self::USERS.bits() | Self::ROLES.bits();
+        const ALL_CHANNELS = Self::THREADS.bits()
+            | Self::TEXT_CHANNELS.bits()
+            | Self::VOICE_CHANNELS.bits()
+            | Self::CATEGORY_CHANNELS.bits();
+
+        const ALL = Self::MENTIONABLES.bits()
+            | Self::ALL_CHANNELS.bits();
+    }
+}
+
+impl AccessCategoryFlags {
+    pub fn iter_each(&self) -> impl Iterator<Item = AccessCategoryFlag> {
+        self.iter().flat_map(Self::try_into)
+    }
+
+    pub fn iter_as_columns(&self) -> impl Iterator<Item = &'static str> {
+        self.iter_each().map(AccessCategoryFlag::ident)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{AccessCategoryFlag, CommandRule, GuildAccess};
+
+    fn access(rules: &[(&str, AccessCategoryFlag, i64, bool)]) -> GuildAccess {
+        GuildAccess {
+            categories: HashMap::new(),
+            command_rules: rules
+                .iter()
+                .map(|&(command, category, id, allow)| CommandRule {
+                    command: command.into(),
+                    category,
+                    id,
+                    allow,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn command_rules_match_whole_command_groups() {
+        let access = access(&[("queue", AccessCategoryFlag::Users, 1, false)]);
+
+        assert!(!access.allows_command("queue", &[1]));
+        assert!(!access.allows_command("queue export", &[1]));
+        assert!(access.allows_command("queuex", &[1]));
+        assert!(access.allows_command("play", &[1]));
+        assert!(access.allows_command("queue", &[2]));
+    }
+
+    #[test]
+    fn command_allow_rules_require_a_match() {
+        let access = access(&[("queue", AccessCategoryFlag::Roles, 5, true)]);
+
+        assert!(access.allows_command("queue export", &[1, 5]));
+        assert!(!access.allows_command("queue export", &[1, 6]));
+        assert!(access.allows_command("queuex", &[1, 6]));
+    }
+
+    #[test]
+    fn command_deny_rules_override_allow_rules() {
+        let access = access(&[
+            ("queue", AccessCategoryFlag::Users, 1, true),
+            ("queue export", AccessCategoryFlag::Roles, 2, false),
+            ("queue", AccessCategoryFlag::TextChannels, 10, true),
+            ("queue", AccessCategoryFlag::CategoryChannels, 20, false),
+        ]);
+
+        // mentionables
+        assert!(access.allows_command("queue export", &[1, 10]));
+        assert!(!access.allows_command("queue export", &[1, 2, 10]));
+        assert!(access.allows_command("queue", &[1, 2, 10]));
+        assert!(!access.allows_command("queue", &[3, 10]));
+
+        // channels
+        assert!(!access.allows_command("queue", &[1, 10, 20]));
+        assert!(!access.allows_command("queue", &[1, 30]));
+    }
+}
@@ -12,7 +12,9 @@ use crate::{
     component::config::access::AccessCategoryFlags,
     core::{
         konst::text::NO_ROWS_AFFECTED_MESSAGE,
-        model::{DatabaseAware, response::initial::message::create::RespondWithMessage},
+        model::{
+            BotStateAware, DatabaseAware, response::initial::message::create::RespondWithMessage,
+        },
     },
     error::CommandResult,
     gateway::GuildIdAware,
@@ -32,6 +34,12 @@ impl BotGuildSlashCommand for Clear {
 
         let category_flags = AccessCategoryFlags::from(self.category);
 
+        let (mut ctx, confirmed) = prompt_for_confirmation(ctx).await?;
+        if !confirmed {
+            ctx.note("Cancelled executing this command.").await?;
+            return Ok(());
+        }
+
         let mut set = JoinSet::new();
         category_flags.iter_as_columns().for_each(|c| {
             let db = ctx.db().clone();
@@ -45,12 +53,6 @@ impl BotGuildSlashCommand for Clear {
             });
         });
 
-        let (mut ctx, confirmed) = prompt_for_confirmation(ctx).await?;
-        if !confirmed {
-            ctx.note("Cancelled executing this command.").await?;
-            return Ok(());
-        }
-
         let mut rows_affected = 0;
         while let Some(res) = set.join_next().await {
             let res = res??;
@@ -61,6 +63,7 @@ impl BotGuildSlashCommand for Clear {
             ctx.susp(NO_ROWS_AFFECTED_MESSAGE).await?;
             return Ok(());
         }
+        ctx.bot().access_cache().invalidate(&ctx.guild_id()).await;
 
         ctx.out(format!(
             "🔐🧹 Cleared all access controls for **{}**.",
@@ -0,0 +1,234 @@
+use itertools::Itertools;
+use sqlx::{Pool, Postgres};
+use twilight_interactions::command::{
+    CommandModel, CommandOption, CreateCommand, CreateOption, ResolvedMentionable,
+};
+use twilight_mention::Mention;
+use twilight_model::{
+    application::{
+        command::{CommandOptionType, CommandType},
+        interaction::InteractionChannel,
+    },
+    channel::message::embed::EmbedField,
+    id::{
+        Id,
+        marker::{ChannelMarker, GuildMarker, RoleMarker, UserMarker},
+    },
+};
+use twilight_util::builder::embed::EmbedFieldBuilder;
+
+use super::{AccessCategoryFlag, mode::AccessModeDisplay};
+use crate::{
+    command::{
+        check,
+        declare::COMMANDS,
+        model::{BotGuildSlashCommand, GuildSlashCmdCtx},
+    },
+    core::{
+        konst::text::{EMPTY_EMBED_FIELD, NO_ROWS_AFFECTED_MESSAGE},
+        model::{
+            BotStateAware, DatabaseAware, response::initial::message::create::RespondWithMessage,
+        },
+    },
+    error::CommandResult,
+    gateway::GuildIdAware,
+};
+
+const EMBED_FIELD_VALUE_LIMIT: usize = 1024;
+
+/// Normalises `input` into the full name of a command or command group, if there is one.
+fn find_command(input: &str) -> Option<String> {
+    let command = input
+        .trim_start_matches('/')
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .join(" ");
+
+    let mut names = command.split(' ');
+    let root = names.next()?;
+    let mut options = &COMMANDS
+        .iter()
+        .find(|c| c.kind == CommandType::ChatInput && c.name == root)?
+        .options[..];
+    for name in names {
+        options = options
+            .iter()
+            .find(|o| {
+                matches!(
+                    o.kind,
+                    CommandOptionType::SubCommand | CommandOptionType::SubCommandGroup
+                ) && o.name == name
+            })?
+            .options
+            .as_deref()
+            .unwrap_or_default();
+    }
+    Some(command)
+}
+
+fn mention(category: AccessCategoryFlag, id: i64) -> String {
+    let id = id.cast_unsigned();
+    match category {
+        AccessCategoryFlag::Users => Id::<UserMarker>::new(id).mention().to_string(),
+        AccessCategoryFlag::Roles => Id::<RoleMarker>::new(id).mention().to_string(),
+        _ => Id::<ChannelMarker>::new(id).mention().to_string(),
+    }
+}
+
+/// Generates the embed field listing all command rules in a guild.
+pub(super) async fn rules_field(
+    db: &Pool<Postgres>,
+    guild_id: Id<GuildMarker>,
+) -> Result<EmbedField, sqlx::Error> {
+    let rules = sqlx::query!(
+        "SELECT command, category, id, allow FROM command_access
+        WHERE guild = $1 ORDER BY command, allow DESC;",
+        guild_id.get().cast_signed(),
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut value = String::new();
+    for (command, rules) in &rules.into_iter().chunk_by(|r| r.command.clone()) {
+        let rules = rules
+            .filter_map(|r| {
+                let category = AccessCategoryFlag::from_i16(r.category)?;
+                Some(format!(
+                    "{}{}",
+                    Some(r.allow).display_icon(),
+                    mention(category, r.id)
+                ))
+            })
+            .join(" ");
+        let line = format!("`/{command}` » {rules}\n");
+        if value.len() + line.len() > EMBED_FIELD_VALUE_LIMIT {
+            break;
+        }
+        value.push_str(&line);
+    }
+    if value.is_empty() {
+        value.push_str(EMPTY_EMBED_FIELD);
+    }
+
+    Ok(EmbedFieldBuilder::new("📜 Command Rules", value).build())
+}
+
+#[derive(CommandOption, CreateOption)]
+enum CommandRuleAction {
+    #[option(name = "Allow", value = 0)]
+    Allow,
+    #[option(name = "Deny", value = 1)]
+    Deny,
+    #[option(name = "Remove Rule", value = 2)]
+    Remove,
+}
+
+/// Edits the rules for who can use a command or command group, or where.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "edit-command")]
+pub struct EditCommand {
+    /// Which command or command group? [like play or queue export]
+    #[command(min_length = 1, max_length = 100)]
+    command: String,
+    /// Do what?
+    action: CommandRuleAction,
+    /// ... for whom/which role?
+    member_or_role: Option<ResolvedMentionable>,
+    /// ... for which channel?
+    channel: Option<InteractionChannel>,
+}
+
+impl BotGuildSlashCommand for EditCommand {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        check::user_is_access_manager(&ctx)?;
+
+        let Some(command) = find_command(&self.command) else {
+            ctx.wrng(format!(
+                "**Unknown command: `{}`**; \
+                Command must be the name of a command or command group like `play` or `queue export`.",
+                self.command
+            ))
+            .await?;
+            return Ok(());
+        };
+
+        let mentionable = self.member_or_role.map(|v| {
+            let flag = match v {
+                ResolvedMentionable::User(_) => AccessCategoryFlag::Users,
+                ResolvedMentionable::Role(_) => AccessCategoryFlag::Roles,
+            };
+            (flag, v.id().get().cast_signed())
+        });
+        let channel = self.channel.map(|v| {
+            (
+                AccessCategoryFlag::of_channel(v.kind),
+                v.id.get().cast_signed(),
+            )
+        });
+        let targets = [mentionable, channel]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        if targets.is_empty() {
+            ctx.wrng("Please specify a member, role or channel for this rule.")
+                .await?;
+            return Ok(());
+        }
+
+        let (categories, ids): (Vec<_>, Vec<_>) = targets
+            .iter()
+            .map(|&(category, id)| (i16::from(category as u8), id))
+            .unzip();
+        let guild_id = ctx.guild_id().get().cast_signed();
+        let rows_affected = match self.action {
+            CommandRuleAction::Allow | CommandRuleAction::Deny => {
+                sqlx::query!(
+                    "INSERT INTO command_access (guild, command, category, id, allow)
+                    SELECT $1, $2, t.category, t.id, $5
+                    FROM UNNEST($3::smallint[], $4::bigint[]) AS t (category, id)
+                    ON CONFLICT (guild, command, id) DO UPDATE SET allow = EXCLUDED.allow
+                    WHERE command_access.allow != EXCLUDED.allow;",
+                    guild_id,
+                    command,
+                    &categories,
+                    &ids,
+                    matches!(self.action, CommandRuleAction::Allow),
+                )
+                .execute(ctx.db())
+                .await?
+            }
+            CommandRuleAction::Remove => sqlx::query!(
+                "DELETE FROM command_access WHERE guild = $1 AND command = $2 AND id = ANY($3);",
+                guild_id,
+                command,
+                &ids,
+            )
+            .execute(ctx.db())
+            .await?,
+        }
+        .rows_affected();
+
+        if rows_affected == 0 {
+            ctx.susp(NO_ROWS_AFFECTED_MESSAGE).await?;
+            return Ok(());
+        }
+        ctx.bot().access_cache().invalidate(&ctx.guild_id()).await;
+
+        let targets = targets
+            .into_iter()
+            .map(|(category, id)| mention(category, id))
+            .join(" and ");
+        let message = match self.action {
+            CommandRuleAction::Allow => format!(
+                "🔐🟩 Allowed **`/{command}`** for {targets}.\n\
+                -# Once there are rules allowing a command, it can only be used as allowed."
+            ),
+            CommandRuleAction::Deny => format!("🔐🟥 Denied **`/{command}`** for {targets}."),
+            CommandRuleAction::Remove => {
+                format!("🔐**`－`** Removed the rules for **`/{command}`** for {targets}.")
+            }
+        };
+        ctx.out(message).await?;
+        Ok(())
+    }
+}
@@ -8,7 +8,6 @@ use twilight_interactions::command::{
 };
 use twilight_model::{
     application::interaction::InteractionChannel,
-    channel::ChannelType,
     id::{
         Id,
         marker::{ChannelMarker, GenericMarker},
@@ -23,7 +22,9 @@ use crate::{
     },
     core::{
         konst::text::NO_ROWS_AFFECTED_MESSAGE,
-        model::{DatabaseAware, response::initial::message::create::RespondWithMessage},
+        model::{
+            BotStateAware, DatabaseAware, response::initial::message::create::RespondWithMessage,
+        },
     },
     error::CommandResult,
     gateway::GuildIdAware,
@@ -39,7 +40,7 @@ impl AccessCategoryMarker for ChannelMarker {}
 fn add_access<T: AccessCategoryMarker>(
     join_set: &mut SqlxResultJoinSet,
     database: Pool<Postgres>,
-    category: &AccessCategoryFlag,
+    category: AccessCategoryFlag,
     guild_id: i64,
     ids: impl IntoIterator<Item = Id<T>>,
 ) {
@@ -67,7 +68,7 @@ fn add_access<T: AccessCategoryMarker>(
 fn remove_access<T: AccessCategoryMarker>(
     join_set: &mut SqlxResultJoinSet,
     database: Pool<Postgres>,
-    category: &AccessCategoryFlag,
+    category: AccessCategoryFlag,
     guild_id: i64,
     ids: impl IntoIterator<Item = Id<T>>,
 ) {
@@ -185,24 +186,12 @@ impl BotGuildSlashCommand for MemberRole {
         match self.action {
             EditAction::Add => {
                 for (category, mentionables) in input_mentionables {
-                    add_access(
-                        &mut set,
-                        database.clone(),
-                        &category,
-                        guild_id,
-                        mentionables,
-                    );
+                    add_access(&mut set, database.clone(), category, guild_id, mentionables);
                 }
             }
             EditAction::Remove => {
                 for (category, mentionables) in input_mentionables {
-                    remove_access(
-                        &mut set,
-                        database.clone(),
-                        &category,
-                        guild_id,
-                        mentionables,
-                    );
+                    remove_access(&mut set, database.clone(), category, guild_id, mentionables);
                 }
             }
         }
@@ -217,6 +206,7 @@ impl BotGuildSlashCommand for MemberRole {
             ctx.susp(NO_ROWS_AFFECTED_MESSAGE).await?;
             return Ok(());
         }
+        ctx.bot().access_cache().invalidate(&ctx.guild_id()).await;
 
         let ignored_changes = input_mentionables_len as u64 - rows_affected;
         let ignored_changes_notice = match ignored_changes {
@@ -274,19 +264,7 @@ impl BotGuildSlashCommand for Channel {
         ]
         .into_iter()
         .flatten()
-        .map(|v| {
-            let flag = match v.kind {
-                ChannelType::PublicThread
-                | ChannelType::PrivateThread
-                | ChannelType::AnnouncementThread => AccessCategoryFlag::Threads,
-                ChannelType::GuildVoice | ChannelType::GuildStageVoice => {
-                    AccessCategoryFlag::VoiceChannels
-                }
-                ChannelType::GuildCategory => AccessCategoryFlag::CategoryChannels,
-                _ => AccessCategoryFlag::TextChannels,
-            };
-            (flag, v.id)
-        })
+        .map(|v| (AccessCategoryFlag::of_channel(v.kind), v.id))
         .fold(HashMap::new(), |mut acc, (k, v)| {
             acc.entry(k).or_default().insert(v);
             acc
@@ -300,12 +278,12 @@ impl BotGuildSlashCommand for Channel {
         match self.action {
             EditAction::Add => {
                 for (category, channels) in input_channels {
-                    add_access(&mut set, database.clone(), &category, guild_id, channels);
+                    add_access(&mut set, database.clone(), category, guild_id, channels);
                 }
             }
             EditAction::Remove => {
                 for (category, channels) in input_channels {
-                    remove_access(&mut set, database.clone(), &category, guild_id, channels);
+                    remove_access(&mut set, database.clone(), category, guild_id, channels);
                 }
             }
         }
@@ -320,6 +298,7 @@ impl BotGuildSlashCommand for Channel {
             ctx.susp(NO_ROWS_AFFECTED_MESSAGE).await?;
             return Ok(());
         }
+        ctx.bot().access_cache().invalidate(&ctx.guild_id()).await;
 
         let ignored_changes = input_channels_len as u64 - rows_affected;
         let ignored_changes_notice = match ignored_changes {
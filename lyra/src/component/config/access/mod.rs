@@ -1,233 +1,33 @@
+mod calculator;
 mod clear;
+mod command;
 mod edit;
 mod mode;
 mod view;
 
-use bitflags::bitflags;
-use const_str::concat as const_str_concat;
-use itertools::Itertools;
-use lyra_ext::logical_bind::LogicalBind;
-use sqlx::{Pool, Postgres};
-use tokio::task::JoinSet;
 use twilight_interactions::command::{CommandModel, CommandOption, CreateCommand, CreateOption};
-use twilight_model::id::{
-    Id,
-    marker::{ChannelMarker, GuildMarker, RoleMarker, UserMarker},
-};
 
+use self::calculator::{AccessCategoryFlag, AccessCategoryFlags};
 pub use self::{
+    calculator::{AccessCache, Calculator, CalculatorBuilder, GuildAccess},
     clear::Clear,
+    command::EditCommand,
     edit::{Channel as EditChannel, MemberRole as EditMemberRole},
     mode::Mode,
     view::View,
 };
-use crate::error::command::check::AccessCalculatorBuildError;
+use crate::{
+    core::model::{BotState, DatabaseAware},
+    error::command::check::AccessCalculatorBuildError,
+};
 use lyra_proc::BotGuildCommandGroup;
 
-struct AccessState {
-    in_access_controls: bool,
-    access_mode: Option<bool>,
-}
-
-pub struct Calculator {
-    pairs: Vec<AccessState>,
-}
-
-impl Calculator {
-    pub fn calculate(self) -> bool {
-        self.pairs.into_iter().all(
-            |AccessState {
-                 in_access_controls,
-                 access_mode,
-             }| {
-                access_mode.is_none_or(|access_mode| access_mode == in_access_controls)
-            },
-        )
-    }
-}
-
-pub struct CalculatorBuilder {
-    set: JoinSet<Result<AccessState, sqlx::Error>>,
-    db: Pool<Postgres>,
-    guild_id: i64,
-}
-
 impl CalculatorBuilder {
-    pub fn new(guild_id: Id<GuildMarker>, db: Pool<Postgres>) -> Self {
-        let guild_id = guild_id.get().cast_signed();
-        Self {
-            set: JoinSet::new(),
-            db,
-            guild_id,
-        }
-    }
-
-    fn query(mut self, category: &AccessCategoryFlag, id: i64) -> Self {
-        let column = category.ident();
-        let db = self.db.clone();
-        self.set.spawn(async move {
-            let in_access_controls = sqlx::query_as::<_, (Option<bool>,)>(&format!(
-                "SELECT EXISTS (SELECT 1 FROM {column} WHERE guild = $1 AND id = $2)"
-            ))
-            .bind(self.guild_id)
-            .bind(id)
-            .fetch_one(&db)
-            .await?
-            .0
-            .expect("sql existence queries must be non-null");
-
-            let (access_mode,) = sqlx::query_as::<_, (Option<bool>,)>(&format!(
-                "SELECT {column} FROM guild_configs WHERE id = $1"
-            ))
-            .bind(self.guild_id)
-            .fetch_one(&db)
-            .await?;
-
-            Ok(AccessState {
-                in_access_controls,
-                access_mode,
-            })
-        });
-        self
-    }
-
-    pub fn roles<'a>(mut self, role_ids: impl Iterator<Item = &'a Id<RoleMarker>>) -> Self {
-        let column = AccessCategoryFlag::Roles.ident();
-        let db = self.db.clone();
-        let where_clause = role_ids.map(|id| format!("id = {id}")).join(" OR ");
-        self.set.spawn(async move {
-            let (Some(in_access_controls),) = sqlx::query_as::<_, (Option<bool>,)>(&format!(
-                "SELECT EXISTS (SELECT 1 FROM {column} WHERE guild = $1 AND ({}))",
-                where_clause.or("true")
-            ))
-            .bind(self.guild_id)
-            .fetch_one(&db)
-            .await?
-            else {
-                panic!("`exists` is `NULL`")
-            };
-
-            let access_mode = sqlx::query!(
-                "SELECT rol_access FROM guild_configs WHERE id = $1",
-                self.guild_id
-            )
-            .fetch_one(&db)
-            .await?
-            .rol_access;
-
-            Ok(AccessState {
-                in_access_controls,
-                access_mode,
-            })
-        });
-        self
-    }
-
-    pub fn user(self, user_id: Id<UserMarker>) -> Self {
-        let id = user_id.get().cast_signed();
-        self.query(&AccessCategoryFlag::Users, id)
-    }
-
-    pub fn thread(self, thread_id: Id<ChannelMarker>) -> Self {
-        let id = thread_id.get().cast_signed();
-        self.query(&AccessCategoryFlag::Threads, id)
-    }
-
-    pub fn text_channel(self, text_channel_id: Id<ChannelMarker>) -> Self {
-        let id = text_channel_id.get().cast_signed();
-        self.query(&AccessCategoryFlag::TextChannels, id)
-    }
-
-    pub fn voice_channel(self, voice_channel_id: Id<ChannelMarker>) -> Self {
-        let id = voice_channel_id.get().cast_signed();
-        self.query(&AccessCategoryFlag::VoiceChannels, id)
-    }
-
-    pub fn category_channel(self, category_channel_id: Id<ChannelMarker>) -> Self {
-        let id = category_channel_id.get().cast_signed();
-        self.query(&AccessCategoryFlag::CategoryChannels, id)
-    }
-
-    pub async fn build(mut self) -> Result<Calculator, AccessCalculatorBuildError> {
-        let mut pairs = Vec::with_capacity(6);
-        while let Some(res) = self.set.join_next().await {
-            pairs.push(res??);
-        }
-
-        Ok(Calculator { pairs })
-    }
-}
-
-#[repr(u8)]
-#[derive(PartialEq, Eq, Hash)]
-enum AccessCategoryFlag {
-    Users = 0b0000_0001,
-    Roles = 0b0000_0010,
-    Threads = 0b0000_0100,
-    TextChannels = 0b0000_1000,
-    VoiceChannels = 0b0001_0000,
-    CategoryChannels = 0b0010_0000,
-}
-
-impl From<AccessCategoryFlag> for AccessCategoryFlags {
-    fn from(value: AccessCategoryFlag) -> Self {
-        Self::from_bits_retain(value as u8)
-    }
-}
-
-impl TryFrom<AccessCategoryFlags> for AccessCategoryFlag {
-    type Error = AccessCategoryFlags;
-
-    fn try_from(value: AccessCategoryFlags) -> Result<Self, Self::Error> {
-        match value.bits() {
-            x if x == Self::Users as u8 => Ok(Self::Users),
-            x if x == Self::Roles as u8 => Ok(Self::Roles),
-            x if x == Self::Threads as u8 => Ok(Self::Threads),
-            x if x == Self::TextChannels as u8 => Ok(Self::TextChannels),
-            x if x == Self::VoiceChannels as u8 => Ok(Self::VoiceChannels),
-            x if x == Self::CategoryChannels as u8 => Ok(Self::CategoryChannels),
-            _ => Err(value),
-        }
-    }
-}
-
-impl AccessCategoryFlag {
-    const fn ident(&self) -> &'static str {
-        const POSTFIX: &str = "_access";
-        macro_rules! concat_postfix {
-            ($postfix:expr) => {
-                const_str_concat!($postfix, POSTFIX)
-            };
-        }
-
-        match self {
-            Self::Users => concat_postfix!("usr"),
-            Self::Roles => concat_postfix!("rol"),
-            Self::Threads => concat_postfix!("xch"),
-            Self::TextChannels => concat_postfix!("tch"),
-            Self::VoiceChannels => concat_postfix!("vch"),
-            Self::CategoryChannels => concat_postfix!("cch"),
-        }
-    }
-}
-
-bitflags! {
-    struct AccessCategoryFlags: u8 {
-        const USERS = 0b0000_0001;
-        const ROLES = 0b0000_0010;
-        const THREADS = 0b0000_0100;
-        const TEXT_CHANNELS = 0b0000_1000;
-        const VOICE_CHANNELS = 0b0001_0000;
-        const CATEGORY_CHANNELS = 0b0010_0000;
-
-        const MENTIONABLES = Self::USERS.bits() | Self::ROLES.bits();
-        const ALL_CHANNELS = Self::THREADS.bits()
-            | Self::TEXT_CHANNELS.bits()
-            | Self::VOICE_CHANNELS.bits()
-            | Self::CATEGORY_CHANNELS.bits();
-
-        const ALL = Self::MENTIONABLES.bits()
-            | Self::ALL_CHANNELS.bits();
+    /// Builds the calculator from the cached access controls of the guild, fetching them in a
+    /// single query if they are not cached.
+    pub async fn build(self, bot: &BotState) -> Result<Calculator, AccessCalculatorBuildError> {
+        let access = GuildAccess::get(bot.access_cache(), bot.db(), self.guild_id).await?;
+        Ok(self.build_with(access))
     }
 }
 
@@ -238,16 +38,6 @@ impl From<AccessCategory> for AccessCategoryFlags {
     }
 }
 
-impl AccessCategoryFlags {
-    pub fn iter_each(&self) -> impl Iterator<Item = AccessCategoryFlag> {
-        self.iter().flat_map(Self::try_into)
-    }
-
-    pub fn iter_as_columns(&self) -> impl Iterator<Item = &'static str> {
-        self.iter_each().map(|f| f.ident())
-    }
-}
-
 #[derive(CommandModel, CreateCommand, BotGuildCommandGroup)]
 #[command(name = "access", desc = ".")]
 pub enum Access {
@@ -261,6 +51,8 @@ pub enum Access {
     Mode(Mode),
     #[command(name = "clear")]
     Clear(Clear),
+    #[command(name = "edit-command")]
+    EditCommand(Box<EditCommand>),
 }
 
 #[derive(CommandOption, CreateOption)]
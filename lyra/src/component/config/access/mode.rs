@@ -13,7 +13,9 @@ use crate::{
     component::config::access::AccessCategoryFlags,
     core::{
         konst::text::NO_ROWS_AFFECTED_MESSAGE,
-        model::{DatabaseAware, response::initial::message::create::RespondWithMessage},
+        model::{
+            BotStateAware, DatabaseAware, response::initial::message::create::RespondWithMessage,
+        },
     },
     error::CommandResult,
     gateway::GuildIdAware,
@@ -129,6 +131,7 @@ impl BotGuildSlashCommand for Mode {
             ctx.susp(NO_ROWS_AFFECTED_MESSAGE).await?;
             return Ok(());
         }
+        ctx.bot().access_cache().invalidate(&ctx.guild_id()).await;
 
         let set_unset = access_mode.map_or("Unset", |_| "Set");
         let set_to = access_mode.map_or(String::new(), |m| {
@@ -30,6 +30,7 @@ impl BotGuildSlashCommand for View {
             voice_channels,
             category_channels
         );
+        let embed = embed.field(super::command::rules_field(db, ctx.guild_id()).await?);
         let embed = embed.validate()?.build();
 
         ctx.respond().embeds([embed]).await?;
@@ -0,0 +1,245 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+use lyra_proc::BotGuildCommandGroup;
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_mention::Mention;
+use twilight_model::id::{Id, marker::RoleMarker};
+
+use crate::{
+    command::model::{BotGuildSlashCommand, GuildSlashCmdCtx},
+    core::{
+        konst::text::NO_ROWS_AFFECTED_MESSAGE,
+        model::{
+            BotStateAware, DatabaseAware, response::initial::message::create::RespondWithMessage,
+        },
+    },
+    error::CommandResult,
+    gateway::GuildIdAware,
+};
+
+/// Who are considered DJs in a guild, other than members with DJ permissions.
+#[derive(Clone)]
+pub struct DjSettings {
+    roles: HashSet<Id<RoleMarker>>,
+    when_alone: bool,
+}
+
+impl DjSettings {
+    pub(super) fn from_columns(roles: Vec<i64>, when_alone: bool) -> Self {
+        Self {
+            roles: roles
+                .into_iter()
+                .map(|id| Id::new(id.cast_unsigned()))
+                .collect(),
+            when_alone,
+        }
+    }
+
+    /// Whether a member with `roles` is a DJ, with `alone_in_voice` checking whether the member
+    /// is in voice without anyone else.
+    pub fn is_dj(&self, roles: &[Id<RoleMarker>], alone_in_voice: impl FnOnce() -> bool) -> bool {
+        roles.iter().any(|r| self.roles.contains(r)) || (self.when_alone && alone_in_voice())
+    }
+}
+
+#[derive(CommandModel, CreateCommand, BotGuildCommandGroup)]
+#[command(name = "dj", desc = ".")]
+pub enum Dj {
+    #[command(name = "view")]
+    View(View),
+    #[command(name = "add")]
+    Add(Add),
+    #[command(name = "remove")]
+    Remove(Remove),
+    #[command(name = "alone")]
+    Alone(Alone),
+}
+
+/// Views who are considered DJs.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "view")]
+pub struct View;
+
+impl BotGuildSlashCommand for View {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let config = ctx.bot().guild_configs().get(ctx.guild_id()).await?;
+        let settings = config.dj();
+
+        let roles = if settings.roles.is_empty() {
+            String::from("`None`")
+        } else {
+            settings
+                .roles
+                .iter()
+                .map(|r| r.mention().to_string())
+                .join(" ")
+        };
+        ctx.out(format!(
+            "🎧 **DJs**\n\
+            - Roles: {roles}\n\
+            - Everyone when alone in voice: `{}`\n\
+            -# Members with the Move Members and Mute Members permissions are always DJs.",
+            if settings.when_alone { "Yes" } else { "No" },
+        ))
+        .await?;
+        Ok(())
+    }
+}
+
+/// Adds roles whose members are considered DJs.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "add")]
+pub struct Add {
+    /// Which role?
+    role: Id<RoleMarker>,
+    /// Which role? (2)
+    role_2: Option<Id<RoleMarker>>,
+    /// Which role? (3)
+    role_3: Option<Id<RoleMarker>>,
+    /// Which role? (4)
+    role_4: Option<Id<RoleMarker>>,
+    /// Which role? (5)
+    role_5: Option<Id<RoleMarker>>,
+}
+
+impl BotGuildSlashCommand for Add {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let roles = [
+            Some(self.role),
+            self.role_2,
+            self.role_3,
+            self.role_4,
+            self.role_5,
+        ]
+        .into_iter()
+        .flatten()
+        .unique()
+        .map(|r| r.get().cast_signed())
+        .collect::<Vec<_>>();
+
+        let rows_affected = sqlx::query!(
+            "INSERT INTO dj_roles (guild, id) SELECT $1, * FROM UNNEST($2::bigint[])
+            ON CONFLICT DO NOTHING;",
+            ctx.guild_id().get().cast_signed(),
+            &roles,
+        )
+        .execute(ctx.db())
+        .await?
+        .rows_affected();
+
+        if rows_affected == 0 {
+            ctx.susp(NO_ROWS_AFFECTED_MESSAGE).await?;
+            return Ok(());
+        }
+        ctx.bot()
+            .guild_configs()
+            .update(ctx.guild_id(), |config| {
+                let roles = roles.into_iter().map(|id| Id::new(id.cast_unsigned()));
+                config.dj_mut().roles.extend(roles);
+            })
+            .await;
+        ctx.out(format!("🎧➕ Added **`{rows_affected}`** role(s) as DJs."))
+            .await?;
+        Ok(())
+    }
+}
+
+/// Removes roles whose members are considered DJs.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "remove")]
+pub struct Remove {
+    /// Which role?
+    role: Id<RoleMarker>,
+    /// Which role? (2)
+    role_2: Option<Id<RoleMarker>>,
+    /// Which role? (3)
+    role_3: Option<Id<RoleMarker>>,
+    /// Which role? (4)
+    role_4: Option<Id<RoleMarker>>,
+    /// Which role? (5)
+    role_5: Option<Id<RoleMarker>>,
+}
+
+impl BotGuildSlashCommand for Remove {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let roles = [
+            Some(self.role),
+            self.role_2,
+            self.role_3,
+            self.role_4,
+            self.role_5,
+        ]
+        .into_iter()
+        .flatten()
+        .map(|r| r.get().cast_signed())
+        .collect::<Vec<_>>();
+
+        let rows_affected = sqlx::query!(
+            "DELETE FROM dj_roles WHERE guild = $1 AND id = ANY($2);",
+            ctx.guild_id().get().cast_signed(),
+            &roles,
+        )
+        .execute(ctx.db())
+        .await?
+        .rows_affected();
+
+        if rows_affected == 0 {
+            ctx.susp(NO_ROWS_AFFECTED_MESSAGE).await?;
+            return Ok(());
+        }
+        ctx.bot()
+            .guild_configs()
+            .update(ctx.guild_id(), |config| {
+                let dj = config.dj_mut();
+                dj.roles
+                    .retain(|id| !roles.contains(&id.get().cast_signed()));
+            })
+            .await;
+        ctx.out(format!(
+            "🎧➖ Removed **`{rows_affected}`** role(s) from DJs."
+        ))
+        .await?;
+        Ok(())
+    }
+}
+
+/// Sets whether everyone is considered a DJ when they are alone in voice.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "alone")]
+pub struct Alone {
+    /// Is everyone a DJ when they are alone in voice?
+    enabled: bool,
+}
+
+impl BotGuildSlashCommand for Alone {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let rows_affected = sqlx::query!(
+            "UPDATE guild_configs SET dj_when_alone = $2 WHERE id = $1 AND dj_when_alone != $2;",
+            ctx.guild_id().get().cast_signed(),
+            self.enabled,
+        )
+        .execute(ctx.db())
+        .await?
+        .rows_affected();
+
+        if rows_affected == 0 {
+            ctx.susp(NO_ROWS_AFFECTED_MESSAGE).await?;
+            return Ok(());
+        }
+        ctx.bot()
+            .guild_configs()
+            .update(ctx.guild_id(), |config| {
+                config.dj_mut().when_alone = self.enabled;
+            })
+            .await;
+        let (emoji, action) = if self.enabled {
+            ("🎧", "Everyone is now")
+        } else {
+            ("🔕", "Everyone is no longer")
+        };
+        ctx.out(format!("{emoji} {action} a DJ when alone in voice."))
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+use std::{collections::HashMap, sync::Arc};
+
+use moka::{future::Cache, ops::compute::Op};
+use sqlx::{Pool, Postgres};
+use twilight_model::id::{Id, marker::GuildMarker};
+
+use super::{
+    dj::DjSettings,
+    limits::QueueLimits,
+    poll::{PollRules, TopicKind},
+    recovery::RecoveryPolicy,
+};
+
+/// The settings of a guild.
+#[derive(Clone)]
+pub struct GuildConfig {
+    now_playing: bool,
+    limits: QueueLimits,
+    dj: DjSettings,
+    poll_rules: HashMap<TopicKind, PollRules>,
+    recovery: RecoveryPolicy,
+}
+
+impl GuildConfig {
+    async fn fetch(db: &Pool<Postgres>, guild_id: Id<GuildMarker>) -> Result<Self, sqlx::Error> {
+        let guild = guild_id.get().cast_signed();
+        let (rec, poll_rules) = tokio::try_join!(
+            sqlx::query!(
+                r#"SELECT
+                    now_playing,
+                    max_tracks_per_requester, max_queue_length, max_track_length, allow_streams, duplicates,
+                    dj_when_alone, ARRAY(SELECT id FROM dj_roles WHERE guild = $1) AS "dj_roles!",
+                    stuck_max_failures, stuck_fallback
+                FROM guild_configs WHERE id = $1;"#,
+                guild,
+            )
+            .fetch_one(db),
+            PollRules::fetch_all(db, guild_id),
+        )?;
+
+        Ok(Self {
+            now_playing: rec.now_playing,
+            limits: QueueLimits::from_columns(
+                rec.max_tracks_per_requester,
+                rec.max_queue_length,
+                rec.max_track_length,
+                rec.allow_streams,
+                rec.duplicates,
+            ),
+            dj: DjSettings::from_columns(rec.dj_roles, rec.dj_when_alone),
+            poll_rules,
+            recovery: RecoveryPolicy::from_columns(rec.stuck_max_failures, rec.stuck_fallback),
+        })
+    }
+
+    pub const fn now_playing(&self) -> bool {
+        self.now_playing
+    }
+
+    pub const fn set_now_playing(&mut self, now_playing: bool) {
+        self.now_playing = now_playing;
+    }
+
+    pub const fn limits(&self) -> QueueLimits {
+        self.limits
+    }
+
+    pub const fn set_limits(&mut self, limits: QueueLimits) {
+        self.limits = limits;
+    }
+
+    pub const fn dj(&self) -> &DjSettings {
+        &self.dj
+    }
+
+    pub const fn dj_mut(&mut self) -> &mut DjSettings {
+        &mut self.dj
+    }
+
+    pub fn poll_rules(&self, topic: TopicKind) -> PollRules {
+        self.poll_rules.get(&topic).copied().unwrap_or_default()
+    }
+
+    pub fn set_poll_rules(&mut self, topic: TopicKind, rules: PollRules) {
+        self.poll_rules.insert(topic, rules);
+    }
+
+    pub const fn recovery(&self) -> RecoveryPolicy {
+        self.recovery
+    }
+
+    pub const fn set_recovery(&mut self, recovery: RecoveryPolicy) {
+        self.recovery = recovery;
+    }
+}
+
+/// The settings of every guild, loaded from the database once per guild.
+///
+/// The `/config` commands write their changes to the database first, then through to here.
+#[derive(Clone)]
+pub struct GuildConfigCache {
+    db: Pool<Postgres>,
+    inner: Cache<Id<GuildMarker>, Arc<GuildConfig>>,
+}
+
+impl GuildConfigCache {
+    pub fn new(db: Pool<Postgres>) -> Self {
+        Self {
+            db,
+            inner: Cache::new(10_000),
+        }
+    }
+
+    /// Gets the settings of a guild, fetching them if they are not cached.
+    ///
+    /// The fetch is serialised with [`Self::update`] on the same guild, so that an update is
+    /// never lost to a fetch that read the settings from before it.
+    pub async fn get(&self, guild_id: Id<GuildMarker>) -> Result<Arc<GuildConfig>, sqlx::Error> {
+        if let Some(config) = self.inner.get(&guild_id).await {
+            return Ok(config);
+        }
+
+        let db = &self.db;
+        let entry = self
+            .inner
+            .entry(guild_id)
+            .and_try_compute_with(|entry| async move {
+                if entry.is_some() {
+                    return Ok(Op::Nop);
+                }
+                let config = GuildConfig::fetch(db, guild_id).await?;
+                Ok::<_, sqlx::Error>(Op::Put(Arc::new(config)))
+            })
+            .await?
+            .into_entry()
+            .expect("guild config must have been cached");
+        Ok(entry.into_value())
+    }
+
+    /// Applies `update` to the cached settings of a guild, if they are cached.
+    ///
+    /// This must only be called after the same change has been written to the database.
+    pub async fn update(
+        &self,
+        guild_id: Id<GuildMarker>,
+        update: impl FnOnce(&mut GuildConfig) + Send,
+    ) {
+        self.inner
+            .entry(guild_id)
+            .and_compute_with(|entry| async move {
+                let Some(entry) = entry else {
+                    return Op::Nop;
+                };
+                let mut config = GuildConfig::clone(entry.value());
+                update(&mut config);
+                Op::Put(Arc::new(config))
+            })
+            .await;
+    }
+
+    pub async fn invalidate(&self, guild_id: Id<GuildMarker>) {
+        self.inner.invalidate(&guild_id).await;
+    }
+}
@@ -0,0 +1,540 @@
+use std::time::Duration;
+
+use lyra_ext::pretty::{
+    duration_display::{DurationDisplay, FromPrettyStr},
+    join::PrettyJoiner,
+};
+use lyra_proc::BotGuildCommandGroup;
+use sqlx::{Pool, Postgres};
+use twilight_interactions::command::{CommandModel, CommandOption, CreateCommand, CreateOption};
+use twilight_model::id::{Id, marker::GuildMarker};
+
+use crate::{
+    command::model::{BotGuildSlashCommand, GuildSlashCmdCtx},
+    core::{
+        konst::text::NO_ROWS_AFFECTED_MESSAGE,
+        model::{
+            BotStateAware, DatabaseAware, response::initial::message::create::RespondWithMessage,
+        },
+    },
+    error::CommandResult,
+    gateway::GuildIdAware,
+    lavalink::{PlaylistAwareTrackData, SeenTracks},
+};
+
+/// What to do with tracks that are already in the upcoming part of the queue.
+#[derive(CommandOption, CreateOption, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    #[option(name = "Allow", value = 0)]
+    Allow,
+    #[option(name = "Warn", value = 1)]
+    Warn,
+    #[option(name = "Reject", value = 2)]
+    Reject,
+}
+
+impl DuplicatePolicy {
+    const fn from_i16(value: i16) -> Self {
+        match value {
+            1 => Self::Warn,
+            2 => Self::Reject,
+            _ => Self::Allow,
+        }
+    }
+
+    const fn as_i16(self) -> i16 {
+        match self {
+            Self::Allow => 0,
+            Self::Warn => 1,
+            Self::Reject => 2,
+        }
+    }
+
+    pub const fn is_allow(self) -> bool {
+        matches!(self, Self::Allow)
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Allow => "Allowed",
+            Self::Warn => "Warned",
+            Self::Reject => "Rejected",
+        }
+    }
+}
+
+/// The limits on what members can add to the queue in a guild.
+///
+/// The limits on the number of tracks only count the tracks yet to be played, as do the
+/// duplicate checks. DJs are not subject to these limits, apart from the duplicate policy. See [`Self::for_dj`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct QueueLimits {
+    max_tracks_per_requester: Option<usize>,
+    max_queue_length: Option<usize>,
+    max_track_length: Option<Duration>,
+    allow_streams: bool,
+    duplicates: DuplicatePolicy,
+}
+
+impl Default for QueueLimits {
+    fn default() -> Self {
+        Self {
+            max_tracks_per_requester: None,
+            max_queue_length: None,
+            max_track_length: None,
+            allow_streams: true,
+            duplicates: DuplicatePolicy::Allow,
+        }
+    }
+}
+
+impl QueueLimits {
+    pub(super) fn from_columns(
+        max_tracks_per_requester: Option<i32>,
+        max_queue_length: Option<i32>,
+        max_track_length: Option<i64>,
+        allow_streams: bool,
+        duplicates: i16,
+    ) -> Self {
+        Self {
+            max_tracks_per_requester: max_tracks_per_requester
+                .and_then(|n| usize::try_from(n).ok()),
+            max_queue_length: max_queue_length.and_then(|n| usize::try_from(n).ok()),
+            max_track_length: max_track_length
+                .map(|n| Duration::from_millis(n.max(0).cast_unsigned())),
+            allow_streams,
+            duplicates: DuplicatePolicy::from_i16(duplicates),
+        }
+    }
+
+    async fn save(self, db: &Pool<Postgres>, guild_id: Id<GuildMarker>) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE guild_configs SET
+                max_tracks_per_requester = $2,
+                max_queue_length = $3,
+                max_track_length = $4,
+                allow_streams = $5,
+                duplicates = $6
+            WHERE id = $1;",
+            guild_id.get().cast_signed(),
+            self.max_tracks_per_requester
+                .map(|n| i32::try_from(n).unwrap_or(i32::MAX)),
+            self.max_queue_length
+                .map(|n| i32::try_from(n).unwrap_or(i32::MAX)),
+            self.max_track_length
+                .map(|d| i64::try_from(d.as_millis()).unwrap_or(i64::MAX)),
+            self.allow_streams,
+            self.duplicates.as_i16(),
+        )
+        .execute(db)
+        .await?;
+        Ok(())
+    }
+
+    /// These limits as they apply to DJs, which is only the duplicate policy.
+    pub fn for_dj(self) -> Self {
+        Self {
+            duplicates: self.duplicates,
+            ..Self::default()
+        }
+    }
+
+    pub const fn duplicates(self) -> DuplicatePolicy {
+        self.duplicates
+    }
+
+    /// Applies these limits to `tracks` about to be added to the queue, with `upcoming` being the
+    /// number of tracks in the queue yet to be played, `upcoming_by_requester` of which are
+    /// added by the member adding `tracks`, and `seen` being those tracks yet to be played.
+    pub fn apply(
+        self,
+        tracks: Vec<PlaylistAwareTrackData>,
+        upcoming: usize,
+        upcoming_by_requester: usize,
+        mut seen: SeenTracks,
+    ) -> LimitedTracks {
+        let mut limited = LimitedTracks {
+            limits: self,
+            accepted: Vec::with_capacity(tracks.len()),
+            streams: 0,
+            too_long: 0,
+            over_requester_limit: 0,
+            over_queue_limit: 0,
+            duplicates: 0,
+            accepted_duplicates: 0,
+        };
+
+        let requester_room = self
+            .max_tracks_per_requester
+            .map(|n| n.saturating_sub(upcoming_by_requester));
+        let queue_room = self.max_queue_length.map(|n| n.saturating_sub(upcoming));
+
+        for track in tracks {
+            let info = &track.inner().info;
+            if !self.allow_streams && info.is_stream {
+                limited.streams += 1;
+            } else if !info.is_stream
+                && self
+                    .max_track_length
+                    .is_some_and(|max| Duration::from_millis(info.length) > max)
+            {
+                limited.too_long += 1;
+            } else if self.duplicates == DuplicatePolicy::Reject && seen.contains(info) {
+                limited.duplicates += 1;
+            } else if requester_room.is_some_and(|room| limited.accepted.len() >= room) {
+                limited.over_requester_limit += 1;
+            } else if queue_room.is_some_and(|room| limited.accepted.len() >= room) {
+                limited.over_queue_limit += 1;
+            } else {
+                if !self.duplicates.is_allow() && seen.insert(info) {
+                    limited.accepted_duplicates += 1;
+                }
+                limited.accepted.push(track);
+            }
+        }
+        limited
+    }
+}
+
+/// The tracks that were accepted into the queue after applying [`QueueLimits`], along with how
+/// many tracks were rejected and why.
+pub struct LimitedTracks {
+    limits: QueueLimits,
+    accepted: Vec<PlaylistAwareTrackData>,
+    streams: usize,
+    too_long: usize,
+    over_requester_limit: usize,
+    over_queue_limit: usize,
+    duplicates: usize,
+    accepted_duplicates: usize,
+}
+
+impl LimitedTracks {
+    pub const fn rejected(&self) -> usize {
+        self.streams
+            + self.too_long
+            + self.duplicates
+            + self.over_requester_limit
+            + self.over_queue_limit
+    }
+
+    /// How many of the accepted tracks were already in the queue.
+    pub const fn accepted_duplicates(&self) -> usize {
+        self.accepted_duplicates
+    }
+
+    pub fn into_accepted(self) -> Vec<PlaylistAwareTrackData> {
+        self.accepted
+    }
+
+    /// The reasons for the rejected tracks, as a human-readable sentence.
+    pub fn rejected_reasons(&self) -> String {
+        let limits = self.limits;
+        let mut reasons = Vec::new();
+        if self.streams != 0 {
+            reasons.push(format!("`{}` were livestreams", self.streams));
+        }
+        if let (n @ 1.., Some(max)) = (self.too_long, limits.max_track_length) {
+            reasons.push(format!("`{n}` were longer than `{}`", max.pretty_display()));
+        }
+        if self.duplicates != 0 {
+            reasons.push(format!("`{}` were already in the queue", self.duplicates));
+        }
+        if let (n @ 1.., Some(max)) = (self.over_requester_limit, limits.max_tracks_per_requester) {
+            reasons.push(format!(
+                "`{n}` would exceed the limit of `{max} upcoming tracks` per member"
+            ));
+        }
+        if let (n @ 1.., Some(max)) = (self.over_queue_limit, limits.max_queue_length) {
+            reasons.push(format!(
+                "`{n}` would exceed the queue limit of `{max} upcoming tracks`"
+            ));
+        }
+        reasons.pretty_join_with_and()
+    }
+}
+
+#[derive(CommandModel, CreateCommand, BotGuildCommandGroup)]
+#[command(name = "limits", desc = ".")]
+pub enum Limits {
+    #[command(name = "view")]
+    View(View),
+    #[command(name = "set")]
+    Set(Set),
+}
+
+fn display_limit(limit: Option<impl std::fmt::Display>) -> String {
+    limit.map_or_else(|| String::from("`None`"), |l| format!("`{l}`"))
+}
+
+/// Views the limits on what members can add to the queue.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "view")]
+pub struct View;
+
+impl BotGuildSlashCommand for View {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let limits = ctx
+            .bot()
+            .guild_configs()
+            .get(ctx.guild_id())
+            .await?
+            .limits();
+
+        ctx.out(format!(
+            "🚧 **Queue limits**\n\
+            - Upcoming tracks per member: {}\n\
+            - Upcoming tracks in queue: {}\n\
+            - Track length: {}\n\
+            - Livestreams: `{}`\n\
+            - Duplicate tracks: `{}`\n\
+            -# DJs are not subject to these limits, apart from the duplicate tracks policy.",
+            display_limit(limits.max_tracks_per_requester),
+            display_limit(limits.max_queue_length),
+            display_limit(limits.max_track_length.map(|d| d.pretty_display())),
+            if limits.allow_streams {
+                "Allowed"
+            } else {
+                "Denied"
+            },
+            limits.duplicates.name(),
+        ))
+        .await?;
+        Ok(())
+    }
+}
+
+/// Sets the limits on what members can add to the queue.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "set")]
+pub struct Set {
+    /// How many tracks yet to be played can each member have in the queue? (0 to remove the limit)
+    #[command(min_value = 0, max_value = 10_000)]
+    tracks_per_member: Option<i64>,
+    /// How many tracks yet to be played can the queue have? (0 to remove the limit)
+    #[command(min_value = 0, max_value = 10_000)]
+    queue_size: Option<i64>,
+    /// How long can each track be? [Must be a timestamp like 10m or 1:00:00] (0 to remove the limit)
+    #[command(min_length = 1)]
+    track_length: Option<String>,
+    /// Can livestreams be added to the queue?
+    allow_livestreams: Option<bool>,
+    /// What to do with tracks that are already in the queue?
+    duplicates: Option<DuplicatePolicy>,
+}
+
+impl BotGuildSlashCommand for Set {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let old = ctx
+            .bot()
+            .guild_configs()
+            .get(ctx.guild_id())
+            .await?
+            .limits();
+        let mut new = old;
+
+        let into_limit = |n: i64| usize::try_from(n).ok().filter(|&n| n != 0);
+        if let Some(n) = self.tracks_per_member {
+            new.max_tracks_per_requester = into_limit(n);
+        }
+        if let Some(n) = self.queue_size {
+            new.max_queue_length = into_limit(n);
+        }
+        if let Some(length) = self.track_length {
+            let duration = if let Ok(secs) = length.parse::<u64>() {
+                Duration::from_secs(secs)
+            } else if let Ok(duration) = Duration::from_pretty_str(&length) {
+                duration
+            } else {
+                ctx.wrng(format!(
+                    "**Invalid track length: `{length}`**; \
+                    Track length must either be in the format like `10m` or `1:00:00`, or as the total seconds like `600`.",
+                ))
+                .await?;
+                return Ok(());
+            };
+            new.max_track_length = Some(duration).filter(|d| !d.is_zero());
+        }
+        if let Some(allow) = self.allow_livestreams {
+            new.allow_streams = allow;
+        }
+        if let Some(policy) = self.duplicates {
+            new.duplicates = policy;
+        }
+
+        if new == old {
+            ctx.susp(NO_ROWS_AFFECTED_MESSAGE).await?;
+            return Ok(());
+        }
+
+        new.save(ctx.db(), ctx.guild_id()).await?;
+        ctx.bot()
+            .guild_configs()
+            .update(ctx.guild_id(), |config| config.set_limits(new))
+            .await;
+        ctx.out("🚧 Updated the queue limits.").await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use lavalink_rs::model::track::{TrackData, TrackInfo};
+
+    use super::{DuplicatePolicy, LimitedTracks, QueueLimits};
+    use crate::lavalink::{PlaylistAwareTrackData, SeenTracks};
+
+    fn track(identifier: &str, length: u64, is_stream: bool) -> PlaylistAwareTrackData {
+        TrackData {
+            info: TrackInfo {
+                identifier: identifier.to_owned(),
+                length,
+                is_stream,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+        .into()
+    }
+
+    fn tracks(identifiers: &[&str]) -> Vec<PlaylistAwareTrackData> {
+        identifiers
+            .iter()
+            .map(|i| track(i, 60_000, false))
+            .collect()
+    }
+
+    fn identifiers(limited: LimitedTracks) -> Vec<String> {
+        limited
+            .into_accepted()
+            .into_iter()
+            .map(|t| t.into_inner().info.identifier)
+            .collect()
+    }
+
+    fn seen(identifiers: &[&str]) -> SeenTracks {
+        let mut seen = SeenTracks::default();
+        for track in tracks(identifiers) {
+            seen.insert(&track.inner().info);
+        }
+        seen
+    }
+
+    #[test]
+    fn queue_length_limit_counts_upcoming_tracks() {
+        let limits = QueueLimits {
+            max_queue_length: Some(5),
+            ..Default::default()
+        };
+
+        let limited = limits.apply(tracks(&["a", "b", "c", "d"]), 3, 0, SeenTracks::default());
+        assert_eq!(limited.rejected(), 2);
+        assert_eq!(limited.over_queue_limit, 2);
+        assert_eq!(identifiers(limited), ["a", "b"]);
+
+        let limited = limits.apply(tracks(&["a"]), 7, 0, SeenTracks::default());
+        assert_eq!(limited.over_queue_limit, 1);
+        assert!(identifiers(limited).is_empty());
+    }
+
+    #[test]
+    fn requester_limit_counts_requesters_upcoming_tracks() {
+        let limits = QueueLimits {
+            max_tracks_per_requester: Some(3),
+            max_queue_length: Some(20),
+            ..Default::default()
+        };
+
+        let limited = limits.apply(tracks(&["a", "b", "c"]), 10, 2, SeenTracks::default());
+        assert_eq!(limited.over_requester_limit, 2);
+        assert_eq!(limited.over_queue_limit, 0);
+        assert_eq!(identifiers(limited), ["a"]);
+
+        let limited = limits.apply(tracks(&["a", "b", "c"]), 19, 0, SeenTracks::default());
+        assert_eq!(limited.over_requester_limit, 0);
+        assert_eq!(limited.over_queue_limit, 2);
+        assert_eq!(identifiers(limited), ["a"]);
+    }
+
+    #[test]
+    fn track_length_limit_exempts_streams() {
+        let limits = QueueLimits {
+            max_track_length: Some(Duration::from_secs(60)),
+            ..Default::default()
+        };
+        let tracks = Vec::from([
+            track("short", 60_000, false),
+            track("long", 60_001, false),
+            track("stream", u64::MAX, true),
+        ]);
+
+        let limited = limits.apply(tracks.clone(), 0, 0, SeenTracks::default());
+        assert_eq!(limited.too_long, 1);
+        assert_eq!(identifiers(limited), ["short", "stream"]);
+
+        let limits = QueueLimits {
+            allow_streams: false,
+            ..limits
+        };
+        let limited = limits.apply(tracks, 0, 0, SeenTracks::default());
+        assert_eq!((limited.too_long, limited.streams), (1, 1));
+        assert_eq!(identifiers(limited), ["short"]);
+    }
+
+    #[test]
+    fn duplicates_allowed() {
+        let limits = QueueLimits::default();
+
+        let limited = limits.apply(tracks(&["a", "b", "b"]), 1, 0, seen(&["a"]));
+        assert_eq!(limited.rejected(), 0);
+        assert_eq!(limited.accepted_duplicates(), 0);
+        assert_eq!(identifiers(limited), ["a", "b", "b"]);
+    }
+
+    #[test]
+    fn duplicates_warned() {
+        let limits = QueueLimits {
+            duplicates: DuplicatePolicy::Warn,
+            ..Default::default()
+        };
+
+        let limited = limits.apply(tracks(&["a", "b", "b", "c"]), 1, 0, seen(&["a"]));
+        assert_eq!(limited.rejected(), 0);
+        assert_eq!(limited.accepted_duplicates(), 2);
+        assert_eq!(identifiers(limited), ["a", "b", "b", "c"]);
+    }
+
+    #[test]
+    fn duplicates_rejected() {
+        let limits = QueueLimits {
+            duplicates: DuplicatePolicy::Reject,
+            ..Default::default()
+        };
+
+        let limited = limits.apply(tracks(&["a", "b", "b", "c"]), 1, 0, seen(&["a"]));
+        assert_eq!(limited.duplicates, 2);
+        assert_eq!(limited.accepted_duplicates(), 0);
+        assert_eq!(identifiers(limited), ["b", "c"]);
+    }
+
+    #[test]
+    fn dj_only_subject_to_duplicate_policy() {
+        let limits = QueueLimits {
+            max_tracks_per_requester: Some(1),
+            max_queue_length: Some(1),
+            max_track_length: Some(Duration::from_secs(1)),
+            allow_streams: false,
+            duplicates: DuplicatePolicy::Reject,
+        }
+        .for_dj();
+        let mut tracks = tracks(&["a", "b", "c"]);
+        tracks.push(track("stream", 0, true));
+
+        let limited = limits.apply(tracks, 5, 5, seen(&["a"]));
+        assert_eq!(limited.rejected(), 1);
+        assert_eq!(limited.duplicates, 1);
+        assert_eq!(identifiers(limited), ["b", "c", "stream"]);
+    }
+}
@@ -1,12 +1,20 @@
 pub mod access;
+pub mod dj;
+mod guild_config;
+pub mod limits;
 pub mod now_playing;
+pub mod poll;
+pub mod recovery;
 
 use twilight_interactions::command::{CommandModel, CreateCommand};
 
 use lyra_proc::BotGuildCommandGroup;
 use twilight_model::guild::Permissions;
 
-use self::{access::Access, now_playing::NowPlaying};
+pub use self::guild_config::GuildConfigCache;
+use self::{
+    access::Access, dj::Dj, limits::Limits, now_playing::NowPlaying, poll::Poll, recovery::Recovery,
+};
 
 #[derive(CommandModel, CreateCommand, BotGuildCommandGroup)]
 #[command(
@@ -20,6 +28,14 @@ pub enum Config {
     Access(Box<Access>),
     #[command(name = "now-playing")]
     NowPlaying(NowPlaying),
+    #[command(name = "limits")]
+    Limits(Limits),
+    #[command(name = "poll")]
+    Poll(Poll),
+    #[command(name = "dj")]
+    Dj(Dj),
+    #[command(name = "recovery")]
+    Recovery(Recovery),
 }
 
 impl Config {
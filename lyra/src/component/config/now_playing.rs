@@ -7,7 +7,8 @@ use crate::{
         require,
     },
     core::model::{
-        DatabaseAware, OwnedHttpAware, response::initial::message::create::RespondWithMessage,
+        BotStateAware, DatabaseAware, OwnedHttpAware,
+        response::initial::message::create::RespondWithMessage,
     },
     error::CommandResult,
     gateway::GuildIdAware,
@@ -36,6 +37,12 @@ impl BotGuildSlashCommand for Toggle {
         .fetch_one(ctx.db())
         .await?
         .now_playing;
+        ctx.bot()
+            .guild_configs()
+            .update(ctx.guild_id(), |config| {
+                config.set_now_playing(new_now_playing);
+            })
+            .await;
 
         let maybe_data = require::player(&ctx).map(|p| p.data());
         let (emoji, action) = if new_now_playing {
@@ -0,0 +1,526 @@
+use std::{collections::HashMap, time::Duration};
+
+use lyra_ext::pretty::duration_display::{DurationDisplay, FromPrettyStr};
+use lyra_proc::BotGuildCommandGroup;
+use sqlx::{Pool, Postgres};
+use twilight_interactions::command::{CommandModel, CommandOption, CreateCommand, CreateOption};
+use twilight_model::id::{Id, marker::GuildMarker};
+
+use crate::{
+    command::model::{BotGuildSlashCommand, GuildSlashCmdCtx},
+    core::{
+        konst::{
+            poll::{DEFAULT_TIMEOUT, MAX_TIMEOUT, MIN_TIMEOUT},
+            text::NO_ROWS_AFFECTED_MESSAGE,
+        },
+        model::{
+            BotStateAware, DatabaseAware, response::initial::message::create::RespondWithMessage,
+        },
+    },
+    error::CommandResult,
+    gateway::GuildIdAware,
+};
+
+/// What a poll is being started for.
+#[derive(CommandOption, CreateOption, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TopicKind {
+    #[option(name = "Skip", value = 0)]
+    Skip,
+    #[option(name = "Back", value = 1)]
+    Back,
+    #[option(name = "Jump", value = 2)]
+    Jump,
+    #[option(name = "Repeat", value = 3)]
+    Repeat,
+    #[option(name = "Shuffle", value = 4)]
+    Shuffle,
+    #[option(name = "Fair Queue", value = 5)]
+    FairQueue,
+    #[option(name = "Pause", value = 6)]
+    Pause,
+    #[option(name = "Clear", value = 7)]
+    Clear,
+}
+
+impl TopicKind {
+    const ALL: [Self; 8] = [
+        Self::Skip,
+        Self::Back,
+        Self::Jump,
+        Self::Repeat,
+        Self::Shuffle,
+        Self::FairQueue,
+        Self::Pause,
+        Self::Clear,
+    ];
+
+    const fn from_i16(value: i16) -> Option<Self> {
+        Some(match value {
+            0 => Self::Skip,
+            1 => Self::Back,
+            2 => Self::Jump,
+            3 => Self::Repeat,
+            4 => Self::Shuffle,
+            5 => Self::FairQueue,
+            6 => Self::Pause,
+            7 => Self::Clear,
+            _ => return None,
+        })
+    }
+
+    const fn as_i16(self) -> i16 {
+        match self {
+            Self::Skip => 0,
+            Self::Back => 1,
+            Self::Jump => 2,
+            Self::Repeat => 3,
+            Self::Shuffle => 4,
+            Self::FairQueue => 5,
+            Self::Pause => 6,
+            Self::Clear => 7,
+        }
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Skip => "Skip",
+            Self::Back => "Back",
+            Self::Jump => "Jump",
+            Self::Repeat => "Repeat",
+            Self::Shuffle => "Shuffle",
+            Self::FairQueue => "Fair Queue",
+            Self::Pause => "Pause",
+            Self::Clear => "Clear",
+        }
+    }
+}
+
+/// How many votes either side of a poll needs to win.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Threshold {
+    /// More than half of the votes of everyone in voice.
+    Majority,
+    /// A fixed number of votes, or all votes of everyone in voice if there are fewer of them.
+    Absolute(u16),
+    /// A percentage of the votes of everyone in voice.
+    Ratio(u8),
+}
+
+impl Threshold {
+    fn from_i16s(kind: i16, value: i16) -> Self {
+        let value = value.max(1).cast_unsigned();
+        match kind {
+            1 => Self::Absolute(value),
+            2 => Self::Ratio(u8::try_from(value).map_or(100, |p| p.min(100))),
+            _ => Self::Majority,
+        }
+    }
+
+    fn as_i16s(self) -> (i16, i16) {
+        match self {
+            Self::Majority => (0, 0),
+            Self::Absolute(n) => (1, i16::try_from(n).unwrap_or(i16::MAX)),
+            Self::Ratio(p) => (2, i16::from(p)),
+        }
+    }
+
+    /// The votes needed for either side to win, out of `total` votes.
+    pub fn votes_needed(self, total: usize) -> usize {
+        match self {
+            Self::Majority => total / 2 + 1,
+            Self::Absolute(n) => usize::from(n).min(total),
+            Self::Ratio(p) => (total * usize::from(p)).div_ceil(100),
+        }
+        .max(1)
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value.eq_ignore_ascii_case("majority") {
+            return Some(Self::Majority);
+        }
+        if let Some(percentage) = value.strip_suffix('%') {
+            return percentage
+                .trim()
+                .parse()
+                .ok()
+                .filter(|p| (1..=100).contains(p))
+                .map(Self::Ratio);
+        }
+        value
+            .parse()
+            .ok()
+            .filter(|&n| n != 0 && i16::try_from(n).is_ok())
+            .map(Self::Absolute)
+    }
+}
+
+impl std::fmt::Display for Threshold {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Majority => f.write_str("Majority"),
+            Self::Absolute(1) => f.write_str("1 vote"),
+            Self::Absolute(n) => write!(f, "{n} votes"),
+            Self::Ratio(p) => write!(f, "{p}% of listeners"),
+        }
+    }
+}
+
+/// The rules for polls of a topic in a guild.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct PollRules {
+    threshold: Threshold,
+    timeout: Duration,
+    dj_override: bool,
+    requester_double: bool,
+}
+
+impl Default for PollRules {
+    fn default() -> Self {
+        Self {
+            threshold: Threshold::Majority,
+            timeout: DEFAULT_TIMEOUT,
+            dj_override: true,
+            requester_double: false,
+        }
+    }
+}
+
+impl PollRules {
+    #[cfg(test)]
+    pub(crate) fn new(threshold: Threshold, requester_double: bool) -> Self {
+        Self {
+            threshold,
+            requester_double,
+            ..Self::default()
+        }
+    }
+
+    fn from_row(
+        threshold_kind: i16,
+        threshold_value: i16,
+        timeout: i64,
+        dj_override: bool,
+        requester_double: bool,
+    ) -> Self {
+        Self {
+            threshold: Threshold::from_i16s(threshold_kind, threshold_value),
+            timeout: Duration::from_millis(timeout.max(0).cast_unsigned()),
+            dj_override,
+            requester_double,
+        }
+    }
+
+    pub(super) async fn fetch_all(
+        db: &Pool<Postgres>,
+        guild_id: Id<GuildMarker>,
+    ) -> Result<HashMap<TopicKind, Self>, sqlx::Error> {
+        let recs = sqlx::query!(
+            "SELECT topic, threshold_kind, threshold_value, timeout, dj_override, requester_double
+            FROM poll_rules WHERE guild = $1;",
+            guild_id.get().cast_signed(),
+        )
+        .fetch_all(db)
+        .await?;
+
+        let mut rules = recs
+            .into_iter()
+            .filter_map(|rec| {
+                let topic = TopicKind::from_i16(rec.topic)?;
+                let rules = Self::from_row(
+                    rec.threshold_kind,
+                    rec.threshold_value,
+                    rec.timeout,
+                    rec.dj_override,
+                    rec.requester_double,
+                );
+                Some((topic, rules))
+            })
+            .collect::<HashMap<_, _>>();
+        for topic in TopicKind::ALL {
+            rules.entry(topic).or_default();
+        }
+        Ok(rules)
+    }
+
+    async fn save(
+        self,
+        db: &Pool<Postgres>,
+        guild_id: Id<GuildMarker>,
+        topic: TopicKind,
+    ) -> Result<(), sqlx::Error> {
+        let (threshold_kind, threshold_value) = self.threshold.as_i16s();
+        sqlx::query!(
+            "INSERT INTO poll_rules
+                (guild, topic, threshold_kind, threshold_value, timeout, dj_override, requester_double)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (guild, topic) DO UPDATE SET
+                threshold_kind = $3,
+                threshold_value = $4,
+                timeout = $5,
+                dj_override = $6,
+                requester_double = $7;",
+            guild_id.get().cast_signed(),
+            topic.as_i16(),
+            threshold_kind,
+            threshold_value,
+            i64::try_from(self.timeout.as_millis()).unwrap_or(i64::MAX),
+            self.dj_override,
+            self.requester_double,
+        )
+        .execute(db)
+        .await?;
+        Ok(())
+    }
+
+    pub const fn threshold(self) -> Threshold {
+        self.threshold
+    }
+
+    pub const fn timeout(self) -> Duration {
+        self.timeout
+    }
+
+    pub const fn dj_override(self) -> bool {
+        self.dj_override
+    }
+
+    pub const fn requester_double(self) -> bool {
+        self.requester_double
+    }
+}
+
+#[derive(CommandModel, CreateCommand, BotGuildCommandGroup)]
+#[command(name = "poll", desc = ".")]
+pub enum Poll {
+    #[command(name = "view")]
+    View(View),
+    #[command(name = "set")]
+    Set(Set),
+}
+
+fn display_rules(topic: TopicKind, rules: PollRules) -> String {
+    format!(
+        "- **{}** » Threshold: `{}` · Timeout: `{}` · DJ override: `{}` · Requester's vote: `{}`",
+        topic.name(),
+        rules.threshold,
+        rules.timeout.pretty_display(),
+        if rules.dj_override { "On" } else { "Off" },
+        if rules.requester_double {
+            "Double"
+        } else {
+            "Single"
+        },
+    )
+}
+
+/// Views the rules for polls.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "view")]
+pub struct View {
+    /// Which kind of polls? (if not given, all kinds)
+    topic: Option<TopicKind>,
+}
+
+impl BotGuildSlashCommand for View {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let topics = self
+            .topic
+            .as_ref()
+            .map_or(&TopicKind::ALL[..], std::slice::from_ref);
+        let config = ctx.bot().guild_configs().get(ctx.guild_id()).await?;
+
+        let rules_lines = topics
+            .iter()
+            .map(|&topic| display_rules(topic, config.poll_rules(topic)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        ctx.out(format!(
+            "🗳️ **Poll rules**\n{rules_lines}\n\
+            -# Polls are only started when a member's action affects everyone else in voice.",
+        ))
+        .await?;
+        Ok(())
+    }
+}
+
+/// Sets the rules for polls.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "set")]
+pub struct Set {
+    /// Which kind of polls? (if not given, all kinds)
+    topic: Option<TopicKind>,
+    /// How many votes are needed? [Must be either majority, a number like 3, or a percentage like 60%]
+    #[command(min_length = 1)]
+    threshold: Option<String>,
+    /// How long can polls last? [Must be a timestamp like 30s or 1:00]
+    #[command(min_length = 1)]
+    timeout: Option<String>,
+    /// Can DJs decide polls by themselves?
+    dj_override: Option<bool>,
+    /// Should the vote of the member who requested the current track count double?
+    requester_double: Option<bool>,
+}
+
+impl BotGuildSlashCommand for Set {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let threshold = if let Some(threshold) = self.threshold {
+            let Some(parsed) = Threshold::parse(&threshold) else {
+                ctx.wrng(format!(
+                    "**Invalid threshold: `{threshold}`**; \
+                    Threshold must either be `majority`, a number of votes like `3`, or a percentage of listeners like `60%`.",
+                ))
+                .await?;
+                return Ok(());
+            };
+            Some(parsed)
+        } else {
+            None
+        };
+        let timeout = if let Some(timeout) = self.timeout {
+            let duration = if let Ok(secs) = timeout.parse::<u64>() {
+                Duration::from_secs(secs)
+            } else if let Ok(duration) = Duration::from_pretty_str(&timeout) {
+                duration
+            } else {
+                ctx.wrng(format!(
+                    "**Invalid timeout: `{timeout}`**; \
+                    Timeout must either be in the format like `30s` or `1:00`, or as the total seconds like `60`.",
+                ))
+                .await?;
+                return Ok(());
+            };
+            if !(MIN_TIMEOUT..=MAX_TIMEOUT).contains(&duration) {
+                ctx.wrng(format!(
+                    "**Timeout out of range**; Timeout must be between `{}` and `{}`.",
+                    MIN_TIMEOUT.pretty_display(),
+                    MAX_TIMEOUT.pretty_display(),
+                ))
+                .await?;
+                return Ok(());
+            }
+            Some(duration)
+        } else {
+            None
+        };
+
+        let topics = self
+            .topic
+            .as_ref()
+            .map_or(&TopicKind::ALL[..], std::slice::from_ref);
+        let config = ctx.bot().guild_configs().get(ctx.guild_id()).await?;
+
+        let mut changed = Vec::new();
+        for &topic in topics {
+            let old = config.poll_rules(topic);
+            let mut new = old;
+            if let Some(threshold) = threshold {
+                new.threshold = threshold;
+            }
+            if let Some(timeout) = timeout {
+                new.timeout = timeout;
+            }
+            if let Some(dj_override) = self.dj_override {
+                new.dj_override = dj_override;
+            }
+            if let Some(requester_double) = self.requester_double {
+                new.requester_double = requester_double;
+            }
+            if new != old {
+                changed.push((topic, new));
+            }
+        }
+
+        if changed.is_empty() {
+            ctx.susp(NO_ROWS_AFFECTED_MESSAGE).await?;
+            return Ok(());
+        }
+
+        for &(topic, rules) in &changed {
+            rules.save(ctx.db(), ctx.guild_id(), topic).await?;
+        }
+        ctx.bot()
+            .guild_configs()
+            .update(ctx.guild_id(), |config| {
+                for (topic, rules) in changed {
+                    config.set_poll_rules(topic, rules);
+                }
+            })
+            .await;
+        let topics = self.topic.map_or_else(
+            || String::from("all polls"),
+            |topic| format!("**{}** polls", topic.name()),
+        );
+        ctx.out(format!("🗳️ Updated the rules for {topics}."))
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Threshold;
+
+    #[test]
+    fn threshold_parse() {
+        let parse = Threshold::parse;
+        assert!(parse("majority") == Some(Threshold::Majority));
+        assert!(parse(" Majority ") == Some(Threshold::Majority));
+        assert!(parse("3") == Some(Threshold::Absolute(3)));
+        assert!(parse("60%") == Some(Threshold::Ratio(60)));
+        assert!(parse("60 %") == Some(Threshold::Ratio(60)));
+        assert!(parse("100%") == Some(Threshold::Ratio(100)));
+
+        assert!(parse("0").is_none());
+        assert!(parse("0%").is_none());
+        assert!(parse("101%").is_none());
+        assert!(parse("40000").is_none());
+        assert!(parse("-1").is_none());
+        assert!(parse("most").is_none());
+    }
+
+    #[test]
+    fn threshold_majority_votes_needed() {
+        assert_eq!(Threshold::Majority.votes_needed(0), 1);
+        assert_eq!(Threshold::Majority.votes_needed(1), 1);
+        assert_eq!(Threshold::Majority.votes_needed(4), 3);
+        assert_eq!(Threshold::Majority.votes_needed(5), 3);
+    }
+
+    #[test]
+    fn threshold_absolute_capped_to_listeners() {
+        assert_eq!(Threshold::Absolute(2).votes_needed(10), 2);
+        assert_eq!(Threshold::Absolute(5).votes_needed(3), 3);
+        assert_eq!(Threshold::Absolute(5).votes_needed(0), 1);
+    }
+
+    #[test]
+    fn threshold_ratio_rounds_up() {
+        assert_eq!(Threshold::Ratio(60).votes_needed(5), 3);
+        assert_eq!(Threshold::Ratio(50).votes_needed(3), 2);
+        assert_eq!(Threshold::Ratio(34).votes_needed(3), 2);
+        assert_eq!(Threshold::Ratio(33).votes_needed(3), 1);
+        assert_eq!(Threshold::Ratio(1).votes_needed(3), 1);
+        assert_eq!(Threshold::Ratio(100).votes_needed(0), 1);
+    }
+
+    #[test]
+    fn threshold_from_i16s() {
+        assert!(Threshold::from_i16s(0, 9) == Threshold::Majority);
+        assert!(Threshold::from_i16s(7, 9) == Threshold::Majority);
+        assert!(Threshold::from_i16s(1, 5) == Threshold::Absolute(5));
+        assert!(Threshold::from_i16s(1, 0) == Threshold::Absolute(1));
+        assert!(Threshold::from_i16s(1, -3) == Threshold::Absolute(1));
+        assert!(Threshold::from_i16s(2, 60) == Threshold::Ratio(60));
+        assert!(Threshold::from_i16s(2, 150) == Threshold::Ratio(100));
+
+        for threshold in [
+            Threshold::Majority,
+            Threshold::Absolute(3),
+            Threshold::Ratio(60),
+        ] {
+            let (kind, value) = threshold.as_i16s();
+            assert!(Threshold::from_i16s(kind, value) == threshold);
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use lyra_proc::BotGuildCommandGroup;
+use twilight_interactions::command::{CommandModel, CreateCommand};
+
+use crate::{
+    command::model::{BotGuildSlashCommand, GuildSlashCmdCtx},
+    core::{
+        konst::text::NO_ROWS_AFFECTED_MESSAGE,
+        model::{
+            BotStateAware, DatabaseAware, response::initial::message::create::RespondWithMessage,
+        },
+    },
+    error::CommandResult,
+    gateway::GuildIdAware,
+    lavalink::RecoveryStep,
+};
+
+/// How stuck tracks are recovered in a guild.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct RecoveryPolicy {
+    max_failures: u8,
+    fallback: bool,
+}
+
+impl RecoveryPolicy {
+    pub(super) fn from_columns(max_failures: i16, fallback: bool) -> Self {
+        Self {
+            max_failures: u8::try_from(max_failures).unwrap_or(1).max(1),
+            fallback,
+        }
+    }
+
+    /// The step to take after a track got stuck for the `failures`-th time.
+    ///
+    /// The track is first retried, then substituted with the same track from another source if
+    /// enabled, then retried again until it got stuck for the maximum number of times, after
+    /// which it is skipped.
+    pub const fn step_for(self, failures: u8) -> RecoveryStep {
+        if failures >= self.max_failures {
+            RecoveryStep::Skip
+        } else if failures == 2 && self.fallback {
+            RecoveryStep::Fallback
+        } else {
+            RecoveryStep::Retry
+        }
+    }
+
+    /// Whether stuck or unplayable tracks are substituted with the same track from other sources.
+    pub const fn fallback(self) -> bool {
+        self.fallback
+    }
+}
+
+#[derive(CommandModel, CreateCommand, BotGuildCommandGroup)]
+#[command(name = "recovery", desc = ".")]
+pub enum Recovery {
+    #[command(name = "view")]
+    View(View),
+    #[command(name = "set")]
+    Set(Set),
+}
+
+/// Views how stuck or unplayable tracks are recovered.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "view")]
+pub struct View;
+
+impl BotGuildSlashCommand for View {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let policy = ctx
+            .bot()
+            .guild_configs()
+            .get(ctx.guild_id())
+            .await?
+            .recovery();
+
+        ctx.out(format!(
+            "🩹 **Track recovery**\n\
+            - Skip after: `{} failure(s)`\n\
+            - Substitute from other sources: `{}`\n\
+            -# Stuck tracks are first retried from where they were last at, \
+            while unplayable tracks can only be substituted.",
+            policy.max_failures,
+            if policy.fallback { "Yes" } else { "No" },
+        ))
+        .await?;
+        Ok(())
+    }
+}
+
+/// Sets how stuck or unplayable tracks are recovered.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "set")]
+pub struct Set {
+    /// After how many times getting stuck should a track be skipped? (1 to skip right away)
+    #[command(min_value = 1, max_value = 10)]
+    skip_after: Option<i64>,
+    /// Should stuck or unplayable tracks be substituted with the same track from other sources?
+    substitute: Option<bool>,
+}
+
+impl BotGuildSlashCommand for Set {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let old = ctx
+            .bot()
+            .guild_configs()
+            .get(ctx.guild_id())
+            .await?
+            .recovery();
+        let mut new = old;
+
+        if let Some(n) = self.skip_after {
+            new.max_failures = u8::try_from(n).unwrap_or(u8::MAX).max(1);
+        }
+        if let Some(fallback) = self.substitute {
+            new.fallback = fallback;
+        }
+
+        if new == old {
+            ctx.susp(NO_ROWS_AFFECTED_MESSAGE).await?;
+            return Ok(());
+        }
+
+        sqlx::query!(
+            "UPDATE guild_configs SET stuck_max_failures = $2, stuck_fallback = $3 WHERE id = $1;",
+            ctx.guild_id().get().cast_signed(),
+            i16::from(new.max_failures),
+            new.fallback,
+        )
+        .execute(ctx.db())
+        .await?;
+        ctx.bot()
+            .guild_configs()
+            .update(ctx.guild_id(), |config| config.set_recovery(new))
+            .await;
+        ctx.out("🩹 Updated how stuck or unplayable tracks are recovered.")
+            .await?;
+        Ok(())
+    }
+}
@@ -56,6 +56,7 @@ pub(super) async fn disconnect_cleanup(
     }
     lavalink.drop_connection(guild_id);
     lavalink.delete_player(guild_id).await?;
+    lavalink.delete_snapshot(guild_id).await?;
 
     Ok(())
 }
@@ -1,9 +1,11 @@
 mod join;
 mod leave;
+mod restore;
 
 pub use join::{Join, auto as auto_join};
 pub use leave::Leave;
 use lyra_ext::{iso8601_time, unix_time};
+pub use restore::restore;
 
 use std::sync::Arc;
 
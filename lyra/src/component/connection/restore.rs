@@ -0,0 +1,120 @@
+use lavalink_rs::{error::LavalinkResult, model::track::TrackData};
+use twilight_model::{
+    gateway::payload::outgoing::UpdateVoiceState,
+    id::{Id, marker::GuildMarker},
+};
+
+use crate::{
+    LavalinkAware,
+    core::model::{CacheAware, DatabaseAware},
+    error::component::connection::restore::Error,
+    gateway::{GuildIdAware, SenderAware},
+    lavalink::{Connection, DelegateMethods, Lavalink, Snapshot, UnwrappedData},
+};
+
+use super::{
+    leave::{disconnect, disconnect_cleanup},
+    users_in_voice,
+};
+
+/// Decodes `encoded_tracks`, with [`None`] in place of each track that could not be decoded.
+///
+/// Errors if none of the tracks could be decoded.
+async fn decode_tracks(
+    lavalink: &Lavalink,
+    guild_id: Id<GuildMarker>,
+    encoded_tracks: &[String],
+) -> LavalinkResult<Vec<Option<TrackData>>> {
+    let client = lavalink.clone_inner();
+    let error = match client.decode_tracks(guild_id, encoded_tracks).await {
+        Ok(tracks) => return Ok(tracks.into_iter().map(Some).collect()),
+        Err(e) => e,
+    };
+
+    // a single undecodable track fails the whole batch, so each track is decoded on its own
+    let mut tracks = Vec::with_capacity(encoded_tracks.len());
+    for encoded in encoded_tracks {
+        let track = client.decode_track(guild_id, encoded).await;
+        if let Err(ref error) = track {
+            tracing::warn!(guild_id = guild_id.get(), %error, "decoding snapshot track failed");
+        }
+        tracks.push(track.ok());
+    }
+    if tracks.iter().all(Option::is_none) {
+        return Err(error);
+    }
+    Ok(tracks)
+}
+
+async fn restore_player(
+    lavalink: &Lavalink,
+    guild_id: Id<GuildMarker>,
+    snapshot: Snapshot,
+) -> LavalinkResult<()> {
+    let player = lavalink
+        .new_player(guild_id, snapshot.text_channel_id())
+        .await?;
+
+    let encoded_tracks = snapshot.encoded_tracks();
+    let tracks = if encoded_tracks.is_empty() {
+        Vec::new()
+    } else {
+        decode_tracks(lavalink, guild_id, &encoded_tracks).await?
+    };
+    let filters = snapshot.filters();
+
+    let data = player.data_unwrapped();
+    let mut data_w = data.write().await;
+    data_w.restore_snapshot(snapshot, tracks);
+    data_w.resume_on(&player, filters).await
+}
+
+/// Restores the player of a guild from its last saved snapshot, rejoining its voice channel.
+///
+/// The snapshot is discarded instead if any of its channels no longer exist, or if no one is
+/// in its voice channel anymore.
+pub async fn restore(
+    cx: &(impl LavalinkAware + CacheAware + SenderAware + GuildIdAware + Sync),
+) -> Result<(), Error> {
+    let guild_id = cx.guild_id();
+    let lavalink = cx.lavalink();
+    let db = lavalink.data();
+
+    let Some(snapshot) = Snapshot::load(db.db(), guild_id).await? else {
+        return Ok(());
+    };
+    if lavalink.has_connection(guild_id).await {
+        return Ok(());
+    }
+
+    let channel_id = snapshot.voice_channel_id();
+    let text_channel_id = snapshot.text_channel_id();
+    let cache = cx.cache();
+    if cache.channel(channel_id).is_none()
+        || cache.channel(text_channel_id).is_none()
+        || users_in_voice(cx, channel_id).is_none_or(|n| n == 0)
+    {
+        tracing::info!("guild {guild_id} discarded player snapshot");
+        lavalink.delete_snapshot(guild_id).await?;
+        return Ok(());
+    }
+
+    let connection = Connection::new(channel_id, text_channel_id, false);
+
+    // CORRECTNESS: as the bot later joins the voice channel, it invokes a
+    // voice state update event, so this is correct.
+    connection.disable_vsu_handler();
+
+    lavalink.new_connection_with(guild_id, connection);
+    cx.sender()
+        .command(&UpdateVoiceState::new(guild_id, channel_id, true, false))?;
+
+    if let Err(e) = restore_player(lavalink, guild_id, snapshot).await {
+        disconnect_cleanup(cx).await?;
+        disconnect(cx)?;
+        return Err(e.into());
+    }
+
+    tracing::info!("guild {guild_id} restored player in {channel_id}");
+    Ok(())
+}
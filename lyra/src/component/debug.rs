@@ -1 +1,78 @@
+use std::fmt::Write;
 
+use lyra_ext::pretty::truncate::PrettyTruncator;
+use lyra_proc::BotGuildCommandGroup;
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_mention::{
+    Mention,
+    timestamp::{Timestamp, TimestampStyle},
+};
+use twilight_model::guild::Permissions;
+
+use crate::{
+    command::model::{BotGuildSlashCommand, GuildSlashCmdCtx},
+    core::{
+        konst::misc::TRACK_RECOVERIES_VIEW_LIMIT,
+        model::{DatabaseAware, response::initial::message::create::RespondWithMessage},
+    },
+    error::CommandResult,
+    gateway::GuildIdAware,
+    lavalink::RecoveryAttempt,
+};
+
+#[derive(CommandModel, CreateCommand, BotGuildCommandGroup)]
+#[command(
+    name = "debug",
+    desc = ".",
+    contexts = "guild",
+    default_permissions = "Self::default_permissions"
+)]
+pub enum Debug {
+    #[command(name = "recoveries")]
+    Recoveries(Recoveries),
+}
+
+impl Debug {
+    const fn default_permissions() -> Permissions {
+        Permissions::MANAGE_GUILD
+    }
+}
+
+fn write_attempt(content: &mut String, attempt: &RecoveryAttempt) -> std::fmt::Result {
+    let at = Timestamp::new(attempt.at().as_secs(), Some(TimestampStyle::RelativeTime));
+    write!(
+        content,
+        "\n- {} `{}` » Attempt `{}`: **{}**",
+        at.mention(),
+        attempt.title().pretty_truncate(60),
+        attempt.attempt(),
+        attempt.step().name(),
+    )?;
+    if let Some(detail) = attempt.detail() {
+        write!(content, " ({})", detail.pretty_truncate(60))?;
+    }
+    Ok(())
+}
+
+/// Views the most recent steps taken to recover stuck tracks.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "recoveries")]
+pub struct Recoveries;
+
+impl BotGuildSlashCommand for Recoveries {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let attempts =
+            RecoveryAttempt::fetch(ctx.db(), ctx.guild_id(), TRACK_RECOVERIES_VIEW_LIMIT).await?;
+        if attempts.is_empty() {
+            ctx.note("No tracks have gotten stuck yet.").await?;
+            return Ok(());
+        }
+
+        let mut content = String::from("🩹 **Recent stuck track recoveries**");
+        for attempt in attempts {
+            write_attempt(&mut content, &attempt).expect("writing to a string must not fail");
+        }
+        ctx.out(content).await?;
+        Ok(())
+    }
+}
@@ -17,7 +17,7 @@ pub struct Uptime;
 
 impl BotSlashCommand for Uptime {
     async fn run(self, mut ctx: SlashCmdCtx) -> CommandResult {
-        let started = lyra_ext::unix_time() - ctx.bot().info().uptime();
+        let started = lyra_ext::unix_time().saturating_sub(ctx.bot().info().uptime());
         let stamp = Timestamp::new(started.as_secs(), Some(TimestampStyle::RelativeTime));
         ctx.out(format!("⏱️ {}.", stamp.mention())).await?;
         Ok(())
@@ -1,15 +1,22 @@
 use twilight_interactions::command::{CommandModel, CreateCommand};
 
 use crate::{
+    LavalinkAndGuildIdAware,
     command::{
-        check,
-        model::{BotGuildSlashCommand, GuildCtx, GuildSlashCmdCtx, RespondWithMessageKind},
+        check::{self, ResolveWithPoll, StartPoll},
+        model::{
+            BotGuildSlashCommand, FollowupKind, GuildCtx, GuildSlashCmdCtx, RespondWithMessageKind,
+        },
+        poll::{Topic as PollTopic, VoidingEvent},
         require,
         util::controller_fmt,
     },
-    core::model::response::initial::message::create::RespondWithMessage,
-    error::component::playback::back::BackError,
-    lavalink::OwnedPlayerData,
+    core::model::response::either::RespondOrFollowup,
+    error::{
+        command::check::{HandlePollError, PollVoidedError},
+        component::playback::back::BackError,
+    },
+    lavalink::{Event, OwnedPlayerData},
 };
 
 /// Jumps to the track before the current one in the queue, wrapping around if queue repeat is enabled.
@@ -30,24 +37,40 @@ impl BotGuildSlashCommand for Back {
 pub async fn back(
     player: require::PlayerInterface,
     data: OwnedPlayerData,
-    ctx: &mut GuildCtx<impl RespondWithMessageKind>,
+    ctx: &mut GuildCtx<impl RespondWithMessageKind + FollowupKind>,
     via_controller: bool,
 ) -> Result<(), BackError> {
     // FAIRNESS: if a member requests to back, they need to be the only person in voice,
     // as backing will be unfair to everyone who queued after this current track: the
     // tracks after the current track will be delayed for the track's duration.
-    check::user_in(require::in_voice(ctx)?.and_unsuppressed()?)?.only()?;
+    // Otherwise, it needs to be decided by a poll.
+    let poll = check::user_in(require::in_voice(ctx)?.and_unsuppressed()?)?
+        .only()
+        .or_else_try_resolve_with(PollTopic::Back)?;
+    let polled = poll.is_some();
 
-    let current_track_title = data
-        .read()
-        .await
-        .queue()
+    let data_r = data.read().await;
+    let queue = data_r.queue();
+    let index = queue.index();
+    let current_track = queue
         .current()
-        .map(|t| t.data().info.title.clone());
+        .map(|t| (t.data().encoded.clone(), t.data().info.title.clone()));
+    drop(data_r);
+
+    poll.and_then_start(ctx).await?;
 
     let mut data_w = data.write().await;
     let queue = data_w.queue_mut();
+    let current_track_unchanged = queue.index() == index
+        && queue.current().map(|t| &t.data().encoded) == current_track.as_ref().map(|(e, _)| e);
+    if polled && !current_track_unchanged {
+        // CORRECTNESS: the current track might have changed while the poll was ongoing, in which
+        // case going back now would go back to a track nobody voted on.
+        return Err(HandlePollError::from(PollVoidedError(VoidingEvent::QueueAdvance)).into());
+    }
+    let current_track_title = current_track.map(|(_, title)| title);
 
+    ctx.get_conn().dispatch(Event::QueueAdvance);
     queue.downgrade_repeat_mode();
     if current_track_title.is_some() {
         // CORRECTNESS: the current track is present and will be ending via the
@@ -59,9 +82,9 @@ pub async fn back(
     let index = queue.current_index().expect("current track exists");
     let message = current_track_title.map_or_else(
         || format!("⏮️ `{}`.", queue[index].data().info.title),
-        |title| format!("⏮️ ~~`{title}`~~.",),
+        |title| format!("⏮️ ~~`{title}`~~."),
     );
-    ctx.out(controller_fmt(ctx, via_controller, &message))
+    ctx.out_f(controller_fmt(ctx, via_controller, &message))
         .await?;
 
     player
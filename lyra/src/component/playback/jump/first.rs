@@ -20,15 +20,9 @@ impl BotGuildSlashCommand for First {
         let player = require::player(&ctx)?;
         let data = player.data();
 
-        let mut data_w = data.write().await;
-        let queue = require::queue_not_empty_mut(&mut data_w)?;
-        let current_track = require::current_track(queue);
-        if let Ok(ref curr) = current_track {
-            check::current_track_is_users(curr, in_voice_with_user)?;
-        }
-        let current_track_exists = current_track.is_ok();
-        let queue_len = queue.len();
-        if queue_len == 1 {
+        let data_r = data.read().await;
+        let queue = require::queue_not_empty(&data_r)?;
+        if queue.len() == 1 {
             ctx.wrng("No where else to jump to.").await?;
             return Ok(());
         }
@@ -38,27 +32,11 @@ impl BotGuildSlashCommand for First {
             return Ok(());
         }
 
-        queue.downgrade_repeat_mode();
-        if current_track_exists {
-            // CORRECTNESS: the current track is present and will be ending via the
-            // `cleanup_now_playing_message_and_play` call later, so this is correct
-            queue.disable_advancing();
-        }
-
-        let index = 0;
-        let mapped_index = queue.map_index_expected(index);
-        ctx.out(format!(
-            "⬅️ Jumped to `{}` (`#{}`).",
-            queue[mapped_index].data().info.title,
-            mapped_index
-        ))
-        .await?;
-        *queue.index_mut() = index;
-        player
-            .cleanup_now_playing_message_and_play(&ctx, mapped_index, &mut data_w)
-            .await?;
-        drop(data_w);
+        let users_track = require::current_track(queue).map_or(Ok(()), |curr| {
+            check::current_track_is_users(&curr, in_voice_with_user)
+        });
+        drop(data_r);
 
-        Ok(())
+        super::jump_to(0, users_track, "⬅️", player, &mut ctx).await
     }
 }
@@ -27,8 +27,8 @@ impl BotGuildSlashCommand for Forward {
         let player = require::player(&ctx)?;
         let data = player.data();
 
-        let mut data_w = data.write().await;
-        let queue = require::queue_not_empty_mut(&mut data_w)?;
+        let data_r = data.read().await;
+        let queue = require::queue_not_empty(&data_r)?;
         let current_track = require::current_track(queue)?;
 
         let jump = i64_as_usize(self.tracks);
@@ -51,32 +51,14 @@ impl BotGuildSlashCommand for Forward {
         }
 
         let skipped = (current_track.position.get()..=index).filter_map(NonZeroUsize::new);
-        check::all_users_track(queue, skipped, in_voice_with_user)?;
-
-        queue.downgrade_repeat_mode();
+        let users_tracks = check::all_users_track(queue, skipped, in_voice_with_user);
+        drop(data_r);
 
         // CORRECTNESS: the current track will always exist as this command cannot be used when the
         // current track doesn't exist, which is possible in two scenarios:
-        // - queue is empty (which is impossible because of the `queue_not_empty_mut` check)
+        // - queue is empty (which is impossible because of the `queue_not_empty` check)
         // - the current queue index is past the end of the queue (which will early returned as
         //   "no where else to jump to"`)
-        // the current track will be ending via the `cleanup_now_playing_message_and_play` call later,
-        // so this is correct.
-        queue.disable_advancing();
-
-        let mapped_index = queue.map_index_expected(index);
-        let track = queue[mapped_index].data();
-        ctx.out(format!(
-            "↪️ Jumped to `{}` (`#{}`).",
-            track.info.title, mapped_index
-        ))
-        .await?;
-        *queue.index_mut() = index;
-        player
-            .cleanup_now_playing_message_and_play(&ctx, mapped_index, &mut data_w)
-            .await?;
-        drop(data_w);
-
-        Ok(())
+        super::jump_to(index, users_tracks, "↪️", player, &mut ctx).await
     }
 }
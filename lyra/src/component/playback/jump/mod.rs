@@ -1,6 +1,24 @@
+use std::num::NonZeroUsize;
+
 use lyra_proc::{BotGuildAutocompleteGroup, BotGuildCommandGroup};
 use twilight_interactions::command::{CommandModel, CreateCommand};
 
+use crate::{
+    LavalinkAndGuildIdAware,
+    command::{
+        check::{ResolveWithPoll, StartPoll},
+        model::GuildSlashCmdCtx,
+        poll::{Topic as PollTopic, VoidingEvent},
+        require,
+    },
+    core::model::{DatabaseAware, response::either::RespondOrFollowup},
+    error::{
+        CommandResult,
+        command::check::{HandlePollError, PollVoidedError, UsersTrackError},
+    },
+    lavalink::Event,
+};
+
 pub mod backward;
 mod first;
 mod forward;
@@ -25,3 +43,72 @@ pub enum Autocomplete {
     #[command(name = "to")]
     To(to::Autocomplete),
 }
+
+/// Jumps to the track at `index`, starting a poll first if the jump is unfair to everyone else in
+/// voice.
+async fn jump_to(
+    index: usize,
+    users_tracks: Result<(), UsersTrackError>,
+    emoji: &str,
+    player: require::PlayerInterface,
+    ctx: &mut GuildSlashCmdCtx,
+) -> CommandResult {
+    let data = player.data();
+    let data_r = data.read().await;
+    let queue = data_r.queue();
+    let mapped_index = queue.map_index_expected(index);
+    let target = queue[mapped_index].data().encoded.clone();
+    let position = NonZeroUsize::new(mapped_index + 1).expect("mapped_index + 1 is non-zero");
+    let topic = PollTopic::Jump(
+        queue[mapped_index].data().info.title.as_str().into(),
+        position,
+    );
+    drop(data_r);
+    let poll = users_tracks.or_else_try_resolve_with(topic)?;
+    let polled = poll.is_some();
+    poll.and_then_start(ctx).await?;
+
+    let mut data_w = data.write().await;
+    let queue = data_w.queue_mut();
+    if index >= queue.len() {
+        ctx.wrng_f("No where else to jump to.").await?;
+        return Ok(());
+    }
+    if polled
+        && (queue.map_index_expected(index) != mapped_index
+            || queue[mapped_index].data().encoded != target)
+    {
+        // CORRECTNESS: the queue might have been modified while the poll was ongoing, in which
+        // case jumping now would jump to a track nobody voted on.
+        return Err(HandlePollError::from(PollVoidedError(VoidingEvent::QueueReindex)).into());
+    }
+
+    ctx.get_conn().dispatch(Event::QueueAdvance);
+    let has_current = require::current_track(queue).is_ok();
+    if has_current {
+        data_w.skip_recorded_play(ctx.db()).await;
+    }
+    let queue = data_w.queue_mut();
+    queue.downgrade_repeat_mode();
+    if has_current {
+        // CORRECTNESS: the current track is present and will be ending via the
+        // `cleanup_now_playing_message_and_play` call later, so this is correct
+        queue.disable_advancing();
+    }
+
+    let mapped_index = queue.map_index_expected(index);
+    let track = queue[mapped_index].data();
+    ctx.out_f(format!(
+        "{emoji} Jumped to `{}` (`#{}`).",
+        track.info.title,
+        mapped_index + 1
+    ))
+    .await?;
+    *queue.index_mut() = index;
+    player
+        .cleanup_now_playing_message_and_play(ctx, mapped_index, &mut data_w)
+        .await?;
+    drop(data_w);
+
+    Ok(())
+}
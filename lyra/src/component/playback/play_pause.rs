@@ -1,15 +1,19 @@
 use twilight_interactions::command::{CommandModel, CreateCommand};
 
 use crate::{
+    LavalinkAndGuildIdAware,
     command::{
-        check,
-        model::{BotGuildSlashCommand, GuildCtx, GuildSlashCmdCtx, RespondWithMessageKind},
+        check::{self, ResolveWithPoll, StartPoll},
+        model::{
+            BotGuildSlashCommand, FollowupKind, GuildCtx, GuildSlashCmdCtx, RespondWithMessageKind,
+        },
+        poll::Topic as PollTopic,
         require,
         util::controller_fmt,
     },
-    core::model::response::initial::message::create::RespondWithMessage,
+    core::model::response::either::RespondOrFollowup,
     error::{CommandResult, component::playback::PlayPauseError},
-    lavalink::OwnedPlayerData,
+    lavalink::{Event, OwnedPlayerData},
 };
 
 /// Toggles the playback of the current track.
@@ -30,34 +34,36 @@ impl BotGuildSlashCommand for PlayPause {
 pub async fn play_pause(
     player: require::PlayerInterface,
     data: OwnedPlayerData,
-    ctx: &mut GuildCtx<impl RespondWithMessageKind>,
+    ctx: &mut GuildCtx<impl RespondWithMessageKind + FollowupKind>,
     via_controller: bool,
 ) -> Result<(), PlayPauseError> {
     let in_voice_with_user = check::user_in(require::in_voice(ctx)?.and_unsuppressed()?)?;
 
     let data_r = data.read().await;
     let pause = !data_r.paused();
-    if pause {
+    let poll = if pause {
         // FAIRNESS: if a member requests to pause, they need to be the only person in voice,
         // as pausing will be unfair to everyone who queued after this current track: the
         // tracks after the current track will be delayed indefinitely until the player
-        // unpaused.
+        // unpaused. Otherwise, it needs to be decided by a poll.
         //
         // TODO: this only serves as a crude approximation, and it should be improved in the
         // future in a fairness rework of some sort. Ideally, if current track is `c`, then:
         // > forall track `x` after `c`: x.requester == c.requester
-        in_voice_with_user.only()?;
+        in_voice_with_user
+            .only()
+            .or_else_try_resolve_with(PollTopic::Pause(pause))?
     } else {
         // FAIRNESS: if a member requests to unpause, it is fair to everyone in voice if the
         // current track is requested by that member as there will be no delays in upcoming
-        // tracks.
-        check::current_track_is_users(
-            &require::current_track(data_r.queue())?,
-            in_voice_with_user,
-        )?;
-    }
+        // tracks. Otherwise, it needs to be decided by a poll.
+        check::current_track_is_users(&require::current_track(data_r.queue())?, in_voice_with_user)
+            .or_else_try_resolve_with(PollTopic::Pause(pause))?
+    };
     drop(data_r);
+    poll.and_then_start(ctx).await?;
 
+    ctx.get_conn().dispatch(Event::PlayerPause);
     let mut data_w = data.write().await;
 
     player.set_pause_with(pause, &mut data_w).await?;
@@ -69,6 +75,6 @@ pub async fn play_pause(
         "⏸️ Resumed."
     };
     let content = controller_fmt(ctx, via_controller, message);
-    ctx.out(content).await?;
+    ctx.out_f(content).await?;
     Ok(())
 }
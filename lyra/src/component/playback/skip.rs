@@ -1,15 +1,22 @@
 use twilight_interactions::command::{CommandModel, CreateCommand};
 
 use crate::{
+    LavalinkAndGuildIdAware,
     command::{
-        check,
-        model::{BotGuildSlashCommand, GuildCtx, GuildSlashCmdCtx, RespondWithMessageKind},
+        check::{self, ResolveWithPoll, StartPoll},
+        model::{
+            BotGuildSlashCommand, FollowupKind, GuildCtx, GuildSlashCmdCtx, RespondWithMessageKind,
+        },
+        poll::{Topic as PollTopic, VoidingEvent},
         require,
         util::controller_fmt,
     },
-    core::model::response::initial::message::create::RespondWithMessage,
-    error::component::playback::skip::SkipError,
-    lavalink::OwnedPlayerData,
+    core::model::{DatabaseAware, response::either::RespondOrFollowup},
+    error::{
+        command::check::{HandlePollError, PollVoidedError},
+        component::playback::skip::SkipError,
+    },
+    lavalink::{Event, OwnedPlayerData},
 };
 
 /// Skip playing the current track.
@@ -30,27 +37,52 @@ impl BotGuildSlashCommand for Skip {
 pub async fn skip(
     player: require::PlayerInterface,
     data: OwnedPlayerData,
-    ctx: &mut GuildCtx<impl RespondWithMessageKind>,
+    ctx: &mut GuildCtx<impl RespondWithMessageKind + FollowupKind>,
     via_controller: bool,
 ) -> Result<(), SkipError> {
-    let data_r = data.read().await;
-    let current_track = require::current_track(data_r.queue())?;
     let in_voice_with_user = check::user_in(require::in_voice(ctx)?.and_unsuppressed()?)?;
 
-    let message = format!("⏭️ ~~`{}`~~.", current_track.track.data().info.title);
+    let data_r = data.read().await;
+    let current_track = require::current_track(data_r.queue())?;
+    let title = current_track.track.data().info.title.clone();
+    let position = current_track.position;
+    let encoded = current_track.track.data().encoded.clone();
 
     // FAIRNESS: if a member requests to skip, it is fair to everyone in voice if the
     // current track is requested by that member as there will be no delays in upcoming
-    // tracks.
-    check::current_track_is_users(&current_track, in_voice_with_user)?;
-
+    // tracks. Otherwise, it needs to be decided by a poll.
+    let users_track = check::current_track_is_users(&current_track, in_voice_with_user);
     drop(data_r);
 
-    let content = controller_fmt(ctx, via_controller, &message);
-    ctx.out(content).await?;
+    let poll =
+        users_track.or_else_try_resolve_with(PollTopic::Skip(title.as_str().into(), position))?;
+    let polled = poll.is_some();
+    poll.and_then_start(ctx).await?;
 
     let mut data_w = data.write().await;
     let queue = data_w.queue_mut();
+    if polled {
+        // CORRECTNESS: the current track might have naturally ended, or the queue might have
+        // been modified while the poll was ongoing, in which case skipping now would skip a
+        // track nobody voted on.
+        let voiding_event = match require::current_track(queue) {
+            Ok(t) if t.position != position => Some(VoidingEvent::QueueAdvance),
+            Ok(t) if t.track.data().encoded != encoded => Some(VoidingEvent::QueueReindex),
+            Ok(_) => None,
+            Err(_) => Some(VoidingEvent::QueueAdvance),
+        };
+        if let Some(event) = voiding_event {
+            return Err(HandlePollError::from(PollVoidedError(event)).into());
+        }
+    }
+
+    let message = format!("⏭️ ~~`{title}`~~.");
+    let content = controller_fmt(ctx, via_controller, &message);
+    ctx.out_f(content).await?;
+
+    ctx.get_conn().dispatch(Event::QueueAdvance);
+    data_w.skip_recorded_play(ctx.db()).await;
+    let queue = data_w.queue_mut();
     queue.downgrade_repeat_mode();
 
     // CORRECTNESS: the current track is present in both scenarios:
@@ -0,0 +1,48 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+
+use crate::{
+    command::{
+        model::{BotGuildSlashCommand, GuildCtx, GuildSlashCmdCtx, RespondWithMessageKind},
+        require,
+        util::controller_fmt,
+    },
+    core::model::response::initial::message::create::RespondWithMessage,
+    error::{CommandResult, component::queue::autoplay::AutoplayError},
+    lavalink::OwnedPlayerData,
+};
+
+/// Toggles automatically adding related tracks once the queue runs out.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "autoplay", contexts = "guild")]
+pub struct Autoplay;
+
+impl BotGuildSlashCommand for Autoplay {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let player = require::player(&ctx)?;
+        let data = player.data();
+        let enabled = !data.read().await.queue().autoplay();
+
+        Ok(autoplay(&mut ctx, data, enabled, false).await?)
+    }
+}
+
+pub async fn autoplay(
+    ctx: &mut GuildCtx<impl RespondWithMessageKind>,
+    data: OwnedPlayerData,
+    enabled: bool,
+    via_controller: bool,
+) -> Result<(), AutoplayError> {
+    data.write()
+        .await
+        .set_autoplay_then_update_and_apply_to_now_playing(enabled)
+        .await?;
+
+    let content = if enabled {
+        "📻 Enabled autoplay; Related tracks will be added once the queue runs out."
+    } else {
+        "**` ⏹ `** Disabled autoplay."
+    };
+    let content = controller_fmt(ctx, via_controller, content);
+    ctx.out(content).await?;
+    Ok(())
+}
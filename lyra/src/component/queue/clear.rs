@@ -5,11 +5,12 @@ use twilight_interactions::command::{CommandModel, CreateCommand};
 use crate::{
     LavalinkAndGuildIdAware,
     command::{
-        check,
+        check::{self, ResolveWithPoll, StartPoll},
         model::{BotGuildSlashCommand, GuildSlashCmdCtx},
+        poll::Topic as PollTopic,
         require,
     },
-    core::model::response::initial::message::create::RespondWithMessage,
+    core::model::response::either::RespondOrFollowup,
     error::CommandResult,
     lavalink::Event,
 };
@@ -29,8 +30,19 @@ impl BotGuildSlashCommand for Clear {
         let data_r = data.read().await;
         let queue = require::queue_not_empty(&data_r)?;
 
+        // FAIRNESS: if a member requests to clear the queue, every track in the queue needs to
+        // be requested by that member, as clearing will be unfair to everyone else who queued.
+        // Otherwise, it needs to be decided by a poll.
         let positions = (1..=queue.len()).filter_map(NonZeroUsize::new);
-        check::all_users_track(queue, positions, in_voice_with_user)?;
+        let users_tracks = check::all_users_track(queue, positions, in_voice_with_user);
+        drop(data_r);
+        users_tracks
+            .or_else_try_resolve_with(PollTopic::Clear)?
+            .and_then_start(&mut ctx)
+            .await?;
+
+        let data_r = data.read().await;
+        let queue = data_r.queue();
         let current_track_exists = require::current_track(queue).is_ok();
 
         if current_track_exists {
@@ -49,7 +61,7 @@ impl BotGuildSlashCommand for Clear {
         ctx.get_conn().dispatch(Event::QueueClear).await?;
 
         data.write().await.queue_mut().clear();
-        ctx.out("⏹️ Cleared the queue.").await?;
+        ctx.out_f("⏹️ Cleared the queue.").await?;
         Ok(())
     }
 }
@@ -0,0 +1,80 @@
+use std::num::NonZeroUsize;
+
+use lyra_ext::pretty::join::PrettyJoiner;
+use twilight_interactions::command::{CommandModel, CreateCommand};
+
+use crate::{
+    command::{
+        check,
+        model::{BotGuildSlashCommand, GuildSlashCmdCtx},
+        require,
+    },
+    core::{
+        konst::misc::ADD_TRACKS_WRAP_LIMIT,
+        model::response::initial::message::create::RespondWithMessage,
+    },
+    error::CommandResult,
+    lavalink::{CorrectTrackInfo, SeenTracks},
+};
+
+/// Removes tracks that are already in the queue before them, from the tracks yet to be played.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "dedupe")]
+pub struct Dedupe;
+
+impl BotGuildSlashCommand for Dedupe {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let in_voice_with_user = check::user_in(require::in_voice(&ctx)?.and_unsuppressed()?)?;
+
+        let player = require::player(&ctx)?;
+        let data = player.data();
+        let data_r = data.read().await;
+        let queue = require::queue_not_empty(&data_r)?;
+
+        // walking the queue in the order that it will be played, so that the first of the
+        // duplicates to be played is kept and only the later ones are removed.
+        let mut seen = SeenTracks::default();
+        let mut positions = (queue.index()..queue.len())
+            .map(|i| queue.map_index_expected(i))
+            .filter(|&i| seen.insert(&queue[i].data().info))
+            .filter_map(|i| NonZeroUsize::new(i + 1))
+            .collect::<Vec<_>>();
+
+        if positions.is_empty() {
+            drop(data_r);
+            ctx.note("There are no duplicate tracks in the queue.")
+                .await?;
+            return Ok(());
+        }
+
+        check::all_users_track(queue, positions.iter().copied(), in_voice_with_user)?;
+        positions.sort_unstable();
+        drop(data_r);
+
+        let mut data_w = data.write().await;
+        let queue = data_w.queue_mut();
+        // the removed tracks are all played after the current track, and every indexer keeps
+        // the relative play order of the remaining tracks, with fair indexers interleaving them
+        // round by round, so the current index still points to the same track after the removal.
+        let removed = queue.dequeue(&positions).collect::<Vec<_>>();
+        let (queue_len, queue_position) = (queue.len(), queue.position());
+        data_w
+            .update_and_apply_now_playing_queue_len_and_position(queue_len, queue_position)
+            .await?;
+        drop(data_w);
+
+        let removed_len = removed.len();
+        let removed_text = if removed_len <= ADD_TRACKS_WRAP_LIMIT {
+            removed
+                .into_iter()
+                .rev()
+                .map(|t| format!("`{}`", t.into_data().info.corrected_title()))
+                .collect::<Box<[_]>>()
+                .pretty_join_with_and()
+        } else {
+            format!("`{removed_len} duplicate tracks`")
+        };
+        ctx.out(format!("**`≡-`** Removed {removed_text}.")).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+use std::fmt::Write;
+
+use lavalink_rs::model::track::TrackData;
+use serde::{Deserialize, Serialize};
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::http::attachment::Attachment;
+
+use crate::{
+    command::{
+        model::{BotGuildSlashCommand, GuildSlashCmdCtx},
+        require,
+    },
+    core::model::response::initial::message::create::RespondWithMessage,
+    error::CommandResult,
+    lavalink::{CorrectTrackInfo, QueueItem},
+};
+
+/// The `#`-prefixed directive in exported `.m3u8` files carrying the encoded Lavalink track of the
+/// entry it precedes, so that the track can be decoded directly when imported.
+pub(super) const M3U_ENCODED_TRACK_DIRECTIVE: &str = "#EXT-X-LAVALINK-TRACK:";
+
+/// A queue exported as a JSON file.
+#[derive(Serialize, Deserialize)]
+pub(super) struct ExportedQueue {
+    pub tracks: Vec<ExportedTrack>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct ExportedTrack {
+    pub encoded: String,
+    pub title: String,
+    pub uri: Option<String>,
+    pub requester: u64,
+    pub playlist: Option<String>,
+}
+
+impl From<&QueueItem> for ExportedTrack {
+    fn from(value: &QueueItem) -> Self {
+        let track = value.data();
+        Self {
+            encoded: track.encoded.clone(),
+            title: track.info.corrected_title().to_owned(),
+            uri: track.info.uri.clone(),
+            requester: value.requester().get(),
+            playlist: value.playlist_data().map(|p| p.corrected_name().to_owned()),
+        }
+    }
+}
+
+impl ExportedQueue {
+    pub(super) fn to_json(&self) -> Vec<u8> {
+        serde_json::to_vec_pretty(self).expect("serialising an exported queue must not fail")
+    }
+}
+
+fn write_m3u_entry(playlist: &mut String, track: &TrackData) -> std::fmt::Result {
+    let info = &track.info;
+    writeln!(
+        playlist,
+        "#EXTINF:{},{} - {}",
+        info.length / 1000,
+        info.corrected_author(),
+        info.corrected_title(),
+    )?;
+    writeln!(playlist, "{M3U_ENCODED_TRACK_DIRECTIVE}{}", track.encoded)?;
+    writeln!(playlist, "{}", info.uri.as_deref().unwrap_or_default())
+}
+
+/// Exports `tracks` as an extended M3U playlist.
+pub(super) fn to_m3u<'a>(tracks: impl IntoIterator<Item = &'a TrackData>) -> String {
+    let mut playlist = String::from("#EXTM3U\n");
+    for track in tracks {
+        write_m3u_entry(&mut playlist, track).expect("writing to a string must not fail");
+    }
+    playlist
+}
+
+/// Exports the queue as files, for importing it again later.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "export")]
+pub struct Export;
+
+impl BotGuildSlashCommand for Export {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let player = require::player(&ctx)?;
+        let data = player.data();
+        let data_r = data.read().await;
+        let queue = require::queue_not_empty(&data_r)?;
+
+        let exported = ExportedQueue {
+            tracks: queue.iter().map(ExportedTrack::from).collect(),
+        };
+        let playlist = to_m3u(queue.iter().map(QueueItem::data));
+        drop(data_r);
+
+        let tracks_len = exported.tracks.len();
+        let json = exported.to_json();
+        let attachments = vec![
+            Attachment::from_bytes(String::from("queue.json"), json, 0),
+            Attachment::from_bytes(String::from("queue.m3u8"), playlist.into_bytes(), 1),
+        ];
+
+        ctx.respond()
+            .content(format!("📤 Exported `{tracks_len} tracks` from the queue."))
+            .attachments(attachments)
+            .await?;
+        Ok(())
+    }
+}
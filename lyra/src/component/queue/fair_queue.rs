@@ -1,35 +1,46 @@
-use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_interactions::command::{CommandModel, CommandOption, CreateCommand, CreateOption};
 
 use crate::{
+    LavalinkAndGuildIdAware,
     command::{
-        check,
+        check::{self, ResolveWithPoll, StartPoll},
         model::{BotGuildSlashCommand, GuildSlashCmdCtx},
+        poll::Topic as PollTopic,
         require,
     },
-    core::model::response::initial::message::create::RespondWithMessage,
+    core::model::response::either::RespondOrFollowup,
     error::CommandResult,
-    lavalink::IndexerType,
+    lavalink::{Event, Fairness, IndexerType},
 };
 
+#[derive(CommandOption, CreateOption, Clone, Copy)]
+enum FairQueueMode {
+    #[option(name = "By track count", value = 0)]
+    TrackCount,
+    #[option(name = "By play time", value = 1)]
+    PlayTime,
+}
+
+impl From<FairQueueMode> for Fairness {
+    fn from(value: FairQueueMode) -> Self {
+        match value {
+            FairQueueMode::TrackCount => Self::TrackCount,
+            FairQueueMode::PlayTime => Self::PlayTime,
+        }
+    }
+}
+
 /// Toggles fair queuing.
 #[derive(CommandModel, CreateCommand)]
 #[command(name = "fair-queue", contexts = "guild")]
-pub struct FairQueue;
+pub struct FairQueue {
+    /// Balance the queue between requesters by which? (If not given, by track count)
+    mode: Option<FairQueueMode>,
+}
 
 impl BotGuildSlashCommand for FairQueue {
     async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
-        check::user_is_dj(&ctx)?;
-
-        // FAIRNESS: if a member requests to enable or disable shuffle, it needs to be
-        // decided by a poll, as modifying the queue indexing order will be unfair to
-        // everyone who queued after this current track: the tracks after the current
-        // track will be delayed for an unspecified amount of time.
-        //
-        // TODO: this only serves as a crude approximation, and should ideally be
-        // decided by a vote, meanwhile disallowing usage if voice only has the
-        // invoked member.
-        let _ = require::in_voice(&ctx)?.and_with_someone_else()?;
-
+        let in_voice_with_user = check::user_in(require::in_voice(&ctx)?)?;
         let data = require::player(&ctx)?.data();
 
         let data_r = data.read().await;
@@ -37,30 +48,43 @@ impl BotGuildSlashCommand for FairQueue {
         let indexer_type = queue.indexer_type();
         drop(data_r);
 
-        match indexer_type {
-            IndexerType::Fair => {
-                data.write()
-                    .await
-                    .set_indexer_then_update_and_apply_to_now_playing(IndexerType::Standard)
-                    .await?;
+        let requested = self.mode.map(Fairness::from);
+        let current = indexer_type.fairness();
+        let fairness = match current {
+            Some(fairness) if requested.is_none_or(|r| r == fairness) => None,
+            _ => Some(requested.unwrap_or(Fairness::TrackCount)),
+        };
 
-                ctx.out("**` ⮆ `** Disabled fair queue.").await?;
-                Ok(())
-            }
-            IndexerType::Shuffled => {
-                ctx.wrng("Cannot enable fair queue as shuffle is currently enabled.")
-                    .await?;
-                Ok(())
-            }
-            IndexerType::Standard => {
-                data.write()
-                    .await
-                    .set_indexer_then_update_and_apply_to_now_playing(IndexerType::Fair)
-                    .await?;
+        // FAIRNESS: if a member requests to enable or disable fair queue, they need to be
+        // the only person in voice, as modifying the queue indexing order will be unfair to
+        // everyone who queued after this current track: the tracks after the current track
+        // will be delayed for an unspecified amount of time. Otherwise, it needs to be
+        // decided by a poll.
+        in_voice_with_user
+            .only()
+            .or_else_try_resolve_with(PollTopic::FairQueue(fairness))?
+            .and_then_start(&mut ctx)
+            .await?;
 
-                ctx.out("⚖️ Enabled fair queue.").await?;
-                Ok(())
-            }
-        }
+        ctx.get_conn().dispatch(Event::QueueReindex);
+        let mut data_w = data.write().await;
+        let shuffled = data_w.queue().indexer_type().shuffled();
+        data_w
+            .set_indexer_then_update_and_apply_to_now_playing(IndexerType::new(fairness, shuffled))
+            .await?;
+        drop(data_w);
+
+        let Some(fairness) = fairness else {
+            ctx.out_f("**` ⮆ `** Disabled fair queue.").await?;
+            return Ok(());
+        };
+        let (verb, by) = match (current, fairness) {
+            (None, Fairness::PlayTime) => ("Enabled", " (by play time)"),
+            (None, Fairness::TrackCount) => ("Enabled", ""),
+            (Some(_), Fairness::PlayTime) => ("Switched", " to balancing by play time"),
+            (Some(_), Fairness::TrackCount) => ("Switched", " to balancing by track count"),
+        };
+        ctx.out_f(format!("⚖️ {verb} fair queue{by}.")).await?;
+        Ok(())
     }
 }
@@ -0,0 +1,21 @@
+mod play;
+mod view;
+
+use lyra_proc::{BotGuildAutocompleteGroup, BotGuildCommandGroup};
+use twilight_interactions::command::{CommandModel, CreateCommand};
+
+#[derive(CommandModel, CreateCommand, BotGuildCommandGroup)]
+#[command(name = "history", desc = ".", contexts = "guild")]
+pub enum History {
+    #[command(name = "view")]
+    View(view::View),
+    #[command(name = "play")]
+    Play(play::Play),
+}
+
+#[derive(CommandModel, BotGuildAutocompleteGroup)]
+#[command(autocomplete = true)]
+pub enum Autocomplete {
+    #[command(name = "play")]
+    Play(play::Autocomplete),
+}
@@ -0,0 +1,190 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+use lyra_ext::pretty::{duration_display::DurationDisplay, truncate::PrettyTruncator};
+use twilight_interactions::command::{AutocompleteValue, CommandModel, CreateCommand};
+use twilight_model::application::command::{CommandOptionChoice, CommandOptionChoiceValue};
+
+use crate::{
+    LavalinkAware,
+    command::model::{
+        BotGuildAutocomplete, BotGuildSlashCommand, GuildAutocompleteCtx, GuildSlashCmdCtx,
+    },
+    core::{
+        konst::discord::COMMAND_CHOICES_LIMIT,
+        model::{
+            CacheAware, DatabaseAware,
+            response::{
+                either::RespondOrFollowup,
+                initial::{autocomplete::RespondAutocomplete, defer::RespondWithDefer},
+            },
+        },
+    },
+    error::{CommandResult, command::AutocompleteResult, component::queue::play},
+    gateway::GuildIdAware,
+    lavalink::HistoryEntry,
+};
+
+fn generate_entry_choice(
+    number: usize,
+    entry: &HistoryEntry,
+    cx: &impl CacheAware,
+) -> CommandOptionChoice {
+    let requester = entry
+        .requester()
+        .and_then(|r| cx.cache().user(r))
+        .map_or_else(
+            || String::from("Unknown User"),
+            |u| {
+                u.global_name
+                    .clone()
+                    .unwrap_or_else(|| u.name.clone())
+                    .pretty_truncate(12)
+                    .to_string()
+            },
+        );
+
+    CommandOptionChoice {
+        name: format!(
+            "#{} ⌛{} 🎤{} 🎵{}",
+            number,
+            entry.length().pretty_display(),
+            requester,
+            entry.title().pretty_truncate(53)
+        ),
+        name_localizations: None,
+        value: CommandOptionChoiceValue::Integer(entry.id()),
+    }
+}
+
+async fn generate_history_choices(
+    focused: &str,
+    finished: &HashSet<i64>,
+    cx: &(impl CacheAware + DatabaseAware + GuildIdAware + Sync),
+) -> Result<Vec<CommandOptionChoice>, sqlx::Error> {
+    let guild_id = cx.guild_id();
+    let focused = focused.trim();
+
+    // an entry number was given, so the choice will be exactly that entry
+    if let Ok(number) = focused.parse::<usize>() {
+        let Some(offset) = number.checked_sub(1) else {
+            return Ok(Vec::new());
+        };
+        let entries = HistoryEntry::fetch(cx.db(), guild_id, offset, 1).await?;
+        return Ok(entries
+            .iter()
+            .filter(|e| !finished.contains(&e.id()))
+            .map(|e| generate_entry_choice(number, e, cx))
+            .collect());
+    }
+
+    let excluded = finished.iter().copied().collect::<Vec<_>>();
+    let entries =
+        HistoryEntry::search(cx.db(), guild_id, focused, &excluded, COMMAND_CHOICES_LIMIT).await?;
+    Ok(entries
+        .iter()
+        .map(|(n, e)| generate_entry_choice(*n, e, cx))
+        .collect())
+}
+
+#[derive(CommandModel)]
+#[command(autocomplete = true)]
+pub struct Autocomplete {
+    track: AutocompleteValue<i64>,
+    track_2: AutocompleteValue<i64>,
+    track_3: AutocompleteValue<i64>,
+    track_4: AutocompleteValue<i64>,
+    track_5: AutocompleteValue<i64>,
+}
+
+impl BotGuildAutocomplete for Autocomplete {
+    async fn execute(self, mut ctx: GuildAutocompleteCtx) -> AutocompleteResult {
+        let tracks = [
+            self.track,
+            self.track_2,
+            self.track_3,
+            self.track_4,
+            self.track_5,
+        ];
+
+        let finished = tracks
+            .iter()
+            .filter_map(|a| match a {
+                AutocompleteValue::Completed(i) => Some(*i),
+                _ => None,
+            })
+            .collect::<HashSet<_>>();
+        let focused = tracks
+            .into_iter()
+            .find_map(|a| match a {
+                AutocompleteValue::Focused(i) => Some(i),
+                _ => None,
+            })
+            .expect("exactly one autocomplete option should be focused");
+
+        let choices = generate_history_choices(&focused, &finished, &ctx).await?;
+        ctx.autocomplete(choices).await?;
+        Ok(())
+    }
+}
+
+/// Adds track(s) from the play history back to the queue.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "play")]
+pub struct Play {
+    /// Which track? [track title / number in history]
+    #[command(autocomplete = true)]
+    track: i64,
+    /// Which track? [track title / number in history] (2)
+    #[command(autocomplete = true)]
+    track_2: Option<i64>,
+    /// Which track? [track title / number in history] (3)
+    #[command(autocomplete = true)]
+    track_3: Option<i64>,
+    /// Which track? [track title / number in history] (4)
+    #[command(autocomplete = true)]
+    track_4: Option<i64>,
+    /// Which track? [track title / number in history] (5)
+    #[command(autocomplete = true)]
+    track_5: Option<i64>,
+}
+
+impl BotGuildSlashCommand for Play {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let ids = [
+            Some(self.track),
+            self.track_2,
+            self.track_3,
+            self.track_4,
+            self.track_5,
+        ]
+        .into_iter()
+        .flatten()
+        .unique()
+        .collect::<Vec<_>>();
+
+        ctx.defer().await?;
+        let guild_id = ctx.guild_id();
+        let entries = HistoryEntry::fetch_by_ids(ctx.db(), guild_id, &ids).await?;
+        if entries.is_empty() {
+            ctx.wrng_f("**No such tracks in the history**; Use the command's autocomplete to pick tracks instead.")
+                .await?;
+            return Ok(());
+        }
+
+        let encoded = entries
+            .iter()
+            .map(|e| e.encoded().to_owned())
+            .collect::<Vec<_>>();
+        let tracks = ctx
+            .lavalink()
+            .clone_inner()
+            .decode_tracks(guild_id, &encoded)
+            .await?;
+
+        super::super::play::enqueue_tracks(&mut ctx, tracks)
+            .await
+            .map_err(play::Error::from)?;
+        Ok(())
+    }
+}
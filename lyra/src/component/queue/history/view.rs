@@ -0,0 +1,201 @@
+use std::fmt::Write;
+
+use futures::StreamExt;
+use lyra_ext::pretty::{duration_display::DurationDisplay, truncate::PrettyTruncator};
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_mention::{
+    Mention,
+    timestamp::{Timestamp, TimestampStyle},
+};
+use twilight_model::channel::message::{Component, Embed, component::ActionRow};
+use twilight_util::builder::embed::{EmbedBuilder, EmbedFooterBuilder};
+use twilight_validate::embed::EmbedValidationError;
+
+use crate::{
+    command::model::{BotGuildSlashCommand, GuildSlashCmdCtx},
+    core::{
+        konst::{
+            colour,
+            misc::{PLAY_HISTORY_PAGE_SIZE, QUEUE_VIEW_TIMEOUT},
+        },
+        model::{
+            BotStateAware, DatabaseAware, HttpAware,
+            response::initial::message::{create::RespondWithMessage, update::RespondWithUpdate},
+        },
+    },
+    error::CommandResult,
+    gateway::GuildIdAware,
+    lavalink::HistoryEntry,
+};
+
+use super::super::view::PageButton;
+
+/// A snapshot of which page of the play history is being viewed.
+struct Page {
+    index: usize,
+    count: usize,
+    total: usize,
+}
+
+impl Page {
+    fn new(total: usize, index: usize) -> Self {
+        let count = total.div_ceil(PLAY_HISTORY_PAGE_SIZE).max(1);
+        Self {
+            index: index.min(count - 1),
+            count,
+            total,
+        }
+    }
+
+    fn new_with(total: usize, index: usize, button: PageButton) -> Self {
+        let page = Self::new(total, index);
+        let index = match button {
+            PageButton::First | PageButton::Current => 0,
+            PageButton::Previous => page.index.saturating_sub(1),
+            PageButton::Next => page.index + 1,
+            PageButton::Last => page.count - 1,
+        };
+        Self::new(total, index)
+    }
+
+    const fn offset(&self) -> usize {
+        self.index * PLAY_HISTORY_PAGE_SIZE
+    }
+
+    const fn is_first(&self) -> bool {
+        self.index == 0
+    }
+
+    const fn is_last(&self) -> bool {
+        self.index + 1 == self.count
+    }
+
+    fn build_row(&self) -> Component {
+        Component::ActionRow(ActionRow {
+            components: vec![
+                PageButton::First.build(self.is_first()),
+                PageButton::Previous.build(self.is_first()),
+                PageButton::Next.build(self.is_last()),
+                PageButton::Last.build(self.is_last()),
+            ],
+        })
+    }
+}
+
+fn write_entry(description: &mut String, number: usize, entry: &HistoryEntry) -> std::fmt::Result {
+    let title = entry.title().pretty_truncate(60);
+    let length = entry.length().pretty_display();
+    let started = Timestamp::new(
+        entry.started().as_secs(),
+        Some(TimestampStyle::RelativeTime),
+    );
+
+    write!(description, "`#{number}` ")?;
+    match entry.uri() {
+        Some(uri) => write!(description, "[{title}]({uri})")?,
+        None => write!(description, "{title}")?,
+    }
+    write!(description, " `⌛{length}`")?;
+    if let Some(requester) = entry.requester() {
+        write!(description, " 🎤{}", requester.mention())?;
+    }
+    write!(description, " {}", started.mention())?;
+    if entry.skipped() {
+        description.write_str(" ⏭️")?;
+    }
+    description.write_char('\n')
+}
+
+fn generate_embed(entries: &[HistoryEntry], page: &Page) -> Result<Embed, EmbedValidationError> {
+    let mut description = String::new();
+    for (number, entry) in (page.offset() + 1..).zip(entries) {
+        write_entry(&mut description, number, entry).expect("writing to a string must not fail");
+    }
+
+    let footer = format!(
+        "Page {}/{} • {} tracks • ⏭️ Skipped",
+        page.index + 1,
+        page.count,
+        page.total,
+    );
+
+    let embed = EmbedBuilder::new()
+        .title("🕘 History")
+        .description(description)
+        .footer(EmbedFooterBuilder::new(footer))
+        .color(colour::EMBED_DEFAULT)
+        .validate()?
+        .build();
+    Ok(embed)
+}
+
+fn generate_empty_embed() -> Embed {
+    EmbedBuilder::new()
+        .title("🕘 History")
+        .description("No tracks have been played yet.")
+        .color(colour::EMBED_DEFAULT)
+        .build()
+}
+
+/// Views the tracks that have been played recently.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "view")]
+pub struct View;
+
+impl BotGuildSlashCommand for View {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let guild_id = ctx.guild_id();
+        let total = HistoryEntry::count(ctx.db(), guild_id).await?;
+        if total == 0 {
+            ctx.respond().embeds([generate_empty_embed()]).await?;
+            return Ok(());
+        }
+
+        let mut page = Page::new(total, 0);
+        let entries =
+            HistoryEntry::fetch(ctx.db(), guild_id, page.offset(), PLAY_HISTORY_PAGE_SIZE).await?;
+        let embed = generate_embed(&entries, &page)?;
+
+        let message = ctx
+            .respond()
+            .embeds([embed])
+            .components([page.build_row()])
+            .await?
+            .retrieve_message()
+            .await?;
+
+        let mut components = ctx
+            .bot()
+            .standby()
+            .wait_for_component_stream(message.id, |_: &_| true);
+
+        // the history is re-fetched on every button press,
+        // so that the view stays up-to-date with tracks being played in the meantime.
+        while let Ok(Some(inter)) =
+            tokio::time::timeout(QUEUE_VIEW_TIMEOUT, components.next()).await
+        {
+            let Some(button) = PageButton::from_interaction(&inter) else {
+                continue;
+            };
+            let mut i = ctx.bot().interaction().ctx(&inter);
+
+            let total = HistoryEntry::count(ctx.db(), guild_id).await?;
+            page = Page::new_with(total, page.index, button);
+            let entries =
+                HistoryEntry::fetch(ctx.db(), guild_id, page.offset(), PLAY_HISTORY_PAGE_SIZE)
+                    .await?;
+            let embed = generate_embed(&entries, &page)?;
+
+            i.update()
+                .embeds([embed])
+                .components([page.build_row()])
+                .await?;
+        }
+
+        ctx.http()
+            .update_message(message.channel_id, message.id)
+            .components(Some(&[]))
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,293 @@
+use std::path::Path;
+
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::channel::Attachment;
+
+use crate::{
+    LavalinkAware,
+    command::model::{BotGuildSlashCommand, GuildSlashCmdCtx},
+    core::{
+        konst::misc::{QUEUE_IMPORT_FILE_SIZE_LIMIT, QUEUE_IMPORT_TRACKS_LIMIT},
+        model::response::{
+            either::RespondOrFollowup,
+            initial::{defer::RespondWithDefer, message::create::RespondWithMessage},
+        },
+    },
+    error::{CommandResult, component::queue::play},
+    gateway::GuildIdAware,
+};
+
+use super::export::{ExportedQueue, M3U_ENCODED_TRACK_DIRECTIVE};
+
+enum ImportedTracks {
+    /// Encoded Lavalink tracks, which can be decoded directly.
+    Encoded(Vec<String>),
+    /// Links to tracks, which have to be loaded the same way as via `/play`.
+    Uris(Vec<Box<str>>),
+}
+
+impl ImportedTracks {
+    fn from_json(content: &str) -> Option<Self> {
+        let queue = serde_json::from_str::<ExportedQueue>(content).ok()?;
+        Some(Self::Encoded(
+            queue.tracks.into_iter().map(|t| t.encoded).collect(),
+        ))
+    }
+
+    fn from_m3u(content: &str) -> Self {
+        let (mut encoded, mut uris) = (Vec::new(), Vec::new());
+        for line in content.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            if let Some(track) = line.strip_prefix(M3U_ENCODED_TRACK_DIRECTIVE) {
+                let track = track.trim();
+                if !track.is_empty() {
+                    encoded.push(track.to_owned());
+                }
+            } else if !line.starts_with('#') {
+                uris.push(line.into());
+            }
+        }
+
+        // playlists exported by the bot always have every track encoded, so if there are any,
+        // the links are only there for other players to use.
+        if encoded.is_empty() {
+            Self::Uris(uris)
+        } else {
+            Self::Encoded(encoded)
+        }
+    }
+
+    const fn len(&self) -> usize {
+        match self {
+            Self::Encoded(tracks) => tracks.len(),
+            Self::Uris(tracks) => tracks.len(),
+        }
+    }
+}
+
+/// Adds track(s) to the queue from a file exported from a queue.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "import")]
+pub struct Import {
+    /// What file? [.json / .m3u8 file]
+    file: Attachment,
+}
+
+impl BotGuildSlashCommand for Import {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let file = self.file;
+        let extension = Path::new(&file.filename)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase);
+        let is_json = match extension.as_deref() {
+            Some("json") => true,
+            Some("m3u" | "m3u8") => false,
+            _ => {
+                ctx.wrng(format!(
+                    "**`{}` is not a queue file**; Queue files must be either `.json` or `.m3u8` files.",
+                    file.filename
+                ))
+                .await?;
+                return Ok(());
+            }
+        };
+        if file.size > QUEUE_IMPORT_FILE_SIZE_LIMIT {
+            ctx.wrng(format!("**`{}` is too large to import.**", file.filename))
+                .await?;
+            return Ok(());
+        }
+
+        let response = reqwest::get(&file.url)
+            .await
+            .and_then(reqwest::Response::error_for_status);
+        let content = match response {
+            Ok(response) => response.text().await.ok(),
+            Err(_) => None,
+        };
+        let Some(content) = content else {
+            ctx.unkn(format!("Failed to download `{}`.", file.filename))
+                .await?;
+            return Ok(());
+        };
+
+        let imported = if is_json {
+            ImportedTracks::from_json(&content)
+        } else {
+            Some(ImportedTracks::from_m3u(&content))
+        };
+        let Some(imported) = imported.filter(|i| i.len() != 0) else {
+            ctx.wrng(format!(
+                "**No tracks found in `{}`**; Queue files can be made via exporting a queue.",
+                file.filename
+            ))
+            .await?;
+            return Ok(());
+        };
+        if imported.len() > QUEUE_IMPORT_TRACKS_LIMIT {
+            ctx.wrng(format!(
+                "**Too many tracks to import: `{}`**; Only up to `{QUEUE_IMPORT_TRACKS_LIMIT}` tracks can be imported at once.",
+                imported.len(),
+            ))
+            .await?;
+            return Ok(());
+        }
+
+        match imported {
+            ImportedTracks::Uris(uris) => {
+                Ok(super::play::play(&mut ctx, uris, super::play::InsertPosition::End).await?)
+            }
+            ImportedTracks::Encoded(encoded) => {
+                ctx.defer().await?;
+                let Ok(tracks) = ctx
+                    .lavalink()
+                    .clone_inner()
+                    .decode_tracks(ctx.guild_id(), &encoded)
+                    .await
+                else {
+                    ctx.wrng_f(format!(
+                        "**Invalid queue file: `{}`**; Some of the tracks could not be decoded.",
+                        file.filename
+                    ))
+                    .await?;
+                    return Ok(());
+                };
+
+                super::play::enqueue_tracks(&mut ctx, tracks)
+                    .await
+                    .map_err(play::Error::from)?;
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lavalink_rs::model::track::{TrackData, TrackInfo};
+
+    use super::{
+        super::export::{ExportedQueue, ExportedTrack, M3U_ENCODED_TRACK_DIRECTIVE, to_m3u},
+        ImportedTracks,
+    };
+
+    fn track(encoded: &str, uri: Option<&str>) -> TrackData {
+        TrackData {
+            encoded: encoded.to_owned(),
+            info: TrackInfo {
+                title: format!("Title {encoded}"),
+                author: String::from("Author"),
+                length: 61_000,
+                uri: uri.map(ToOwned::to_owned),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn tracks() -> [TrackData; 3] {
+        [
+            track("QAAA1", Some("https://example.com/1")),
+            track("QAAA2", None),
+            track("QAAA3", Some("https://example.com/3")),
+        ]
+    }
+
+    fn encoded(imported: Option<ImportedTracks>) -> Vec<String> {
+        match imported {
+            Some(ImportedTracks::Encoded(encoded)) => encoded,
+            Some(ImportedTracks::Uris(_)) => panic!("tracks should be encoded"),
+            None => panic!("tracks should be imported"),
+        }
+    }
+
+    fn uris(imported: ImportedTracks) -> Vec<Box<str>> {
+        match imported {
+            ImportedTracks::Uris(uris) => uris,
+            ImportedTracks::Encoded(_) => panic!("tracks should be links"),
+        }
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let exported = ExportedQueue {
+            tracks: tracks()
+                .iter()
+                .map(|t| ExportedTrack {
+                    encoded: t.encoded.clone(),
+                    title: t.info.title.clone(),
+                    uri: t.info.uri.clone(),
+                    requester: 1,
+                    playlist: None,
+                })
+                .collect(),
+        };
+        let content = String::from_utf8(exported.to_json()).expect("JSON must be UTF-8");
+
+        let imported = ImportedTracks::from_json(&content);
+        assert_eq!(encoded(imported), ["QAAA1", "QAAA2", "QAAA3"]);
+    }
+
+    #[test]
+    fn json_malformed() {
+        assert!(ImportedTracks::from_json("").is_none());
+        assert!(ImportedTracks::from_json("#EXTM3U").is_none());
+        assert!(ImportedTracks::from_json("{}").is_none());
+        assert!(ImportedTracks::from_json(r#"{"tracks": [{"encoded": "QAAA1"}]}"#).is_none());
+        assert!(ImportedTracks::from_json(r#"{"tracks": {}}"#).is_none());
+
+        let imported = ImportedTracks::from_json(r#"{"tracks": []}"#);
+        assert!(encoded(imported).is_empty());
+    }
+
+    #[test]
+    fn m3u_round_trip() {
+        let content = to_m3u(&tracks());
+        assert!(content.starts_with("#EXTM3U\n#EXTINF:61,Author - Title QAAA1\n"));
+        assert_eq!(
+            content
+                .lines()
+                .filter(|l| l.starts_with(M3U_ENCODED_TRACK_DIRECTIVE))
+                .count(),
+            3
+        );
+
+        let imported = ImportedTracks::from_m3u(&content);
+        assert_eq!(encoded(Some(imported)), ["QAAA1", "QAAA2", "QAAA3"]);
+    }
+
+    #[test]
+    fn m3u_without_encoded_tracks() {
+        let content = "#EXTM3U\n\
+            #EXTINF:61,Author - Title\n\
+            https://example.com/1\n\
+            \n\
+            #EXTINF:-1,Stream\n  \
+            https://example.com/2  \n";
+
+        let imported = ImportedTracks::from_m3u(content);
+        assert_eq!(
+            uris(imported),
+            [
+                Box::from("https://example.com/1"),
+                Box::from("https://example.com/2")
+            ]
+        );
+    }
+
+    #[test]
+    fn m3u_malformed() {
+        assert_eq!(ImportedTracks::from_m3u("").len(), 0);
+        assert_eq!(ImportedTracks::from_m3u("#EXTM3U\n#EXTINF:1,\n").len(), 0);
+        assert_eq!(
+            ImportedTracks::from_m3u(r#"{"tracks": []}"#).len(),
+            1,
+            "non-directive lines are taken as links"
+        );
+
+        let content = format!(
+            "{M3U_ENCODED_TRACK_DIRECTIVE}\n{M3U_ENCODED_TRACK_DIRECTIVE}  \nhttps://example.com/1\n"
+        );
+        let imported = ImportedTracks::from_m3u(&content);
+        assert_eq!(uris(imported), [Box::from("https://example.com/1")]);
+    }
+}
@@ -1,31 +1,45 @@
+mod autoplay;
 mod clear;
+mod dedupe;
+mod export;
 mod fair_queue;
+mod history;
+mod import;
 mod mov;
 mod play;
+mod playlist;
 mod remove;
 mod remove_range;
 mod repeat;
+mod search;
 mod shuffle;
+mod view;
 
+pub use autoplay::{Autoplay, autoplay};
 pub use clear::Clear;
 pub use fair_queue::FairQueue;
+pub use history::{Autocomplete as HistoryAutocomplete, History};
 use lyra_ext::{
     num::{i64_as_usize, usize_as_i64},
     pretty::{duration_display::DurationDisplay, join::PrettyJoiner, truncate::PrettyTruncator},
 };
 
 pub use mov::{Autocomplete as MoveAutocomplete, Move};
-pub use play::AddToQueue;
+pub use play::{AddToQueue, PlayNext};
 pub use play::{Autocomplete as PlayAutocomplete, File as PlayFile, Play};
+pub use playlist::{Autocomplete as PlaylistAutocomplete, Playlist};
 pub use remove::{Autocomplete as RemoveAutocomplete, Remove};
 pub use remove_range::{Autocomplete as RemoveRangeAutocomplete, RemoveRange};
 pub use repeat::{Repeat, get_next_repeat_mode, repeat};
+pub use search::Search;
 pub use shuffle::{Shuffle, shuffle};
 
 use std::{collections::HashSet, num::NonZeroUsize, time::Duration};
 
 use fuzzy_matcher::FuzzyMatcher;
 use itertools::Itertools;
+use lyra_proc::BotGuildCommandGroup;
+use twilight_interactions::command::{CommandModel, CreateCommand};
 use twilight_model::application::command::{CommandOptionChoice, CommandOptionChoiceValue};
 
 use crate::{
@@ -46,6 +60,19 @@ use crate::{
     lavalink::{CorrectTrackInfo, QueueItem},
 };
 
+#[derive(CommandModel, CreateCommand, BotGuildCommandGroup)]
+#[command(name = "queue", desc = ".", contexts = "guild")]
+pub enum Queue {
+    #[command(name = "view")]
+    View(view::View),
+    #[command(name = "dedupe")]
+    Dedupe(dedupe::Dedupe),
+    #[command(name = "export")]
+    Export(export::Export),
+    #[command(name = "import")]
+    Import(Box<import::Import>),
+}
+
 fn generate_position_choice(
     position: NonZeroUsize,
     track: &QueueItem,
@@ -156,7 +183,7 @@ pub fn generate_position_choices_from_fuzzy_match<'a>(
             Some((
                 p,
                 t,
-                FUZZY_MATCHER.fuzzy_match(&format!("{requester} {author} {title}",), focused)?,
+                FUZZY_MATCHER.fuzzy_match(&format!("{requester} {author} {title}"), focused)?,
             ))
         })
         .sorted_by_key(|(_, _, s)| -s)
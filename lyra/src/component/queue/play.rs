@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{fmt::Write, num::NonZeroUsize, time::Duration};
 
 use futures::future;
 use itertools::{Either, Itertools};
@@ -21,13 +21,17 @@ use twilight_model::{
         interaction::InteractionContextType,
     },
     channel::{Attachment, Message},
-    id::{Id, marker::GuildMarker},
+    id::{
+        Id,
+        marker::{GuildMarker, UserMarker},
+    },
 };
 use twilight_util::builder::command::CommandBuilder;
 
 use crate::{
     LavalinkAware,
     command::{
+        check,
         common::PlaySource,
         model::{
             BotGuildAutocomplete, BotGuildMessageCommand, BotGuildSlashCommand, FollowupKind,
@@ -36,10 +40,11 @@ use crate::{
         },
         require, util,
     },
+    component::config::limits::{LimitedTracks, QueueLimits},
     core::{
         konst::{discord::COMMAND_CHOICES_LIMIT, misc::ADD_TRACKS_WRAP_LIMIT},
         model::{
-            UserIdAware,
+            BotStateAware, UserIdAware,
             response::{
                 either::RespondOrFollowup,
                 initial::{
@@ -54,14 +59,75 @@ use crate::{
         CommandResult, LoadFailed as LoadFailedError,
         command::AutocompleteResult,
         component::queue::play::{self, LoadTrackProcessManyError, QueryError},
+        core::RespondError,
+        lavalink::UpdateNowPlayingMessageError,
     },
     gateway::GuildIdAware,
     lavalink::{
-        CorrectPlaylistInfo, CorrectTrackInfo, PlaylistAwareTrackData, PlaylistMetadata,
-        UnwrappedData, UnwrappedPlayerInfoUri, make_playlist_aware,
+        CorrectPlaylistInfo, CorrectTrackInfo, OwnedPlayerData, PlaylistAwareTrackData,
+        PlaylistMetadata, Queue, SeenTracks, UnwrappedData, UnwrappedPlayerInfoUri,
+        make_playlist_aware,
     },
 };
 
+/// Where in the queue to add tracks.
+#[derive(Clone, Copy)]
+pub enum InsertPosition {
+    /// At the end of the queue.
+    End,
+    /// Right after the current track.
+    Next,
+    /// Right before the track at a position in the queue, or at the end of the queue if the
+    /// position is right after the last track.
+    At(NonZeroUsize),
+}
+
+impl InsertPosition {
+    /// Parses the `position` option of the play commands, returning [`None`] if invalid.
+    fn parse(input: Option<&str>) -> Option<Self> {
+        let Some(input) = input.map(str::trim) else {
+            return Some(Self::End);
+        };
+        if input.eq_ignore_ascii_case("next") {
+            return Some(Self::Next);
+        }
+        input.parse().ok().map(Self::At)
+    }
+
+    /// The index that tracks are to be inserted at in `queue`, in the order that the tracks will
+    /// be played, or [`None`] if they are to be added to the end.
+    fn resolve(self, queue: &Queue) -> Option<usize> {
+        match self {
+            Self::End => None,
+            Self::Next => Some(queue.index() + usize::from(queue.current().is_some())),
+            Self::At(position) => {
+                let index = position.get() - 1;
+                Some(queue.unmap_index(index).unwrap_or(index))
+            }
+        }
+    }
+
+    /// Like [`Self::resolve`], but for when there is no queue yet.
+    const fn resolve_in_empty(self) -> Option<usize> {
+        match self {
+            Self::End => None,
+            Self::Next => Some(0),
+            Self::At(position) => Some(position.get() - 1),
+        }
+    }
+}
+
+async fn respond_invalid_position(
+    ctx: &mut GuildSlashCmdCtx,
+    input: &str,
+) -> Result<(), RespondError> {
+    ctx.wrng(format!(
+        "**Invalid position: `{input}`**; Position must either be `next` or a position in the queue."
+    ))
+    .await?;
+    Ok(())
+}
+
 struct LoadTrackContext {
     guild_id: Id<GuildMarker>,
     lavalink: LavalinkClient,
@@ -160,6 +226,78 @@ impl LoadTrackResults {
 
         (tracks, playlists)
     }
+
+    /// Describes the loaded tracks and playlists, for displaying in a response.
+    fn describe(&self) -> String {
+        let (tracks, playlists) = self.split();
+        let playlists = playlists
+            .iter()
+            .map(|p| (&p.metadata, p.tracks.len()))
+            .collect::<Vec<_>>();
+        describe_tracks(&tracks, &playlists)
+    }
+}
+
+/// Describes the tracks that were accepted into the queue, for displaying in a response.
+///
+/// Consecutive tracks from the same playlist are described together.
+fn describe_accepted(accepted: &[PlaylistAwareTrackData]) -> String {
+    let tracks = accepted
+        .iter()
+        .filter(|t| t.playlist().is_none())
+        .map(PlaylistAwareTrackData::inner)
+        .collect::<Vec<_>>();
+    let playlists = accepted
+        .iter()
+        .filter_map(PlaylistAwareTrackData::playlist)
+        .dedup_by_with_count(|a, b| std::ptr::eq(*a, *b))
+        .map(|(count, p)| (p, count))
+        .collect::<Vec<_>>();
+    describe_tracks(&tracks, &playlists)
+}
+
+/// Describes `tracks` and the number of tracks from each of `playlists`.
+fn describe_tracks(tracks: &[&TrackData], playlists: &[(&PlaylistMetadata, usize)]) -> String {
+    let tracks_len = tracks.len();
+    let track_text = match tracks_len {
+        0 => String::new(),
+        1..=ADD_TRACKS_WRAP_LIMIT => tracks
+            .iter()
+            .map(|t| {
+                format!(
+                    "[`{}`](<{}>)",
+                    t.info.corrected_title(),
+                    t.info.uri_unwrapped()
+                )
+            })
+            .collect::<Vec<_>>()
+            .pretty_join_with_and(),
+        _ => format!("`{tracks_len} tracks`"),
+    };
+    let playlists_len = playlists.len();
+    let playlist_text = match playlists_len {
+        0 => String::new(),
+        1..=ADD_TRACKS_WRAP_LIMIT => playlists
+            .iter()
+            .map(|(metadata, len)| {
+                format!(
+                    "`{len} tracks` from playlist [`{}`](<{}>)",
+                    metadata.corrected_name(),
+                    metadata.uri
+                )
+            })
+            .collect::<Vec<_>>()
+            .pretty_join_with_and(),
+        _ => format!(
+            "`{} tracks` in total from `{playlists_len} playlists`",
+            playlists.iter().map(|(_, len)| len).sum::<usize>(),
+        ),
+    };
+    [track_text, playlist_text]
+        .into_iter()
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .pretty_join_with_and()
 }
 
 impl From<LoadTrackResults> for Vec<PlaylistAwareTrackData> {
@@ -323,14 +461,25 @@ impl BotGuildAutocomplete for Autocomplete {
     }
 }
 
-async fn play(
+pub(super) async fn play(
     ctx: &mut GuildCtx<impl RespondWithMessageKind + FollowupKind + RespondWithDeferKind>,
     queries: impl IntoIterator<Item = Box<str>> + Send,
+    position: InsertPosition,
 ) -> Result<(), play::Error> {
     ctx.defer().await?;
+    load_and_play(ctx, queries, position).await
+}
+
+/// Loads `queries` and adds the loaded tracks to the queue, responding via followups as the
+/// interaction must already be acknowledged.
+pub(super) async fn load_and_play(
+    ctx: &mut GuildCtx<impl RespondWithMessageKind + FollowupKind>,
+    queries: impl IntoIterator<Item = Box<str>> + Send,
+    position: InsertPosition,
+) -> Result<(), play::Error> {
     let load_ctx = LoadTrackContext::from(&*ctx);
     match load_ctx.process_many(queries).await {
-        Ok(results) => Ok(handle_load_track_results(ctx, results).await?),
+        Ok(results) => Ok(handle_load_track_results(ctx, results, position).await?),
         Err(e) => match e {
             LoadTrackProcessManyError::Query(query) => match query {
                 QueryError::LoadFailed(LoadFailedError(query)) => {
@@ -356,84 +505,260 @@ async fn play(
 async fn handle_load_track_results(
     ctx: &mut GuildCtx<impl RespondWithMessageKind + FollowupKind>,
     results: LoadTrackResults,
+    position: InsertPosition,
 ) -> Result<(), play::HandleLoadTrackResultsError> {
-    let (tracks, playlists) = results.split();
-    let tracks_len = tracks.len();
-    let track_text = match tracks_len {
-        0 => String::new(),
-        1..=ADD_TRACKS_WRAP_LIMIT => tracks
-            .iter()
-            .map(|t| {
-                format!(
-                    "[`{}`](<{}>)",
-                    t.info.corrected_title(),
-                    t.info.uri_unwrapped()
-                )
-            })
-            .collect::<Vec<_>>()
-            .pretty_join_with_and(),
-        _ => format!("`{tracks_len} tracks`"),
-    };
-    let playlists_len = playlists.len();
-    let playlist_text = match playlists_len {
-        0 => String::new(),
-        1..=ADD_TRACKS_WRAP_LIMIT => playlists
-            .iter()
-            .map(|p| {
-                format!(
-                    "`{} tracks` from playlist [`{}`](<{}>)",
-                    p.tracks.len(),
-                    p.metadata.corrected_name(),
-                    p.metadata.uri
-                )
-            })
-            .collect::<Vec<_>>()
-            .pretty_join_with_and(),
-        _ => format!(
-            "`{} tracks` in total from `{} playlists`",
-            playlists.iter().fold(0, |l, p| l + p.tracks.len()),
-            playlists_len
-        ),
+    let enqueued_text = results.describe();
+    let loaded_tracks = Vec::from(results);
+    let limits = queue_limits(ctx).await?;
+    let user_id = ctx.user_id();
+
+    // the limits are applied before joining and creating a player, so that nothing is created
+    // if none of the tracks can be added.
+    let existing_data = require::player(ctx).ok().map(|player| player.data());
+    let limited = match existing_data {
+        Some(ref data) => {
+            let data_r = data.read().await;
+            let limited =
+                apply_queue_limits(limits, Some(data_r.queue()), user_id, loaded_tracks.clone());
+            drop(data_r);
+            limited
+        }
+        None => apply_queue_limits(limits, None, user_id, loaded_tracks.clone()),
     };
-    let enqueued_text = [track_text, playlist_text]
-        .into_iter()
-        .filter(|s| !s.is_empty())
-        .collect::<Vec<_>>()
-        .pretty_join_with_and();
+    if let Err(warning) = describe_limited(limited, &enqueued_text, loaded_tracks.len()) {
+        ctx.wrng_f(warning).await?;
+        return Ok(());
+    }
+    if let Some(warning) = check_insert_position(ctx, existing_data.as_ref(), position).await {
+        ctx.wrng_f(warning).await?;
+        return Ok(());
+    }
+
     util::auto_join_or_check_in_voice_with_user_and_check_not_suppressed(ctx).await?;
-    let total_tracks = Vec::from(results);
-    let total_tracks_len = total_tracks.len();
-    let plus = match total_tracks_len {
-        0 => panic!("no tracks or playlists loaded"),
+    let player = util::auto_new_player(ctx).await?;
+    let data = player.data_unwrapped();
+
+    let (content, first_track) = match enqueue_at(
+        &data,
+        limits,
+        loaded_tracks,
+        &enqueued_text,
+        user_id,
+        position,
+    )
+    .await?
+    {
+        Ok(enqueued) => enqueued,
+        Err(warning) => {
+            ctx.wrng_f(warning).await?;
+            return Ok(());
+        }
+    };
+    ctx.out_f(content).await?;
+    player.play(first_track.inner()).await?;
+    Ok(())
+}
+
+/// Describes the tracks accepted by the queue limits as the content of the response, along with
+/// the accepted tracks, or returns why none of the `loaded_len` tracks were accepted as a
+/// warning.
+fn describe_limited(
+    limited: LimitedTracks,
+    enqueued_text: &str,
+    loaded_len: usize,
+) -> Result<(String, Vec<PlaylistAwareTrackData>), String> {
+    let rejected_text = (limited.rejected() != 0).then(|| limited.rejected_reasons());
+    let accepted_duplicates = limited.accepted_duplicates();
+    let accepted = limited.into_accepted();
+    let accepted_len = accepted.len();
+    let plus = match accepted_len {
+        0 => {
+            let Some(reasons) = rejected_text else {
+                panic!("no tracks or playlists loaded")
+            };
+            return Err(format!(
+                "**None of the `{loaded_len} tracks` can be added**; Of those tracks, {reasons}."
+            ));
+        }
         1 => "**`＋`**",
         _ => "**`≡+`**",
     };
-    let first_track = total_tracks
+    let mut content = rejected_text.map_or_else(
+        || format!("{plus} Added {enqueued_text}."),
+        |reasons| {
+            format!(
+                "{plus} Added {}.\n\
+                -# Of the `{} tracks` not added, {reasons}.",
+                describe_accepted(&accepted),
+                loaded_len - accepted_len,
+            )
+        },
+    );
+    if accepted_duplicates != 0 {
+        write!(
+            content,
+            "\n-# `{accepted_duplicates}` of the added tracks were already in the queue."
+        )
+        .expect("writing to a string must not fail");
+    }
+    Ok((content, accepted))
+}
+
+/// Checks whether the user can add tracks at `position`, returning why as a warning if not, with
+/// `data` being that of the player if there is one.
+///
+/// Members who are not DJs can only add tracks ahead of their own tracks.
+async fn check_insert_position(
+    ctx: &GuildCtx<impl RespondWithMessageKind + FollowupKind>,
+    data: Option<&OwnedPlayerData>,
+    position: InsertPosition,
+) -> Option<String> {
+    let user_id = ctx.user_id();
+    let (index, queue_len, played, fair, ahead_of_others) = match data {
+        Some(data) => {
+            let data_r = data.read().await;
+            let queue = data_r.queue();
+            let queue_len = queue.len();
+            let index = position.resolve(queue)?;
+            // if nothing is playing, the tracks before the queue index have all been played
+            let played = if queue.current().is_some() {
+                0
+            } else {
+                queue.index().min(queue_len)
+            };
+            let fair = queue.indexer_type().fairness().is_some();
+            let ahead_of_others = queue
+                .iter_mapped_positions_and_items(index..queue_len)
+                .any(|(_, t)| t.requester() != user_id);
+            drop(data_r);
+            (index, queue_len, played, fair, ahead_of_others)
+        }
+        None => (position.resolve_in_empty()?, 0, 0, false, false),
+    };
+
+    if fair {
+        return Some(String::from(
+            "**Cannot add tracks at a position while fair queue is enabled**; Fair queue positions the tracks by itself, so add them without a position instead.",
+        ));
+    }
+    if index > queue_len {
+        return Some(format!(
+            "**Invalid position: `{}`**; Position must be from `1` to `{}`.",
+            index + 1,
+            queue_len + 1,
+        ));
+    }
+    if index < played {
+        return Some(String::from(
+            "**Cannot add tracks before tracks that were already played**; Nothing is playing, so add them after those tracks instead.",
+        ));
+    }
+    (ahead_of_others && !check::is_user_dj(ctx)).then(|| {
+        String::from("**Cannot add tracks ahead of other members' tracks**; Only DJs can do so.")
+    })
+}
+
+/// Applies `limits` to `tracks` and adds the accepted tracks to the queue at `position`,
+/// returning the content of the response along with the first added track, or why none of the
+/// tracks were accepted as a warning.
+async fn enqueue_at(
+    data: &OwnedPlayerData,
+    limits: QueueLimits,
+    tracks: Vec<PlaylistAwareTrackData>,
+    enqueued_text: &str,
+    requester: Id<UserMarker>,
+    position: InsertPosition,
+) -> Result<Result<(String, PlaylistAwareTrackData), String>, UpdateNowPlayingMessageError> {
+    let mut data_w = data.write().await;
+    // CORRECTNESS: the limits must be applied again under the same guard the tracks are added
+    // with, as other tracks might have been added since they were checked before joining, in
+    // which case adding all of the tracks checked then could exceed the limits.
+    let loaded_len = tracks.len();
+    let limited = apply_queue_limits(limits, Some(data_w.queue()), requester, tracks);
+    let (mut content, accepted) = match describe_limited(limited, enqueued_text, loaded_len) {
+        Ok(described) => described,
+        Err(warning) => return Ok(Err(warning)),
+    };
+    let first_track = accepted
         .first()
-        .expect("at least one track must be loaded")
+        .expect("at least one track must be accepted")
         .clone();
-    let player = util::auto_new_player(ctx).await?;
-    let data = player.data_unwrapped();
-    let (now_playing_msg_exists, queue_len) = {
-        let data_r = data.read().await;
-        let queue = data_r.queue();
-        let pair = (require::current_track(queue).is_ok(), queue.len());
-        drop(data_r);
-        pair
+
+    let queue = data_w.queue_mut();
+    let now_playing_msg_exists = require::current_track(queue).is_ok();
+    let inserted = if let Some(index) = position.resolve(queue) {
+        queue.enqueue_at(accepted, requester, index)
+    } else {
+        queue.enqueue(accepted, requester);
+        true
     };
+
     if now_playing_msg_exists {
-        data.write()
-            .await
-            .update_and_apply_now_playing_queue_len(queue_len + total_tracks_len)
+        let (queue_len, queue_position) = (queue.len(), queue.position());
+        data_w
+            .update_and_apply_now_playing_queue_len_and_position(queue_len, queue_position)
             .await?;
     }
-    data.write()
-        .await
-        .queue_mut()
-        .enqueue(total_tracks, ctx.user_id());
-    ctx.out_f(format!("{plus} Added {enqueued_text}.")).await?;
-    player.play(first_track.inner()).await?;
-    Ok(())
+    drop(data_w);
+
+    if !inserted {
+        content
+            .push_str("\n-# Fair queue is enabled, so the tracks were positioned by it instead.");
+    }
+    Ok(Ok((content, first_track)))
+}
+
+/// The guild's queue limits as they apply to the user, which is only the duplicate policy if the
+/// user is a DJ.
+async fn queue_limits(
+    ctx: &GuildCtx<impl RespondWithMessageKind + FollowupKind>,
+) -> Result<QueueLimits, sqlx::Error> {
+    let limits = ctx
+        .bot()
+        .guild_configs()
+        .get(ctx.guild_id())
+        .await?
+        .limits();
+    Ok(if check::is_user_dj(ctx) {
+        limits.for_dj()
+    } else {
+        limits
+    })
+}
+
+/// Applies `limits` to `tracks` about to be added by `user_id` to `queue`, if there is one.
+fn apply_queue_limits(
+    limits: QueueLimits,
+    queue: Option<&Queue>,
+    user_id: Id<UserMarker>,
+    tracks: Vec<PlaylistAwareTrackData>,
+) -> LimitedTracks {
+    let mut seen = SeenTracks::default();
+    let (mut upcoming, mut upcoming_by_requester) = (0, 0);
+    if let Some(queue) = queue {
+        for (_, t) in queue.iter_mapped_positions_and_items(queue.index()..queue.len()) {
+            upcoming += 1;
+            upcoming_by_requester += usize::from(t.requester() == user_id);
+            if !limits.duplicates().is_allow() {
+                seen.insert(&t.data().info);
+            }
+        }
+    }
+
+    limits.apply(tracks, upcoming, upcoming_by_requester, seen)
+}
+
+/// Adds already loaded tracks to the queue, the same way as loading them via `/play` would.
+///
+/// # Panics
+///
+/// This function panics if `tracks` is empty.
+pub(super) async fn enqueue_tracks(
+    ctx: &mut GuildCtx<impl RespondWithMessageKind + FollowupKind>,
+    tracks: impl IntoIterator<Item = TrackData>,
+) -> Result<(), play::HandleLoadTrackResultsError> {
+    let results = tracks.into_iter().map(LoadTrackResult::Track).collect();
+    handle_load_track_results(ctx, LoadTrackResults(results), InsertPosition::End).await
 }
 
 /// Adds track(s) to the queue.
@@ -458,6 +783,9 @@ pub struct Play {
     /// Search from where? (if not given, Youtube)
     #[command(rename = "source")]
     _source: Option<PlaySource>,
+    /// Add where? [`next` / position in queue] (If not given, at the end of the queue)
+    #[command(min_length = 1)]
+    position: Option<String>,
 }
 
 impl BotGuildSlashCommand for Play {
@@ -473,7 +801,12 @@ impl BotGuildSlashCommand for Play {
         .flatten()
         .map(String::into_boxed_str);
 
-        Ok(play(&mut ctx, queries).await?)
+        let Some(position) = InsertPosition::parse(self.position.as_deref()) else {
+            respond_invalid_position(&mut ctx, self.position.as_deref().unwrap_or_default())
+                .await?;
+            return Ok(());
+        };
+        Ok(play(&mut ctx, queries, position).await?)
     }
 }
 
@@ -491,6 +824,9 @@ pub struct File {
     track_4: Option<Attachment>,
     /// What track? (5)
     track_5: Option<Attachment>,
+    /// Add where? [`next` / position in queue] (If not given, at the end of the queue)
+    #[command(min_length = 1)]
+    position: Option<String>,
 }
 
 impl BotGuildSlashCommand for File {
@@ -516,8 +852,13 @@ impl BotGuildSlashCommand for File {
             return Ok(());
         }
 
+        let Some(position) = InsertPosition::parse(self.position.as_deref()) else {
+            respond_invalid_position(&mut ctx, self.position.as_deref().unwrap_or_default())
+                .await?;
+            return Ok(());
+        };
         let urls = files.into_iter().map(|f| f.url.into());
-        Ok(play(&mut ctx, urls).await?)
+        Ok(play(&mut ctx, urls, position).await?)
     }
 }
 
@@ -562,14 +903,33 @@ impl AddToQueue {
 
 impl BotGuildMessageCommand for AddToQueue {
     async fn run(mut ctx: GuildMessageCmdCtx) -> CommandResult {
-        let message = ctx.target_message();
+        add_from_message(&mut ctx, InsertPosition::End).await
+    }
+}
 
-        let queries = extract_queries(message);
-        if queries.is_empty() {
-            ctx.wrng("No audio files or URLs found in this message.")
-                .await?;
-            return Ok(());
-        }
-        Ok(play(&mut ctx, queries).await?)
+pub struct PlayNext;
+
+impl PlayNext {
+    pub const NAME: &'static str = "⏭️ Play next";
+    pub fn create_command() -> Command {
+        CommandBuilder::new(Self::NAME, String::new(), CommandType::Message)
+            .contexts(std::iter::once(InteractionContextType::Guild))
+            .build()
+    }
+}
+
+impl BotGuildMessageCommand for PlayNext {
+    async fn run(mut ctx: GuildMessageCmdCtx) -> CommandResult {
+        add_from_message(&mut ctx, InsertPosition::Next).await
+    }
+}
+
+async fn add_from_message(ctx: &mut GuildMessageCmdCtx, position: InsertPosition) -> CommandResult {
+    let queries = extract_queries(ctx.target_message());
+    if queries.is_empty() {
+        ctx.wrng("No audio files or URLs found in this message.")
+            .await?;
+        return Ok(());
     }
+    Ok(play(ctx, queries, position).await?)
 }
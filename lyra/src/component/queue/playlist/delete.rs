@@ -0,0 +1,43 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+
+use crate::{
+    command::model::{BotGuildSlashCommand, GuildSlashCmdCtx},
+    core::model::{
+        DatabaseAware, UserIdAware, response::initial::message::create::RespondWithMessage,
+    },
+    error::CommandResult,
+};
+
+/// Deletes one of your saved playlists.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "delete")]
+pub struct Delete {
+    /// Which playlist?
+    #[command(autocomplete = true)]
+    name: String,
+}
+
+impl BotGuildSlashCommand for Delete {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let deleted = sqlx::query_scalar!(
+            "DELETE FROM user_playlists WHERE owner = $1 AND name = $2 RETURNING id;",
+            ctx.user_id().get().cast_signed(),
+            self.name,
+        )
+        .fetch_optional(ctx.db())
+        .await?;
+
+        if deleted.is_none() {
+            ctx.wrng(format!(
+                "**You have no playlist named `{}`**; Use the command's autocomplete to pick a playlist instead.",
+                self.name
+            ))
+            .await?;
+            return Ok(());
+        }
+
+        ctx.out(format!("🗑️ Deleted playlist `{}`.", self.name))
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use std::{fmt::Write, time::Duration};
+
+use lyra_ext::pretty::{duration_display::DurationDisplay, truncate::PrettyTruncator};
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_util::builder::embed::{EmbedBuilder, EmbedFooterBuilder};
+
+use crate::{
+    command::model::{BotGuildSlashCommand, GuildSlashCmdCtx},
+    core::{
+        konst::{colour, misc::USER_PLAYLISTS_LIMIT},
+        model::{
+            DatabaseAware, UserIdAware, response::initial::message::create::RespondWithMessage,
+        },
+    },
+    error::CommandResult,
+};
+
+/// Lists your saved playlists.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "list")]
+pub struct List;
+
+impl BotGuildSlashCommand for List {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let playlists = sqlx::query!(
+            r#"SELECT
+                p.name,
+                COUNT(t.position) as "tracks!",
+                COALESCE(SUM(t.length), 0)::bigint as "length!"
+            FROM user_playlists p
+            LEFT JOIN user_playlist_tracks t ON t.playlist = p.id
+            WHERE p.owner = $1
+            GROUP BY p.id
+            ORDER BY p.name;"#,
+            ctx.user_id().get().cast_signed(),
+        )
+        .fetch_all(ctx.db())
+        .await?;
+
+        let mut description = String::new();
+        for p in &playlists {
+            let length = Duration::from_millis(p.length.max(0).cast_unsigned()).pretty_display();
+            writeln!(
+                description,
+                "`{}` `{} tracks` `⌛{length}`",
+                p.name.pretty_truncate(60),
+                p.tracks,
+            )
+            .expect("writing to a string must not fail");
+        }
+        if description.is_empty() {
+            description.push_str("You have no saved playlists. Use `/playlist save` to save one.");
+        }
+
+        let embed = EmbedBuilder::new()
+            .title("💾 Playlists")
+            .description(description)
+            .footer(EmbedFooterBuilder::new(format!(
+                "{}/{USER_PLAYLISTS_LIMIT} playlists",
+                playlists.len()
+            )))
+            .color(colour::EMBED_DEFAULT)
+            .build();
+        ctx.respond().embeds([embed]).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+
+use crate::{
+    command::model::{BotGuildSlashCommand, GuildSlashCmdCtx},
+    core::model::{
+        DatabaseAware, UserIdAware, response::initial::message::create::RespondWithMessage,
+    },
+    error::CommandResult,
+};
+
+/// Adds the tracks of one of your saved playlists to the queue.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "load")]
+pub struct Load {
+    /// Which playlist?
+    #[command(autocomplete = true)]
+    name: String,
+}
+
+impl BotGuildSlashCommand for Load {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let uris = sqlx::query_scalar!(
+            "SELECT t.uri FROM user_playlist_tracks t
+            JOIN user_playlists p ON p.id = t.playlist
+            WHERE p.owner = $1 AND p.name = $2
+            ORDER BY t.position;",
+            ctx.user_id().get().cast_signed(),
+            self.name,
+        )
+        .fetch_all(ctx.db())
+        .await?;
+
+        if uris.is_empty() {
+            ctx.wrng(format!(
+                "**You have no playlist named `{}`**; Use the command's autocomplete to pick a playlist instead.",
+                self.name
+            ))
+            .await?;
+            return Ok(());
+        }
+
+        super::super::play::play(
+            &mut ctx,
+            uris.into_iter().map(String::into_boxed_str),
+            super::super::play::InsertPosition::End,
+        )
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+mod delete;
+mod list;
+mod load;
+mod rename;
+mod save;
+
+use fuzzy_matcher::FuzzyMatcher;
+use itertools::Itertools;
+use lyra_proc::{BotGuildAutocompleteGroup, BotGuildCommandGroup};
+use twilight_interactions::command::{AutocompleteValue, CommandModel, CreateCommand};
+use twilight_model::application::command::{CommandOptionChoice, CommandOptionChoiceValue};
+
+use crate::{
+    command::model::{BotGuildAutocomplete, GuildAutocompleteCtx},
+    core::{
+        konst::discord::COMMAND_CHOICES_LIMIT,
+        model::{DatabaseAware, UserIdAware, response::initial::autocomplete::RespondAutocomplete},
+        statik::fuzzy_matcher::FUZZY_MATCHER,
+    },
+    error::command::AutocompleteResult,
+};
+
+#[derive(CommandModel, CreateCommand, BotGuildCommandGroup)]
+#[command(name = "playlist", desc = ".", contexts = "guild")]
+pub enum Playlist {
+    #[command(name = "save")]
+    Save(save::Save),
+    #[command(name = "load")]
+    Load(load::Load),
+    #[command(name = "list")]
+    List(list::List),
+    #[command(name = "rename")]
+    Rename(rename::Rename),
+    #[command(name = "delete")]
+    Delete(delete::Delete),
+}
+
+#[derive(CommandModel, BotGuildAutocompleteGroup)]
+#[command(autocomplete = true)]
+pub enum Autocomplete {
+    #[command(name = "save")]
+    Save(super::remove_range::Autocomplete),
+    #[command(name = "load")]
+    Load(NameAutocomplete),
+    #[command(name = "rename")]
+    Rename(NameAutocomplete),
+    #[command(name = "delete")]
+    Delete(NameAutocomplete),
+}
+
+#[derive(CommandModel)]
+#[command(autocomplete = true)]
+pub struct NameAutocomplete {
+    name: AutocompleteValue<String>,
+}
+
+impl BotGuildAutocomplete for NameAutocomplete {
+    async fn execute(self, mut ctx: GuildAutocompleteCtx) -> AutocompleteResult {
+        let AutocompleteValue::Focused(name) = self.name else {
+            panic!("not exactly one autocomplete option focused")
+        };
+
+        let names = sqlx::query_scalar!(
+            "SELECT name FROM user_playlists WHERE owner = $1 ORDER BY name;",
+            ctx.user_id().get().cast_signed(),
+        )
+        .fetch_all(ctx.db())
+        .await?;
+
+        let choices: Vec<_> = if name.is_empty() {
+            names.into_iter().take(COMMAND_CHOICES_LIMIT).collect()
+        } else {
+            names
+                .into_iter()
+                .filter_map(|n| Some((FUZZY_MATCHER.fuzzy_match(&n, &name)?, n)))
+                .sorted_by_key(|(s, _)| -s)
+                .take(COMMAND_CHOICES_LIMIT)
+                .map(|(_, n)| n)
+                .collect()
+        };
+        let choices: Vec<_> = choices
+            .into_iter()
+            .map(|n| CommandOptionChoice {
+                name: n.clone(),
+                name_localizations: None,
+                value: CommandOptionChoiceValue::String(n),
+            })
+            .collect();
+
+        ctx.autocomplete(choices).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+
+use crate::{
+    command::model::{BotGuildSlashCommand, GuildSlashCmdCtx},
+    core::model::{
+        DatabaseAware, UserIdAware, response::initial::message::create::RespondWithMessage,
+    },
+    error::CommandResult,
+};
+
+/// Renames one of your saved playlists.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "rename")]
+pub struct Rename {
+    /// Which playlist?
+    #[command(autocomplete = true)]
+    name: String,
+    /// What to rename the playlist to?
+    #[command(min_length = 1, max_length = 100)]
+    new_name: String,
+}
+
+impl BotGuildSlashCommand for Rename {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let owner = ctx.user_id().get().cast_signed();
+        let new_name = self.new_name.trim();
+
+        let taken = sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM user_playlists WHERE owner = $1 AND name = $2) as "exists!";"#,
+            owner,
+            new_name,
+        )
+        .fetch_one(ctx.db())
+        .await?;
+        if taken {
+            ctx.wrng(format!(
+                "**You already have a playlist named `{new_name}`**; Pick a different name instead."
+            ))
+            .await?;
+            return Ok(());
+        }
+
+        let renamed = sqlx::query_scalar!(
+            "UPDATE user_playlists SET name = $3 WHERE owner = $1 AND name = $2 RETURNING id;",
+            owner,
+            self.name,
+            new_name,
+        )
+        .fetch_optional(ctx.db())
+        .await?;
+
+        if renamed.is_none() {
+            ctx.wrng(format!(
+                "**You have no playlist named `{}`**; Use the command's autocomplete to pick a playlist instead.",
+                self.name
+            ))
+            .await?;
+            return Ok(());
+        }
+
+        ctx.out(format!(
+            "✏️ Renamed playlist `{}` to `{new_name}`.",
+            self.name
+        ))
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,180 @@
+use std::fmt::Write;
+
+use lyra_ext::num::{i64_as_usize, usize_as_i64};
+use sqlx::{Pool, Postgres};
+use twilight_interactions::command::{CommandModel, CreateCommand};
+
+use crate::{
+    command::{
+        model::{BotGuildSlashCommand, GuildSlashCmdCtx},
+        require,
+    },
+    core::{
+        konst::misc::{USER_PLAYLIST_TRACKS_LIMIT, USER_PLAYLISTS_LIMIT},
+        model::{
+            DatabaseAware, UserIdAware, response::initial::message::create::RespondWithMessage,
+        },
+    },
+    error::CommandResult,
+    lavalink::CorrectTrackInfo,
+};
+
+struct SavedTrack {
+    uri: String,
+    title: String,
+    length: i64,
+}
+
+/// Saves the queue, or a range of it, as a personal playlist.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "save")]
+pub struct Save {
+    /// What to name the playlist? (If a playlist with this name exists, it will be overwritten)
+    #[command(min_length = 1, max_length = 100)]
+    name: String,
+    /// Which starting track? [track title / position in queue] (If not given, the first track)
+    #[command(min_value = 1, autocomplete = true)]
+    start: Option<i64>,
+    /// Which ending track? [track title / position in queue] (If not given, the last track)
+    #[command(min_value = 1, autocomplete = true)]
+    end: Option<i64>,
+}
+
+impl BotGuildSlashCommand for Save {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let player = require::player(&ctx)?;
+        let data = player.data();
+        let data_r = data.read().await;
+        let queue = require::queue_not_empty(&data_r)?;
+
+        let queue_len = queue.len();
+        let start = self.start.unwrap_or(1);
+        let end = self.end.unwrap_or_else(|| usize_as_i64(queue_len));
+        super::super::validate_input_position(start, queue_len)?;
+        super::super::validate_input_position(end, queue_len)?;
+
+        if end < start {
+            drop(data_r);
+            ctx.wrng(format!(
+                "Invalid ending position: `{end}`; Ending position must be from `{start}` to `{queue_len}`."
+            ))
+            .await?;
+            return Ok(());
+        }
+
+        let range = i64_as_usize(start) - 1..i64_as_usize(end);
+        let tracks = queue
+            .iter()
+            .skip(range.start)
+            .take(range.len())
+            .filter_map(|t| {
+                let info = &t.data().info;
+                Some(SavedTrack {
+                    uri: info.uri.clone()?,
+                    title: info.corrected_title().to_owned(),
+                    length: info.length.cast_signed(),
+                })
+            })
+            .collect::<Vec<_>>();
+        drop(data_r);
+
+        let skipped = range.len() - tracks.len();
+        if tracks.is_empty() {
+            ctx.wrng("None of the tracks in the given range can be saved to a playlist.")
+                .await?;
+            return Ok(());
+        }
+        if tracks.len() > USER_PLAYLIST_TRACKS_LIMIT {
+            ctx.wrng(format!(
+                "**Too many tracks to save: `{}`**; Playlists can only have up to `{USER_PLAYLIST_TRACKS_LIMIT}` tracks.",
+                tracks.len(),
+            ))
+            .await?;
+            return Ok(());
+        }
+
+        let owner = ctx.user_id().get().cast_signed();
+        let name = self.name.trim();
+        let saved = tracks.len();
+        if !save_playlist(ctx.db(), owner, name, tracks).await? {
+            ctx.wrng(format!(
+                "**You already have `{USER_PLAYLISTS_LIMIT}` playlists**; Delete or overwrite one of them instead."
+            ))
+            .await?;
+            return Ok(());
+        }
+
+        let mut content = format!("💾 Saved `{saved} tracks` as playlist `{name}`.");
+        if skipped != 0 {
+            write!(
+                content,
+                "\n-# `{skipped} tracks` without links could not be saved."
+            )
+            .expect("writing to a string must not fail");
+        }
+        ctx.out(content).await?;
+        Ok(())
+    }
+}
+
+/// Saves `tracks` as the playlist `name` of `owner`, overwriting an existing playlist with the
+/// same name. Returns `false` if the playlist is new and `owner` has no room for another one.
+async fn save_playlist(
+    db: &Pool<Postgres>,
+    owner: i64,
+    name: &str,
+    tracks: Vec<SavedTrack>,
+) -> Result<bool, sqlx::Error> {
+    let mut tx = db.begin().await?;
+
+    let rec = sqlx::query!(
+        r#"SELECT
+            COUNT(*) as "count!",
+            COUNT(*) FILTER (WHERE name = $2) as "exists!"
+        FROM user_playlists WHERE owner = $1;"#,
+        owner,
+        name,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    if rec.exists == 0 && rec.count >= USER_PLAYLISTS_LIMIT {
+        return Ok(false);
+    }
+
+    let playlist = sqlx::query_scalar!(
+        "INSERT INTO user_playlists (owner, name) VALUES ($1, $2)
+        ON CONFLICT (owner, name) DO UPDATE SET name = EXCLUDED.name
+        RETURNING id;",
+        owner,
+        name,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "DELETE FROM user_playlist_tracks WHERE playlist = $1;",
+        playlist
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let positions = (0..).take(tracks.len()).collect::<Vec<i32>>();
+    let (uris, (titles, lengths)): (Vec<_>, (Vec<_>, Vec<_>)) = tracks
+        .into_iter()
+        .map(|t| (t.uri, (t.title, t.length)))
+        .unzip();
+    sqlx::query!(
+        "INSERT INTO user_playlist_tracks (playlist, position, uri, title, length)
+        SELECT $1, * FROM UNNEST($2::int[], $3::text[], $4::text[], $5::bigint[]);",
+        playlist,
+        &positions,
+        &uris,
+        &titles,
+        &lengths,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(true)
+}
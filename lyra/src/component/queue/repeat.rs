@@ -3,11 +3,12 @@ use twilight_interactions::command::{CommandModel, CommandOption, CreateCommand,
 use crate::{
     LavalinkAndGuildIdAware,
     command::{
-        check,
+        check::{self, ResolveWithPoll, StartPoll},
         model::{
             BotGuildSlashCommand, CtxKind, FollowupKind, GuildCtx, GuildSlashCmdCtx,
             RespondWithMessageKind,
         },
+        poll::Topic as PollTopic,
         require,
         util::controller_fmt,
     },
@@ -17,7 +18,7 @@ use crate::{
     },
     core::{
         http::InteractionClient,
-        model::response::{followup::Followup, initial::message::create::RespondWithMessage},
+        model::response::{either::RespondOrFollowup, followup::Followup},
     },
     error::{CommandResult, component::queue::repeat::RepeatError},
     lavalink::{Event, OwnedPlayerData, RepeatMode as LavalinkRepeatMode},
@@ -83,13 +84,15 @@ pub async fn repeat(
     via_controller: bool,
 ) -> Result<(), RepeatError> {
     if matches!(mode, LavalinkRepeatMode::Track) {
-        // TODO: #44
-        //
-        // check::user_in(in_voice)?.only()
-        //    .or_else_try_resolve_with(Topic::Repeat(mode))?
-        //    .and_then_start(&mut ctx)
-        //    .await?;
-        check::user_in(require::in_voice(ctx)?)?.only()?;
+        // FAIRNESS: if a member requests to repeat only the current track, they need to be
+        // the only person in voice, as repeating it will be unfair to everyone who queued
+        // after this current track: the tracks after the current track will be delayed
+        // indefinitely. Otherwise, it needs to be decided by a poll.
+        check::user_in(require::in_voice(ctx)?)?
+            .only()
+            .or_else_try_resolve_with(PollTopic::Repeat(mode))?
+            .and_then_start(ctx)
+            .await?;
     }
 
     ctx.get_conn().dispatch(Event::QueueRepeat);
@@ -100,8 +103,7 @@ pub async fn repeat(
 
     let message = format!("{} {}.", mode.emoji(), mode);
     let content = controller_fmt(ctx, via_controller, &message);
-    //out_or_upd!(content, ?ctx); TODO: #44
-    ctx.out(content).await?;
+    ctx.out_f(content).await?;
 
     if data.read().await.queue().current().is_none() {
         ctx.notef(format!(
@@ -0,0 +1,289 @@
+use std::{fmt::Write, time::Duration};
+
+use futures::StreamExt;
+use lavalink_rs::model::track::{PlaylistData, TrackData};
+use lyra_ext::pretty::{duration_display::DurationDisplay, truncate::PrettyTruncator};
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::{
+    application::interaction::{Interaction, InteractionData},
+    channel::message::{
+        Component, Embed, EmojiReactionType,
+        component::{ActionRow, SelectMenu, SelectMenuOption, SelectMenuType},
+    },
+};
+use twilight_util::builder::embed::{EmbedBuilder, EmbedFooterBuilder, ImageSource};
+use twilight_validate::embed::EmbedValidationError;
+
+use crate::{
+    LavalinkAware,
+    command::{
+        common::PlaySource,
+        model::{BotGuildSlashCommand, GuildSlashCmdCtx, NonPingInteraction},
+    },
+    core::{
+        konst::{
+            colour,
+            discord::SELECT_MENU_OPTIONS_LIMIT,
+            misc::{SEARCH_COLLECTIONS_LIMIT, SEARCH_PICKER_TIMEOUT, SEARCH_TRACKS_LIMIT},
+        },
+        model::{
+            BotStateAware, HttpAware, UserIdAware,
+            response::{
+                either::RespondOrFollowup,
+                initial::{defer::RespondWithDefer, message::update::RespondWithUpdate},
+            },
+        },
+    },
+    error::CommandResult,
+    gateway::GuildIdAware,
+    lavalink::{CorrectPlaylistInfo, CorrectTrackInfo, LoadSearch, PluginInfo, SearchResult},
+};
+
+use super::play::{self, InsertPosition};
+
+const SEARCH_PICKER_ID: &str = "search-picker";
+
+#[derive(Clone, Copy)]
+enum SearchResultKind {
+    Track,
+    Album,
+    Artist,
+    Playlist,
+}
+
+impl SearchResultKind {
+    const fn emoji(self) -> &'static str {
+        match self {
+            Self::Track => "🎵",
+            Self::Album => "💿",
+            Self::Artist => "👤",
+            Self::Playlist => "📚",
+        }
+    }
+}
+
+/// A search result that can be picked, loadable via its URI.
+struct SearchItem {
+    kind: SearchResultKind,
+    name: String,
+    author: Option<String>,
+    length: Option<Duration>,
+    total_tracks: Option<usize>,
+    uri: String,
+    artwork_url: Option<String>,
+}
+
+impl SearchItem {
+    fn from_track(track: TrackData) -> Option<Self> {
+        let info = track.info;
+        Some(Self {
+            kind: SearchResultKind::Track,
+            name: info.corrected_title().to_owned(),
+            author: Some(info.corrected_author().to_owned()),
+            length: (!info.is_stream).then(|| Duration::from_millis(info.length)),
+            total_tracks: None,
+            uri: info.uri?,
+            artwork_url: info.artwork_url,
+        })
+    }
+
+    fn from_playlist(kind: SearchResultKind, playlist: &PlaylistData) -> Option<Self> {
+        let plugin_info = playlist.parse_plugin_info()?;
+        Some(Self {
+            kind,
+            name: playlist.info.corrected_name().to_owned(),
+            author: plugin_info.author,
+            length: None,
+            total_tracks: plugin_info.total_tracks,
+            uri: plugin_info.url?,
+            artwork_url: plugin_info.artwork_url,
+        })
+    }
+
+    /// The length or the number of tracks, and the author of this result.
+    fn details(&self) -> String {
+        let mut details = String::new();
+        if let Some(length) = self.length {
+            write!(details, "⌛{} ", length.pretty_display())
+                .expect("writing to a string must not fail");
+        }
+        if let Some(total_tracks) = self.total_tracks {
+            write!(details, "📚{total_tracks} tracks ").expect("writing to a string must not fail");
+        }
+        if let Some(ref author) = self.author {
+            write!(details, "👤{}", author.pretty_truncate(30))
+                .expect("writing to a string must not fail");
+        }
+        details.trim_end().to_owned()
+    }
+}
+
+fn collect_items(result: SearchResult) -> Vec<SearchItem> {
+    let collections = |kind, playlists: Vec<PlaylistData>| {
+        playlists
+            .into_iter()
+            .filter_map(move |p| SearchItem::from_playlist(kind, &p))
+            .take(SEARCH_COLLECTIONS_LIMIT)
+    };
+
+    result
+        .tracks
+        .into_iter()
+        .filter_map(SearchItem::from_track)
+        .take(SEARCH_TRACKS_LIMIT)
+        .chain(collections(SearchResultKind::Album, result.albums))
+        .chain(collections(SearchResultKind::Artist, result.artists))
+        .chain(collections(SearchResultKind::Playlist, result.playlists))
+        .take(SELECT_MENU_OPTIONS_LIMIT)
+        .collect()
+}
+
+fn generate_embed(query: &str, items: &[SearchItem]) -> Result<Embed, EmbedValidationError> {
+    let mut description = String::new();
+    for (number, item) in (1..).zip(items) {
+        writeln!(
+            description,
+            "`#{number}` {} [{}]({}) `{}`",
+            item.kind.emoji(),
+            item.name.pretty_truncate(60),
+            item.uri,
+            item.details(),
+        )
+        .expect("writing to a string must not fail");
+    }
+
+    let mut embed = EmbedBuilder::new()
+        .title(format!("🔎 {}", query.pretty_truncate(200)))
+        .description(description)
+        .footer(EmbedFooterBuilder::new(
+            "Pick the results to add to the queue below • 🎵 Track • 💿 Album • 👤 Artist • 📚 Playlist",
+        ))
+        .color(colour::EMBED_DEFAULT);
+    if let Some(thumbnail) = items
+        .iter()
+        .find_map(|i| i.artwork_url.as_deref())
+        .and_then(|url| ImageSource::url(url).ok())
+    {
+        embed = embed.thumbnail(thumbnail);
+    }
+    Ok(embed.validate()?.build())
+}
+
+fn build_picker(items: &[SearchItem]) -> Component {
+    let options = items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| SelectMenuOption {
+            default: false,
+            description: Some(item.details()).filter(|d| !d.is_empty()),
+            emoji: Some(EmojiReactionType::Unicode {
+                name: item.kind.emoji().to_owned(),
+            }),
+            label: item.name.pretty_truncate(100).into_owned(),
+            value: i.to_string(),
+        })
+        .collect::<Vec<_>>();
+
+    Component::ActionRow(ActionRow {
+        components: vec![Component::SelectMenu(SelectMenu {
+            channel_types: None,
+            custom_id: String::from(SEARCH_PICKER_ID),
+            default_values: None,
+            disabled: false,
+            kind: SelectMenuType::Text,
+            max_values: Some(u8::try_from(options.len()).unwrap_or(u8::MAX)),
+            min_values: Some(1),
+            options: Some(options),
+            placeholder: Some(String::from("Pick the results to add to the queue")),
+        })],
+    })
+}
+
+/// The URIs of the items picked in the search picker, in the order that they were listed.
+fn picked_uris(inter: &Interaction, items: Vec<SearchItem>) -> Vec<Box<str>> {
+    let Some(InteractionData::MessageComponent(ref component)) = inter.data else {
+        return Vec::new();
+    };
+    let mut picked = component
+        .values
+        .iter()
+        .filter_map(|v| v.parse::<usize>().ok())
+        .collect::<Vec<_>>();
+    picked.sort_unstable();
+
+    items
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| picked.binary_search(i).is_ok())
+        .map(|(_, item)| item.uri.into_boxed_str())
+        .collect()
+}
+
+/// Searches for tracks, albums, artists and playlists, then picks which of them to add to the queue.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "search", contexts = "guild")]
+pub struct Search {
+    /// Search for what?
+    #[command(min_length = 1)]
+    query: String,
+    /// Search from where? (if not given, Youtube)
+    source: Option<PlaySource>,
+}
+
+impl BotGuildSlashCommand for Search {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        ctx.defer().await?;
+        let source = self.source.unwrap_or_default();
+        let result = ctx
+            .lavalink()
+            .clone_inner()
+            .load_search(
+                ctx.guild_id(),
+                &format!("{}:{}", source.value(), self.query),
+            )
+            .await?;
+
+        let items = collect_items(result);
+        if items.is_empty() {
+            ctx.wrng_f(format!(
+                "**No results found for `{}`**; Try using a different search query or source.",
+                self.query
+            ))
+            .await?;
+            return Ok(());
+        }
+
+        let message = ctx
+            .respond_or_followup()
+            .embeds([generate_embed(&self.query, &items)?])
+            .components([build_picker(&items)])
+            .await?
+            .retrieve_message()
+            .await?;
+
+        let user_id = ctx.user_id();
+        let mut components = ctx
+            .bot()
+            .standby()
+            .wait_for_component_stream(message.id, move |e: &Interaction| {
+                e.author_id_expected() == user_id
+            });
+        let Ok(Some(inter)) = tokio::time::timeout(SEARCH_PICKER_TIMEOUT, components.next()).await
+        else {
+            ctx.http()
+                .update_message(message.channel_id, message.id)
+                .components(Some(&[]))
+                .await?;
+            return Ok(());
+        };
+
+        let uris = picked_uris(&inter, items);
+        let mut i = ctx.bot().interaction().ctx(&inter);
+        i.update().components([]).await?;
+        if uris.is_empty() {
+            return Ok(());
+        }
+
+        Ok(play::load_and_play(&mut ctx, uris, InsertPosition::End).await?)
+    }
+}
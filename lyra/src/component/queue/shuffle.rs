@@ -1,15 +1,19 @@
 use twilight_interactions::command::{CommandModel, CreateCommand};
 
 use crate::{
+    LavalinkAndGuildIdAware,
     command::{
-        check,
-        model::{BotGuildSlashCommand, GuildCtx, GuildSlashCmdCtx, RespondWithMessageKind},
+        check::{self, ResolveWithPoll, StartPoll},
+        model::{
+            BotGuildSlashCommand, FollowupKind, GuildCtx, GuildSlashCmdCtx, RespondWithMessageKind,
+        },
+        poll::Topic as PollTopic,
         require,
         util::controller_fmt,
     },
-    core::model::response::initial::message::create::RespondWithMessage,
+    core::model::response::either::RespondOrFollowup,
     error::{CommandResult, component::queue::shuffle::ShuffleError},
-    lavalink::{IndexerType, OwnedPlayerData},
+    lavalink::{Event, IndexerType, OwnedPlayerData},
 };
 
 /// Toggles queue shuffling.
@@ -29,44 +33,36 @@ impl BotGuildSlashCommand for Shuffle {
 
 pub async fn shuffle(
     data: OwnedPlayerData,
-    ctx: &mut GuildCtx<impl RespondWithMessageKind>,
+    ctx: &mut GuildCtx<impl RespondWithMessageKind + FollowupKind>,
     via_controller: bool,
 ) -> Result<(), ShuffleError> {
+    let shuffled = !data.read().await.queue().indexer_type().shuffled();
+
     // FAIRNESS: if a member requests to enable or disable shuffle, they need to be
     // the only person in voice, as modifying the queue indexing order will be
     // unfair to everyone who queued after this current track: the tracks after the
-    // current track will be delayed for an unspecified amount of time.
-    check::user_in(require::in_voice(ctx)?)?.only()?;
-
-    let indexer_type = data.read().await.queue().indexer_type();
-    match indexer_type {
-        IndexerType::Shuffled => {
-            data.write()
-                .await
-                .set_indexer_then_update_and_apply_to_now_playing(IndexerType::Standard)
-                .await?;
+    // current track will be delayed for an unspecified amount of time. Otherwise,
+    // it needs to be decided by a poll.
+    check::user_in(require::in_voice(ctx)?)?
+        .only()
+        .or_else_try_resolve_with(PollTopic::Shuffle(shuffled))?
+        .and_then_start(ctx)
+        .await?;
 
-            let content = controller_fmt(ctx, via_controller, "**` ⮆ `** Disabled shuffle.");
-            ctx.out(content).await?;
-            Ok(())
-        }
-        IndexerType::Fair => {
-            ctx.wrng(
-                // The shuffle button on the playback controller will be disabled, so no need to use `controller_fmt` here
-                "Cannot enable shuffle as fair queue is currently enabled.",
-            )
-            .await?;
-            Ok(())
-        }
-        IndexerType::Standard => {
-            data.write()
-                .await
-                .set_indexer_then_update_and_apply_to_now_playing(IndexerType::Shuffled)
-                .await?;
+    ctx.get_conn().dispatch(Event::QueueReindex);
+    let mut data_w = data.write().await;
+    let fairness = data_w.queue().indexer_type().fairness();
+    data_w
+        .set_indexer_then_update_and_apply_to_now_playing(IndexerType::new(fairness, shuffled))
+        .await?;
+    drop(data_w);
 
-            let content = controller_fmt(ctx, via_controller, "🔀 Enabled shuffle.");
-            ctx.out(content).await?;
-            Ok(())
-        }
-    }
+    let content = if shuffled {
+        "🔀 Enabled shuffle."
+    } else {
+        "**` ⮆ `** Disabled shuffle."
+    };
+    let content = controller_fmt(ctx, via_controller, content);
+    ctx.out_f(content).await?;
+    Ok(())
 }
@@ -0,0 +1,320 @@
+use std::{fmt::Write, num::NonZeroUsize, time::Duration};
+
+use futures::StreamExt;
+use lyra_ext::pretty::{duration_display::DurationDisplay, truncate::PrettyTruncator};
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_mention::Mention;
+use twilight_model::{
+    application::interaction::{Interaction, InteractionData},
+    channel::message::{
+        Component, Embed, EmojiReactionType,
+        component::{ActionRow, Button, ButtonStyle},
+    },
+};
+use twilight_util::builder::embed::{EmbedBuilder, EmbedFooterBuilder};
+use twilight_validate::embed::EmbedValidationError;
+
+use crate::{
+    command::{
+        model::{BotGuildSlashCommand, GuildSlashCmdCtx},
+        require,
+    },
+    core::{
+        konst::{
+            colour,
+            misc::{QUEUE_VIEW_PAGE_SIZE, QUEUE_VIEW_TIMEOUT},
+        },
+        model::{
+            BotStateAware, HttpAware,
+            response::initial::message::{create::RespondWithMessage, update::RespondWithUpdate},
+        },
+    },
+    error::CommandResult,
+    lavalink::{CorrectTrackInfo, Fairness, Queue, QueueItem, RepeatMode},
+};
+
+#[derive(Clone, Copy)]
+pub(super) enum PageButton {
+    First,
+    Previous,
+    Current,
+    Next,
+    Last,
+}
+
+impl PageButton {
+    const fn custom_id(self) -> &'static str {
+        match self {
+            Self::First => "first",
+            Self::Previous => "previous",
+            Self::Current => "current",
+            Self::Next => "next",
+            Self::Last => "last",
+        }
+    }
+
+    const fn emoji(self) -> &'static str {
+        match self {
+            Self::First => "⏮️",
+            Self::Previous => "◀️",
+            Self::Current => "🎵",
+            Self::Next => "▶️",
+            Self::Last => "⏭️",
+        }
+    }
+
+    pub(super) fn from_interaction(interaction: &Interaction) -> Option<Self> {
+        let Some(InteractionData::MessageComponent(ref component)) = interaction.data else {
+            return None;
+        };
+        [
+            Self::First,
+            Self::Previous,
+            Self::Current,
+            Self::Next,
+            Self::Last,
+        ]
+        .into_iter()
+        .find(|b| b.custom_id() == component.custom_id)
+    }
+
+    pub(super) fn build(self, disabled: bool) -> Component {
+        let style = match self {
+            Self::Current => ButtonStyle::Primary,
+            _ => ButtonStyle::Secondary,
+        };
+        Component::Button(Button {
+            custom_id: Some(self.custom_id().to_owned()),
+            disabled,
+            emoji: Some(EmojiReactionType::Unicode {
+                name: self.emoji().to_owned(),
+            }),
+            label: None,
+            style,
+            url: None,
+            sku_id: None,
+        })
+    }
+}
+
+/// A snapshot of which page of the queue is being viewed.
+struct Page {
+    index: usize,
+    count: usize,
+    current: Option<usize>,
+}
+
+impl Page {
+    fn new(queue: &Queue, index: usize) -> Self {
+        let count = queue.len().div_ceil(QUEUE_VIEW_PAGE_SIZE).max(1);
+        let current = queue.current_index().map(|i| i / QUEUE_VIEW_PAGE_SIZE);
+        Self {
+            index: index.min(count - 1),
+            count,
+            current,
+        }
+    }
+
+    fn new_at_current(queue: &Queue) -> Self {
+        let index = queue.index().min(queue.len().saturating_sub(1)) / QUEUE_VIEW_PAGE_SIZE;
+        Self::new(queue, index)
+    }
+
+    fn new_with(queue: &Queue, index: usize, button: PageButton) -> Self {
+        let page = Self::new(queue, index);
+        let index = match button {
+            PageButton::First => 0,
+            PageButton::Previous => page.index.saturating_sub(1),
+            PageButton::Current => return Self::new_at_current(queue),
+            PageButton::Next => page.index + 1,
+            PageButton::Last => page.count - 1,
+        };
+        Self::new(queue, index)
+    }
+
+    const fn is_first(&self) -> bool {
+        self.index == 0
+    }
+
+    const fn is_last(&self) -> bool {
+        self.index + 1 == self.count
+    }
+
+    fn is_current(&self) -> bool {
+        self.current.is_none_or(|c| c == self.index)
+    }
+
+    fn build_row(&self) -> Component {
+        Component::ActionRow(ActionRow {
+            components: vec![
+                PageButton::First.build(self.is_first()),
+                PageButton::Previous.build(self.is_first()),
+                PageButton::Current.build(self.is_current()),
+                PageButton::Next.build(self.is_last()),
+                PageButton::Last.build(self.is_last()),
+            ],
+        })
+    }
+}
+
+fn write_item(
+    description: &mut String,
+    position: NonZeroUsize,
+    item: &QueueItem,
+    is_current: bool,
+) -> std::fmt::Result {
+    let info = &item.data().info;
+    let title = info.corrected_title().pretty_truncate(60);
+    let length = Duration::from_millis(info.length).pretty_display();
+    let requester = item.requester().mention();
+
+    if is_current {
+        write!(description, "**`#{position}`** 🎵 **")?;
+    } else {
+        write!(description, "`#{position}` ")?;
+    }
+    match info.uri {
+        Some(ref uri) => write!(description, "[{title}]({uri})")?,
+        None => write!(description, "{title}")?,
+    }
+    if is_current {
+        description.write_str("**")?;
+    }
+    write!(description, " `⌛{length}` 🎤{requester}")?;
+    if let Some(playlist) = item.playlist_data() {
+        let name = playlist.corrected_name().pretty_truncate(30);
+        write!(description, " 📚 {name}")?;
+    }
+    description.write_char('\n')
+}
+
+fn generate_embed(queue: &Queue, page: &Page) -> Result<Embed, EmbedValidationError> {
+    let start = page.index * QUEUE_VIEW_PAGE_SIZE;
+    let end = (start + QUEUE_VIEW_PAGE_SIZE).min(queue.len());
+    let current = queue.current();
+
+    // the tracks are listed in the order that they will be played, but numbered by their positions
+    // in the queue, so that the numbers are the same as those used by other queue commands.
+    let mut description = String::new();
+    for (position, item) in queue.iter_mapped_positions_and_items(start..end) {
+        let is_current = current.is_some_and(|c| std::ptr::eq(c, item));
+        write_item(&mut description, position, item, is_current)
+            .expect("writing to a string must not fail");
+    }
+
+    let total_length = Duration::from_millis(queue.iter().map(|t| t.data().info.length).sum());
+    let mut footer = format!(
+        "Page {}/{} • {} tracks • ⏳ {}",
+        page.index + 1,
+        page.count,
+        queue.len(),
+        total_length.pretty_display(),
+    );
+    let indexer_type = queue.indexer_type();
+    match indexer_type.fairness() {
+        None => {}
+        Some(Fairness::TrackCount) => footer.push_str(" • ⚖️ Fair queue"),
+        Some(Fairness::PlayTime) => footer.push_str(" • ⚖️ Fair queue (by play time)"),
+    }
+    if indexer_type.shuffled() {
+        footer.push_str(" • 🔀 Shuffled");
+    }
+    match queue.repeat_mode() {
+        RepeatMode::Off => {}
+        RepeatMode::All => footer.push_str(" • 🔁 Repeating all"),
+        RepeatMode::Track => footer.push_str(" • 🔂 Repeating one"),
+    }
+
+    let embed = EmbedBuilder::new()
+        .title("📜 Queue")
+        .description(description)
+        .footer(EmbedFooterBuilder::new(footer))
+        .color(colour::EMBED_DEFAULT)
+        .validate()?
+        .build();
+    Ok(embed)
+}
+
+fn generate_empty_embed() -> Embed {
+    EmbedBuilder::new()
+        .title("📜 Queue")
+        .description("The queue is empty.")
+        .color(colour::EMBED_DEFAULT)
+        .build()
+}
+
+/// Views the queue.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "view")]
+pub struct View;
+
+impl BotGuildSlashCommand for View {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let player = require::player(&ctx)?;
+        let data = player.data();
+        let data_r = data.read().await;
+        let queue = require::queue_not_empty(&data_r)?;
+
+        let mut page = Page::new_at_current(queue);
+        let embed = generate_embed(queue, &page)?;
+        drop(data_r);
+
+        let message = ctx
+            .respond()
+            .embeds([embed])
+            .components([page.build_row()])
+            .await?
+            .retrieve_message()
+            .await?;
+
+        let mut components = ctx
+            .bot()
+            .standby()
+            .wait_for_component_stream(message.id, |_: &_| true);
+
+        // the player data is re-read on every button press,
+        // so that the view stays up-to-date with the queue being modified in the meantime.
+        while let Ok(Some(inter)) =
+            tokio::time::timeout(QUEUE_VIEW_TIMEOUT, components.next()).await
+        {
+            let Some(button) = PageButton::from_interaction(&inter) else {
+                continue;
+            };
+            let mut i = ctx.bot().interaction().ctx(&inter);
+
+            let Ok(player) = require::player(&ctx) else {
+                i.update()
+                    .embeds([generate_empty_embed()])
+                    .components([])
+                    .await?;
+                return Ok(());
+            };
+            let data = player.data();
+            let data_r = data.read().await;
+            let queue = data_r.queue();
+            if queue.is_empty() {
+                drop(data_r);
+                i.update()
+                    .embeds([generate_empty_embed()])
+                    .components([])
+                    .await?;
+                return Ok(());
+            }
+
+            page = Page::new_with(queue, page.index, button);
+            let embed = generate_embed(queue, &page)?;
+            drop(data_r);
+
+            i.update()
+                .embeds([embed])
+                .components([page.build_row()])
+                .await?;
+        }
+
+        ctx.http()
+            .update_message(message.channel_id, message.id)
+            .components(Some(&[]))
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,463 @@
+use std::fmt::Write;
+
+use itertools::Itertools;
+use lavalink_rs::model::player::{
+    Equalizer, Filters, Karaoke, Rotation, Timescale, TremoloVibrato,
+};
+use lyra_ext::num::usize_as_u8;
+use lyra_proc::BotGuildCommandGroup;
+use twilight_interactions::command::{CommandModel, CommandOption, CreateCommand, CreateOption};
+use twilight_model::guild::Permissions;
+
+use crate::{
+    command::{
+        check::DJ_PERMISSIONS,
+        model::{BotGuildSlashCommand, GuildSlashCmdCtx},
+    },
+    component::tuning::{ApplyFilter, UpdateFilter, require_in_voice_unsuppressed_and_player},
+    core::model::response::initial::message::create::RespondWithMessage,
+    error::CommandResult,
+};
+
+/// Calls `$f!(field, "Name")` for each filter in [`Filters`] that effect profiles can set.
+macro_rules! for_each_filter {
+    ($f:ident) => {
+        $f!(equalizer, "Equaliser");
+        $f!(karaoke, "Karaoke");
+        $f!(timescale, "Speed & Pitch");
+        $f!(tremolo, "Tremolo");
+        $f!(vibrato, "Vibrato");
+        $f!(rotation, "Rotation");
+        $f!(distortion, "Distortion");
+        $f!(channel_mix, "Channel Mix");
+        $f!(low_pass, "Low Pass");
+    };
+}
+
+/// Replaces the filters in `filters` that are set in `mask` with those of `from`.
+fn overlay(mut filters: Filters, mask: &Filters, from: Filters) -> Filters {
+    macro_rules! overlay {
+        ($field:ident, $name:literal) => {
+            if mask.$field.is_some() {
+                filters.$field = from.$field;
+            }
+        };
+    }
+    for_each_filter!(overlay);
+    filters
+}
+
+fn is_active<T: Default + PartialEq>(filter: Option<&T>) -> bool {
+    filter.is_some_and(|f| *f != T::default())
+}
+
+/// The names of the filters active in `current` that `profile` would override.
+fn overridden_filter_names(current: &Filters, profile: &Filters) -> Vec<&'static str> {
+    let mut names = Vec::new();
+    macro_rules! push_overridden {
+        ($field:ident, $name:literal) => {
+            if is_active(current.$field.as_ref()) && profile.$field.is_some() {
+                names.push($name);
+            }
+        };
+    }
+    for_each_filter!(push_overridden);
+    names
+}
+
+/// The playback speed multiplier that the timescale in `filters` results in.
+fn speed_multiplier(filters: &Filters) -> f64 {
+    filters.timescale.as_ref().map_or(1., |timescale| {
+        timescale.speed.unwrap_or(1.) * timescale.rate.unwrap_or(1.)
+    })
+}
+
+fn equaliser(gains: &[f64]) -> Vec<Equalizer> {
+    gains
+        .iter()
+        .enumerate()
+        .map(|(band, &gain)| Equalizer {
+            band: usize_as_u8(band),
+            gain,
+        })
+        .collect()
+}
+
+#[derive(CommandOption, CreateOption, Clone, Copy)]
+pub enum Profile {
+    #[option(name = "Nightcore", value = 0)]
+    Nightcore,
+    #[option(name = "Vaporwave", value = 1)]
+    Vaporwave,
+    #[option(name = "8D", value = 2)]
+    EightD,
+    #[option(name = "Bass Boost", value = 3)]
+    BassBoost,
+    #[option(name = "Karaoke", value = 4)]
+    Karaoke,
+}
+
+impl Profile {
+    const fn into_i16(self) -> i16 {
+        match self {
+            Self::Nightcore => 0,
+            Self::Vaporwave => 1,
+            Self::EightD => 2,
+            Self::BassBoost => 3,
+            Self::Karaoke => 4,
+        }
+    }
+
+    const fn from_i16(value: i16) -> Option<Self> {
+        match value {
+            0 => Some(Self::Nightcore),
+            1 => Some(Self::Vaporwave),
+            2 => Some(Self::EightD),
+            3 => Some(Self::BassBoost),
+            4 => Some(Self::Karaoke),
+            _ => None,
+        }
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Nightcore => "Nightcore",
+            Self::Vaporwave => "Vaporwave",
+            Self::EightD => "8D",
+            Self::BassBoost => "Bass Boost",
+            Self::Karaoke => "Karaoke",
+        }
+    }
+
+    /// The filters this profile sets, leaving the rest unset.
+    fn filters(self) -> Filters {
+        match self {
+            Self::Nightcore => Filters {
+                timescale: Some(Timescale {
+                    rate: Some(1.25),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            Self::Vaporwave => Filters {
+                timescale: Some(Timescale {
+                    speed: Some(0.85),
+                    pitch: Some(0.8),
+                    ..Default::default()
+                }),
+                equalizer: Some(equaliser(&[0.3, 0.3])),
+                tremolo: Some(TremoloVibrato {
+                    frequency: Some(14.),
+                    depth: Some(0.3),
+                }),
+                ..Default::default()
+            },
+            Self::EightD => Filters {
+                rotation: Some(Rotation {
+                    rotation_hz: Some(0.2),
+                }),
+                ..Default::default()
+            },
+            Self::BassBoost => Filters {
+                equalizer: Some(equaliser(&[0.6, 0.5, 0.4, 0.25, 0.1])),
+                ..Default::default()
+            },
+            Self::Karaoke => Filters {
+                karaoke: Some(Karaoke {
+                    level: Some(1.),
+                    mono_level: Some(1.),
+                    filter_band: Some(220.),
+                    filter_width: Some(100.),
+                }),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl ApplyFilter for Profile {
+    fn apply_to(self, filter: Filters) -> Filters {
+        let filters = self.filters();
+        overlay(filter, &filters.clone(), filters)
+    }
+}
+
+/// An effect profile applied over the filters that were applied before it.
+#[derive(Clone)]
+pub struct ActiveEffect {
+    profile: Profile,
+    previous: Filters,
+}
+
+impl ActiveEffect {
+    /// Recreates an effect from the parts persisted in a player snapshot, returning [`None`] if
+    /// `profile` is unknown.
+    pub fn from_parts(profile: i16, previous: Filters) -> Option<Self> {
+        Profile::from_i16(profile).map(|profile| Self { profile, previous })
+    }
+
+    pub const fn profile_i16(&self) -> i16 {
+        self.profile.into_i16()
+    }
+
+    pub const fn previous(&self) -> &Filters {
+        &self.previous
+    }
+}
+
+impl ApplyFilter for ActiveEffect {
+    /// Restores the filters the profile set to what they were before the profile was applied,
+    /// keeping the other filters as they are.
+    fn apply_to(self, filter: Filters) -> Filters {
+        overlay(filter, &self.profile.filters(), self.previous)
+    }
+}
+
+/// Enables `to`, after disabling the effect `from` if there is one.
+struct SwitchEffect {
+    from: Option<ActiveEffect>,
+    to: Profile,
+}
+
+impl ApplyFilter for SwitchEffect {
+    fn apply_to(self, filter: Filters) -> Filters {
+        let filter = match self.from {
+            Some(from) => from.apply_to(filter),
+            None => filter,
+        };
+        self.to.apply_to(filter)
+    }
+}
+
+#[derive(CommandModel, CreateCommand, BotGuildCommandGroup)]
+#[command(
+    name = "effect",
+    desc = ".",
+    contexts = "guild",
+    default_permissions = "Self::default_permissions"
+)]
+pub enum Effect {
+    #[command(name = "on")]
+    On(On),
+    #[command(name = "off")]
+    Off(Off),
+}
+
+impl Effect {
+    const fn default_permissions() -> Permissions {
+        DJ_PERMISSIONS
+    }
+}
+
+/// Enables an effect profile, applying multiple filters at once.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "on")]
+pub struct On {
+    /// Which effect?
+    profile: Profile,
+}
+
+impl BotGuildSlashCommand for On {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let (_, player) = require_in_voice_unsuppressed_and_player(&ctx)?;
+        let profile = self.profile;
+
+        let current = player.info().await?.filters.unwrap_or_default();
+        let data = player.data();
+        let mut data_w = data.write().await;
+        // switching from another effect is relative to the filters from before that effect
+        let from = data_w.effect().cloned();
+        let previous = match from.clone() {
+            Some(active) => active.apply_to(current),
+            None => current,
+        };
+        let overridden = overridden_filter_names(&previous, &profile.filters());
+
+        let filters = player
+            .update_filter(SwitchEffect { from, to: profile })
+            .await?;
+        data_w.set_effect(ActiveEffect { profile, previous });
+        let speed = speed_multiplier(&filters);
+        data_w.set_speed(speed);
+        data_w.update_and_apply_now_playing_speed(speed).await?;
+        drop(data_w);
+
+        let mut content = format!("✨🟢 Enabled effect **`{}`**.", profile.name());
+        if !overridden.is_empty() {
+            let overridden = overridden.iter().map(|n| format!("`{n}`")).join(", ");
+            write!(content, "\n-# Overrode: {overridden}.")
+                .expect("writing to a string must not fail");
+        }
+        ctx.out(content).await?;
+        Ok(())
+    }
+}
+
+/// Disables the current effect profile, restoring the filters from before it.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "off")]
+pub struct Off;
+
+impl BotGuildSlashCommand for Off {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let (_, player) = require_in_voice_unsuppressed_and_player(&ctx)?;
+
+        let data = player.data();
+        let mut data_w = data.write().await;
+        let Some(active) = data_w.effect().cloned() else {
+            drop(data_w);
+            ctx.wrng("**No effect is enabled**; Enable one via `/effect on` first.")
+                .await?;
+            return Ok(());
+        };
+        let name = active.profile.name();
+
+        let filters = player.update_filter(active).await?;
+        data_w.take_effect();
+        let speed = speed_multiplier(&filters);
+        data_w.set_speed(speed);
+        data_w.update_and_apply_now_playing_speed(speed).await?;
+        drop(data_w);
+        ctx.out(format!(
+            "✨🔴 Disabled effect **`{name}`** and restored the previous filters."
+        ))
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lavalink_rs::model::player::{
+        Filters, Karaoke, LowPass, Rotation, Timescale, TremoloVibrato,
+    };
+
+    use super::{ActiveEffect, Profile, SwitchEffect, equaliser, overlay, overridden_filter_names};
+    use crate::component::tuning::ApplyFilter;
+
+    const PROFILES: [Profile; 5] = [
+        Profile::Nightcore,
+        Profile::Vaporwave,
+        Profile::EightD,
+        Profile::BassBoost,
+        Profile::Karaoke,
+    ];
+
+    fn current() -> Filters {
+        Filters {
+            volume: Some(0.8),
+            equalizer: Some(equaliser(&[0.1; 15])),
+            karaoke: Some(Karaoke {
+                level: Some(0.5),
+                ..Default::default()
+            }),
+            timescale: Some(Timescale {
+                speed: Some(1.1),
+                pitch: Some(1.2),
+                ..Default::default()
+            }),
+            tremolo: Some(TremoloVibrato {
+                frequency: Some(2.),
+                depth: Some(0.5),
+            }),
+            rotation: Some(Rotation {
+                rotation_hz: Some(1.),
+            }),
+            low_pass: Some(LowPass {
+                smoothing: Some(20.),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn overlay_only_replaces_masked_filters() {
+        let mask = Filters {
+            rotation: Some(Rotation::default()),
+            ..Default::default()
+        };
+        let filters = overlay(current(), &mask, Filters::default());
+
+        assert_eq!(
+            filters,
+            Filters {
+                rotation: None,
+                ..current()
+            }
+        );
+    }
+
+    #[test]
+    fn disabling_effect_restores_previous_filters() {
+        for previous in [current(), Filters::default()] {
+            for profile in PROFILES {
+                let applied = profile.apply_to(previous.clone());
+                assert_eq!(applied.volume, previous.volume);
+                assert_eq!(applied.low_pass, previous.low_pass);
+
+                let effect = ActiveEffect {
+                    profile,
+                    previous: previous.clone(),
+                };
+                assert_eq!(effect.apply_to(applied), previous);
+            }
+        }
+    }
+
+    #[test]
+    fn switching_effects_restores_filters_from_before_the_first() {
+        let original = current();
+        let applied = Profile::Vaporwave.apply_to(original.clone());
+        let from = ActiveEffect {
+            profile: Profile::Vaporwave,
+            previous: original.clone(),
+        };
+
+        let previous = from.clone().apply_to(applied.clone());
+        assert_eq!(previous, original);
+
+        let switched = SwitchEffect {
+            from: Some(from),
+            to: Profile::BassBoost,
+        }
+        .apply_to(applied);
+        assert_eq!(switched.timescale, original.timescale);
+        assert_eq!(switched.tremolo, original.tremolo);
+
+        let effect = ActiveEffect {
+            profile: Profile::BassBoost,
+            previous,
+        };
+        assert_eq!(effect.apply_to(switched), original);
+    }
+
+    #[test]
+    fn overridden_filter_names_per_profile() {
+        let expected: [&[&str]; 5] = [
+            &["Speed & Pitch"],
+            &["Equaliser", "Speed & Pitch", "Tremolo"],
+            &["Rotation"],
+            &["Equaliser"],
+            &["Karaoke"],
+        ];
+        for (profile, expected) in PROFILES.into_iter().zip(expected) {
+            assert_eq!(
+                overridden_filter_names(&current(), &profile.filters()),
+                expected
+            );
+            assert!(overridden_filter_names(&Filters::default(), &profile.filters()).is_empty());
+        }
+    }
+
+    #[test]
+    fn overridden_filter_names_skip_default_filters() {
+        let current = Filters {
+            timescale: Some(Timescale::default()),
+            equalizer: Some(Vec::new()),
+            ..Default::default()
+        };
+        for profile in PROFILES {
+            assert!(overridden_filter_names(&current, &profile.filters()).is_empty());
+        }
+    }
+}
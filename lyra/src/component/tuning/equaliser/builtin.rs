@@ -0,0 +1,295 @@
+use std::{
+    collections::HashSet,
+    env, fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
+
+use tokio::sync::RwLock;
+
+use crate::error::component::tuning::LoadEqualiserPresetsError;
+
+use super::{MAX_GAIN, MIN_GAIN, PresetEntry, PresetsFile};
+
+/// The presets compiled into the bot, which preset files can add to or override.
+const DEFAULT_PRESETS: &str = include_str!("../../../../../preset/equalisers.toml");
+
+/// The path, last modified time and size of each preset file, for telling whether any of them
+/// changed since they were last loaded.
+type FilesStamp = Vec<(PathBuf, Option<SystemTime>, u64)>;
+
+struct Loaded {
+    presets: Arc<[PresetEntry]>,
+    stamp: FilesStamp,
+}
+
+/// The equaliser presets available in every guild: the compiled-in defaults merged with the
+/// `.toml` preset files in `PRESETS_DIR` if set, which are reloaded whenever they change.
+///
+/// Invalid preset files are skipped with their errors logged, so that they never prevent the
+/// rest of the presets from loading. Only equaliser presets can be loaded from preset files, as
+/// the effect profiles are built in.
+pub struct EqualiserPresets {
+    dir: Option<PathBuf>,
+    loaded: RwLock<Loaded>,
+}
+
+fn parse(content: &str) -> Result<Vec<PresetEntry>, LoadEqualiserPresetsError> {
+    let mut presets = toml::from_str::<PresetsFile>(content)?.equaliser;
+    let mut names = HashSet::new();
+    for preset in &mut presets {
+        preset.name = preset.name.trim().to_owned();
+        if !(1..=100).contains(&preset.name.chars().count()) {
+            return Err(LoadEqualiserPresetsError::InvalidName(preset.name.clone()));
+        }
+        // names are matched case-insensitively, the same as when merging presets
+        if !names.insert(preset.name.to_ascii_lowercase()) {
+            return Err(LoadEqualiserPresetsError::DuplicateName(
+                preset.name.clone(),
+            ));
+        }
+        if let Some((band, &gain)) = preset
+            .gains
+            .iter()
+            .enumerate()
+            .find(|(_, g)| !(MIN_GAIN..=MAX_GAIN).contains(*g))
+        {
+            return Err(LoadEqualiserPresetsError::GainOutOfRange {
+                name: preset.name.clone(),
+                band: band + 1,
+                gain,
+            });
+        }
+    }
+    Ok(presets)
+}
+
+fn read_and_parse(path: &Path) -> Result<Vec<PresetEntry>, LoadEqualiserPresetsError> {
+    parse(&fs::read_to_string(path)?)
+}
+
+/// Adds `new` to `presets`, replacing the presets with the same names.
+fn merge(presets: &mut Vec<PresetEntry>, new: Vec<PresetEntry>) {
+    for preset in new {
+        if let Some(p) = presets
+            .iter_mut()
+            .find(|p| p.name.eq_ignore_ascii_case(&preset.name))
+        {
+            *p = preset;
+        } else {
+            presets.push(preset);
+        }
+    }
+}
+
+fn stamp_files(dir: &Path) -> std::io::Result<FilesStamp> {
+    let mut stamp = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let is_toml = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("toml"));
+        if !is_toml {
+            continue;
+        }
+        // files whose metadata can't be read are still stamped, so that loading them logs why
+        match fs::metadata(&path) {
+            Ok(metadata) if metadata.is_file() => {
+                stamp.push((path, metadata.modified().ok(), metadata.len()));
+            }
+            Ok(_) => {}
+            Err(_) => stamp.push((path, None, 0)),
+        }
+    }
+    stamp.sort_unstable_by(|(a, ..), (b, ..)| a.cmp(b));
+    Ok(stamp)
+}
+
+/// Loads the compiled-in presets, then the preset files in `dir` in the order of their names.
+fn load(dir: Option<&Path>) -> Loaded {
+    let mut presets = parse(DEFAULT_PRESETS).unwrap_or_else(|error| {
+        tracing::error!(%error, "skipped the compiled-in equaliser presets");
+        Vec::new()
+    });
+
+    let stamp = dir
+        .map(|dir| {
+            stamp_files(dir).unwrap_or_else(|error| {
+                tracing::error!(%error, dir = %dir.display(), "reading the presets directory failed");
+                Vec::new()
+            })
+        })
+        .unwrap_or_default();
+    for (path, ..) in &stamp {
+        match read_and_parse(path) {
+            Ok(new) => merge(&mut presets, new),
+            Err(error) => {
+                tracing::error!(%error, file = %path.display(), "skipped invalid equaliser preset file");
+            }
+        }
+    }
+
+    tracing::info!(count = presets.len(), "loaded equaliser presets");
+    Loaded {
+        presets: presets.into(),
+        stamp,
+    }
+}
+
+impl EqualiserPresets {
+    pub fn load() -> Self {
+        let dir = env::var_os("PRESETS_DIR")
+            .filter(|d| !d.is_empty())
+            .map(PathBuf::from);
+        let loaded = load(dir.as_deref());
+        Self {
+            dir,
+            loaded: RwLock::new(loaded),
+        }
+    }
+
+    /// Reloads the presets if any of the preset files were added, changed or removed.
+    pub async fn reload_if_changed(&self) {
+        let Some(dir) = self.dir.as_deref() else {
+            return;
+        };
+        let stamp = stamp_files(dir).unwrap_or_default();
+        if stamp == self.loaded.read().await.stamp {
+            return;
+        }
+
+        tracing::info!("equaliser preset files changed, reloading");
+        *self.loaded.write().await = load(Some(dir));
+    }
+
+    pub(super) async fn all(&self) -> Arc<[PresetEntry]> {
+        self.loaded.read().await.presets.clone()
+    }
+
+    pub(super) async fn find(&self, name: &str) -> Option<PresetEntry> {
+        self.all()
+            .await
+            .iter()
+            .find(|p| p.name.eq_ignore_ascii_case(name))
+            .cloned()
+    }
+
+    pub(super) async fn contains(&self, name: &str) -> bool {
+        self.find(name).await.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::component::tuning::LoadEqualiserPresetsError;
+
+    use super::{DEFAULT_PRESETS, PresetEntry, merge, parse};
+
+    fn preset(name: &str, gain: f64) -> String {
+        format!("[[equaliser]]\nname = {name:?}\ngains = {:?}\n", [gain; 15])
+    }
+
+    fn names(presets: &[PresetEntry]) -> Vec<&str> {
+        presets.iter().map(|p| p.name.as_str()).collect()
+    }
+
+    #[test]
+    fn default_presets_parse() {
+        let presets = parse(DEFAULT_PRESETS).expect("the compiled-in presets must be valid");
+        assert_eq!(
+            names(&presets),
+            [
+                "Acoustic",
+                "Bass",
+                "Beats",
+                "Classic",
+                "Clear",
+                "Deep Bass",
+                "Dubstep",
+                "Electronic",
+                "Hardstyle",
+                "Hip-Hop",
+                "Jazz",
+                "Metal",
+                "Movie",
+                "Pop",
+                "R&B",
+                "Rock",
+                "Vocal Booster",
+            ]
+        );
+    }
+
+    #[test]
+    #[expect(clippy::float_cmp)]
+    fn parse_trims_names() {
+        let presets = parse(&preset("  Loud  ", 0.5)).expect("preset must be valid");
+        assert_eq!(names(&presets), ["Loud"]);
+        assert_eq!(presets[0].gains, [0.5; 15]);
+    }
+
+    #[test]
+    fn parse_rejects_wrong_band_count() {
+        for gains in ["[]", "[0.0, 0.0]", &format!("{:?}", [0.; 16])] {
+            let content = format!("[[equaliser]]\nname = \"Bands\"\ngains = {gains}\n");
+            assert!(matches!(
+                parse(&content),
+                Err(LoadEqualiserPresetsError::Toml(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn parse_rejects_gains_out_of_range() {
+        for gain in [-0.26, 1.01] {
+            let Err(LoadEqualiserPresetsError::GainOutOfRange { name, band, .. }) =
+                parse(&preset("Loud", gain))
+            else {
+                panic!("gain {gain} must be rejected");
+            };
+            assert_eq!((name.as_str(), band), ("Loud", 1));
+        }
+        assert!(parse(&preset("Quiet", -0.25)).is_ok());
+        assert!(parse(&preset("Loud", 1.)).is_ok());
+    }
+
+    #[test]
+    fn parse_rejects_invalid_names() {
+        for name in ["", "   ", &"a".repeat(101)] {
+            assert!(matches!(
+                parse(&preset(name, 0.)),
+                Err(LoadEqualiserPresetsError::InvalidName(_))
+            ));
+        }
+
+        let content = preset("Loud", 0.5) + &preset(" loud", 0.6);
+        let Err(LoadEqualiserPresetsError::DuplicateName(name)) = parse(&content) else {
+            panic!("duplicate names must be rejected");
+        };
+        assert_eq!(name, "loud");
+    }
+
+    #[test]
+    #[expect(clippy::float_cmp)]
+    fn files_add_to_and_override_defaults() {
+        let mut presets = parse(DEFAULT_PRESETS).expect("the compiled-in presets must be valid");
+        let defaults_len = presets.len();
+
+        let file = preset("BASS", 0.1) + &preset("Loud", 0.5);
+        merge(&mut presets, parse(&file).expect("presets must be valid"));
+        assert_eq!(presets.len(), defaults_len + 1);
+        assert_eq!(presets[1].name, "BASS");
+        assert_eq!(presets[1].gains, [0.1; 15]);
+        assert_eq!(presets[defaults_len].name, "Loud");
+
+        // later files override earlier ones
+        merge(
+            &mut presets,
+            parse(&preset("loud", 0.6)).expect("preset must be valid"),
+        );
+        assert_eq!(presets.len(), defaults_len + 1);
+        assert_eq!(presets[defaults_len].gains, [0.6; 15]);
+    }
+}
@@ -0,0 +1,53 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+
+use crate::{
+    command::model::{BotGuildSlashCommand, GuildSlashCmdCtx},
+    core::model::{
+        DatabaseAware, UserIdAware, response::initial::message::create::RespondWithMessage,
+    },
+    error::CommandResult,
+    gateway::GuildIdAware,
+};
+
+use super::SavedPreset;
+
+/// Deletes a saved equaliser preset.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "delete")]
+pub struct Delete {
+    /// Which preset?
+    #[command(autocomplete = true)]
+    name: String,
+}
+
+impl BotGuildSlashCommand for Delete {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let Some(preset) = SavedPreset::find(
+            ctx.db(),
+            ctx.guild_id().get().cast_signed(),
+            ctx.user_id().get().cast_signed(),
+            &self.name,
+        )
+        .await?
+        else {
+            ctx.wrng(format!(
+                "**No saved preset named `{}`**; Use the command's autocomplete to pick a preset instead.",
+                self.name
+            ))
+            .await?;
+            return Ok(());
+        };
+
+        sqlx::query!("DELETE FROM equaliser_presets WHERE id = $1;", preset.id)
+            .execute(ctx.db())
+            .await?;
+
+        ctx.out(format!(
+            "🗑️ Deleted {} preset `{}`.",
+            super::scope_name(preset.personal),
+            preset.name
+        ))
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::http::attachment::Attachment;
+
+use crate::{
+    command::model::{BotGuildSlashCommand, GuildSlashCmdCtx},
+    core::model::{
+        DatabaseAware, UserIdAware, response::initial::message::create::RespondWithMessage,
+    },
+    error::CommandResult,
+    gateway::GuildIdAware,
+};
+
+use super::{PresetEntry, PresetsFile, SavedPreset};
+
+/// Exports saved equaliser presets as a file, for sharing or importing them again later.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "export")]
+pub struct Export {
+    /// Which preset? (If not given, all saved presets)
+    #[command(autocomplete = true)]
+    name: Option<String>,
+}
+
+impl BotGuildSlashCommand for Export {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let guild = ctx.guild_id().get().cast_signed();
+        let user = ctx.user_id().get().cast_signed();
+
+        let presets = if let Some(name) = self.name {
+            let Some(preset) = SavedPreset::find(ctx.db(), guild, user, &name).await? else {
+                ctx.wrng(format!(
+                    "**No saved preset named `{name}`**; Use the command's autocomplete to pick a preset instead."
+                ))
+                .await?;
+                return Ok(());
+            };
+            vec![preset]
+        } else {
+            SavedPreset::fetch_all(ctx.db(), guild, user).await?
+        };
+        if presets.is_empty() {
+            ctx.wrng("**No presets have been saved yet**; Save one via `/equaliser save` first.")
+                .await?;
+            return Ok(());
+        }
+
+        let file = PresetsFile {
+            equaliser: presets
+                .into_iter()
+                .map(|p| PresetEntry {
+                    name: p.name,
+                    gains: p.gains,
+                })
+                .collect(),
+        };
+        let presets_len = file.equaliser.len();
+        let toml = toml::to_string(&file).expect("serialising equaliser presets must not fail");
+        let attachments = vec![Attachment::from_bytes(
+            String::from("equalisers.toml"),
+            toml.into_bytes(),
+            0,
+        )];
+
+        ctx.respond()
+            .content(format!("📤 Exported `{presets_len} equaliser preset(s)`."))
+            .attachments(attachments)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+use std::path::Path;
+
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::channel::Attachment;
+
+use crate::{
+    command::model::{BotGuildSlashCommand, GuildSlashCmdCtx},
+    core::{
+        konst::misc::{EQUALISER_PRESET_FILE_SIZE_LIMIT, EQUALISER_PRESETS_LIMIT},
+        model::{
+            BotStateAware, DatabaseAware, UserIdAware,
+            response::initial::message::create::RespondWithMessage,
+        },
+    },
+    error::CommandResult,
+    gateway::GuildIdAware,
+};
+
+use super::{MAX_GAIN, MIN_GAIN, PresetsFile};
+
+/// Saves equaliser presets from a file exported from saved presets.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "import")]
+pub struct Import {
+    /// What file? [.toml file] (Presets with existing names will be overwritten)
+    file: Attachment,
+    /// Save the presets only for yourself? (If not given, no)
+    personal: Option<bool>,
+}
+
+impl BotGuildSlashCommand for Import {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let file = self.file;
+        let is_toml = Path::new(&file.filename)
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("toml"));
+        if !is_toml {
+            ctx.wrng(format!(
+                "**`{}` is not a preset file**; Preset files must be `.toml` files.",
+                file.filename
+            ))
+            .await?;
+            return Ok(());
+        }
+        if file.size > EQUALISER_PRESET_FILE_SIZE_LIMIT {
+            ctx.wrng(format!("**`{}` is too large to import.**", file.filename))
+                .await?;
+            return Ok(());
+        }
+
+        let content = match reqwest::get(&file.url).await {
+            Ok(response) => response.text().await.ok(),
+            Err(_) => None,
+        };
+        let Some(content) = content else {
+            ctx.unkn(format!("Failed to download `{}`.", file.filename))
+                .await?;
+            return Ok(());
+        };
+
+        let Some(mut presets) = toml::from_str::<PresetsFile>(&content)
+            .ok()
+            .map(|f| f.equaliser)
+            .filter(|p| !p.is_empty())
+        else {
+            ctx.wrng(format!(
+                "**No presets found in `{}`**; Preset files can be made via exporting saved presets.",
+                file.filename
+            ))
+            .await?;
+            return Ok(());
+        };
+        for preset in &mut presets {
+            preset.name = preset.name.trim().to_owned();
+        }
+
+        let builtins = ctx.bot().equaliser_presets().all().await;
+        let invalid = presets.iter().find(|p| {
+            !(1..=100).contains(&p.name.chars().count())
+                || builtins
+                    .iter()
+                    .any(|b| b.name.eq_ignore_ascii_case(&p.name))
+                || !super::gains_valid(&p.gains)
+        });
+        if let Some(invalid) = invalid {
+            ctx.wrng(format!(
+                "**Invalid preset: `{}`**; Preset names must be from `1` to `100` characters and not a built-in preset, \
+                and band gains must be from `{MIN_GAIN}` to `{MAX_GAIN}`.",
+                invalid.name
+            ))
+            .await?;
+            return Ok(());
+        }
+
+        let personal = self.personal.unwrap_or_default();
+        let owner = personal.then(|| ctx.user_id().get().cast_signed());
+        let guild = ctx.guild_id().get().cast_signed();
+        if !super::save_presets(ctx.db(), guild, owner, &presets).await? {
+            ctx.wrng(format!(
+                "**Too many presets to import**; There can only be up to `{EQUALISER_PRESETS_LIMIT}` {} presets.",
+                super::scope_name(personal),
+            ))
+            .await?;
+            return Ok(());
+        }
+
+        ctx.out(format!(
+            "📥 Imported `{} equaliser preset(s)` as {} presets.",
+            presets.len(),
+            super::scope_name(personal)
+        ))
+        .await?;
+        Ok(())
+    }
+}
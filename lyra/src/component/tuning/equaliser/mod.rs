@@ -1,16 +1,52 @@
+mod builtin;
 mod custom;
+mod delete;
+mod export;
+mod import;
 mod off;
 mod preset;
+mod rename;
+mod save;
 
+use std::sync::Arc;
+
+use fuzzy_matcher::FuzzyMatcher;
+use itertools::Itertools;
 use lavalink_rs::model::player::{Equalizer, Filters};
-use lyra_ext::num::usize_as_u8;
-use lyra_proc::BotGuildCommandGroup;
-use twilight_interactions::command::{CommandModel, CreateCommand};
-use twilight_model::guild::Permissions;
+use lyra_ext::num::{usize_as_i64, usize_as_u8};
+use lyra_proc::{BotGuildAutocompleteGroup, BotGuildCommandGroup};
+use serde::{Deserialize, Deserializer, Serialize, de};
+use sqlx::{Pool, Postgres};
+use twilight_interactions::command::{AutocompleteValue, CommandModel, CreateCommand};
+use twilight_model::{
+    application::command::{CommandOptionChoice, CommandOptionChoiceValue},
+    guild::Permissions,
+};
+
+use crate::{
+    command::{
+        check::DJ_PERMISSIONS,
+        model::{BotGuildAutocomplete, GuildAutocompleteCtx},
+    },
+    core::{
+        konst::{discord::COMMAND_CHOICES_LIMIT, misc::EQUALISER_PRESETS_LIMIT},
+        model::{
+            BotStateAware, DatabaseAware, UserIdAware,
+            response::initial::autocomplete::RespondAutocomplete,
+        },
+        statik::fuzzy_matcher::FUZZY_MATCHER,
+    },
+    error::command::AutocompleteResult,
+    gateway::GuildIdAware,
+};
 
-use crate::command::check::DJ_PERMISSIONS;
+pub use builtin::EqualiserPresets;
 
 const EQUALISER_N: usize = 15;
+const MIN_GAIN: f64 = -0.25;
+const MAX_GAIN: f64 = 1.0;
+
+type Gains = [f64; EQUALISER_N];
 
 struct SetEqualiser([Equalizer; EQUALISER_N]);
 
@@ -29,6 +65,25 @@ impl SetEqualiser {
             }))
         })
     }
+
+    fn from_gains(gains: Gains) -> Self {
+        Self(core::array::from_fn(|i| Equalizer {
+            band: usize_as_u8(i),
+            gain: gains[i],
+        }))
+    }
+
+    /// The gains of the equaliser currently applied in `filters`, if there is one.
+    fn current_gains(filters: Option<Filters>) -> Option<Gains> {
+        let bands = filters?.equalizer?;
+        let mut gains = [Self::DEFAULT_GAIN; EQUALISER_N];
+        for Equalizer { band, gain } in bands {
+            if let Some(g) = gains.get_mut(usize::from(band)) {
+                *g = gain;
+            }
+        }
+        Self::new(gains.map(Some)).map(|_| gains)
+    }
 }
 
 impl super::ApplyFilter for Option<SetEqualiser> {
@@ -40,6 +95,150 @@ impl super::ApplyFilter for Option<SetEqualiser> {
     }
 }
 
+/// Equaliser presets in the same format as `preset/equalisers.toml`, for exporting and importing
+/// saved presets.
+#[derive(Serialize, Deserialize)]
+struct PresetsFile {
+    equaliser: Vec<PresetEntry>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct PresetEntry {
+    name: String,
+    #[serde(deserialize_with = "deserialize_gains")]
+    gains: Gains,
+}
+
+/// Deserialises exactly [`EQUALISER_N`] gains, as deserialising [`Gains`] directly ignores any
+/// gains after those.
+fn deserialize_gains<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Gains, D::Error> {
+    let gains = Vec::<f64>::deserialize(deserializer)?;
+    let len = gains.len();
+    gains
+        .try_into()
+        .map_err(|_| de::Error::invalid_length(len, &"15 gains"))
+}
+
+fn gains_valid(gains: &Gains) -> bool {
+    gains.iter().all(|g| (MIN_GAIN..=MAX_GAIN).contains(g))
+}
+
+fn gains_from_column(gains: Vec<f64>) -> Gains {
+    gains
+        .try_into()
+        .expect("saved equaliser presets must have exactly 15 gains")
+}
+
+const fn scope_name(personal: bool) -> &'static str {
+    if personal { "personal" } else { "server" }
+}
+
+/// A saved preset visible to a user in a guild: either their own personal preset, or a preset
+/// saved for the entire guild. If both exist with the same name, the personal one takes
+/// precedence.
+struct SavedPreset {
+    id: i64,
+    name: String,
+    personal: bool,
+    gains: Gains,
+}
+
+impl SavedPreset {
+    async fn find(
+        db: &Pool<Postgres>,
+        guild: i64,
+        user: i64,
+        name: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let rec = sqlx::query!(
+            r#"SELECT id, name, owner IS NOT NULL as "personal!", gains FROM equaliser_presets
+            WHERE guild = $1 AND (owner IS NULL OR owner = $2) AND name = $3
+            ORDER BY owner IS NULL LIMIT 1;"#,
+            guild,
+            user,
+            name,
+        )
+        .fetch_optional(db)
+        .await?;
+
+        Ok(rec.map(|r| Self {
+            id: r.id,
+            name: r.name,
+            personal: r.personal,
+            gains: gains_from_column(r.gains),
+        }))
+    }
+
+    async fn fetch_all(
+        db: &Pool<Postgres>,
+        guild: i64,
+        user: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let recs = sqlx::query!(
+            r#"SELECT id, name, owner IS NOT NULL as "personal!", gains FROM equaliser_presets
+            WHERE guild = $1 AND (owner IS NULL OR owner = $2)
+            ORDER BY name, owner IS NULL;"#,
+            guild,
+            user,
+        )
+        .fetch_all(db)
+        .await?;
+
+        Ok(recs
+            .into_iter()
+            .map(|r| Self {
+                id: r.id,
+                name: r.name,
+                personal: r.personal,
+                gains: gains_from_column(r.gains),
+            })
+            .collect())
+    }
+}
+
+/// Saves `presets` for `guild`, or only for `owner` if given, overwriting existing presets with
+/// the same names. Returns `false` if the new presets would not fit within
+/// [`EQUALISER_PRESETS_LIMIT`].
+async fn save_presets(
+    db: &Pool<Postgres>,
+    guild: i64,
+    owner: Option<i64>,
+    presets: &[PresetEntry],
+) -> Result<bool, sqlx::Error> {
+    let mut tx = db.begin().await?;
+
+    let names = presets.iter().map(|p| p.name.clone()).collect::<Vec<_>>();
+    let kept = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM equaliser_presets
+        WHERE guild = $1 AND owner IS NOT DISTINCT FROM $2 AND NOT (name = ANY($3));"#,
+        guild,
+        owner,
+        &names,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    let new = names.iter().unique().count();
+    if kept + usize_as_i64(new) > EQUALISER_PRESETS_LIMIT {
+        return Ok(false);
+    }
+
+    for preset in presets {
+        sqlx::query!(
+            "INSERT INTO equaliser_presets (guild, owner, name, gains) VALUES ($1, $2, $3, $4)
+            ON CONFLICT (guild, owner, name) DO UPDATE SET gains = EXCLUDED.gains;",
+            guild,
+            owner,
+            preset.name,
+            &preset.gains,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(true)
+}
+
 #[derive(CommandModel, CreateCommand, BotGuildCommandGroup)]
 #[command(
     name = "equaliser",
@@ -54,6 +253,16 @@ pub enum Equaliser {
     Custom(Box<custom::Custom>),
     #[command(name = "off")]
     Off(off::Off),
+    #[command(name = "save")]
+    Save(save::Save),
+    #[command(name = "rename")]
+    Rename(rename::Rename),
+    #[command(name = "delete")]
+    Delete(delete::Delete),
+    #[command(name = "export")]
+    Export(export::Export),
+    #[command(name = "import")]
+    Import(Box<import::Import>),
 }
 
 impl Equaliser {
@@ -61,3 +270,79 @@ impl Equaliser {
         DJ_PERMISSIONS
     }
 }
+
+#[derive(CommandModel, BotGuildAutocompleteGroup)]
+#[command(autocomplete = true)]
+pub enum Autocomplete {
+    #[command(name = "preset")]
+    Preset(preset::Autocomplete),
+    #[command(name = "rename")]
+    Rename(SavedAutocomplete),
+    #[command(name = "delete")]
+    Delete(SavedAutocomplete),
+    #[command(name = "export")]
+    Export(SavedAutocomplete),
+}
+
+#[derive(CommandModel)]
+#[command(autocomplete = true)]
+pub struct SavedAutocomplete {
+    name: AutocompleteValue<String>,
+}
+
+impl BotGuildAutocomplete for SavedAutocomplete {
+    async fn execute(self, ctx: GuildAutocompleteCtx) -> AutocompleteResult {
+        let AutocompleteValue::Focused(name) = self.name else {
+            panic!("not exactly one autocomplete option focused")
+        };
+        autocomplete_presets(ctx, &name, false).await
+    }
+}
+
+/// Responds with the presets matching `query`: saved presets visible to the user, and built-in
+/// presets if `builtin` is `true`.
+async fn autocomplete_presets(
+    mut ctx: GuildAutocompleteCtx,
+    query: &str,
+    builtin: bool,
+) -> AutocompleteResult {
+    let saved = SavedPreset::fetch_all(
+        ctx.db(),
+        ctx.guild_id().get().cast_signed(),
+        ctx.user_id().get().cast_signed(),
+    )
+    .await?;
+
+    let saved = saved
+        .into_iter()
+        .map(|p| (format!("{} ({})", p.name, scope_name(p.personal)), p.name));
+    let builtins = if builtin {
+        ctx.bot().equaliser_presets().all().await
+    } else {
+        Arc::from([])
+    };
+    let builtins = builtins.iter().map(|p| (p.name.clone(), p.name.clone()));
+    let presets = saved.chain(builtins);
+
+    let choices: Vec<(String, String)> = if query.is_empty() {
+        presets.take(COMMAND_CHOICES_LIMIT).collect()
+    } else {
+        presets
+            .filter_map(|(label, n)| Some((FUZZY_MATCHER.fuzzy_match(&label, query)?, label, n)))
+            .sorted_by_key(|(s, _, _)| -s)
+            .take(COMMAND_CHOICES_LIMIT)
+            .map(|(_, label, n)| (label, n))
+            .collect()
+    };
+    let choices: Vec<_> = choices
+        .into_iter()
+        .map(|(label, n)| CommandOptionChoice {
+            name: label,
+            name_localizations: None,
+            value: CommandOptionChoiceValue::String(n),
+        })
+        .collect();
+
+    ctx.autocomplete(choices).await?;
+    Ok(())
+}
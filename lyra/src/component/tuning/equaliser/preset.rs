@@ -1,44 +1,52 @@
-use lyra_ext::num::usize_as_u8;
-use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_interactions::command::{AutocompleteValue, CommandModel, CreateCommand};
 
 use crate::{
-    command::model::GuildSlashCmdCtx,
+    command::model::{BotGuildAutocomplete, GuildAutocompleteCtx, GuildSlashCmdCtx},
     component::tuning::{
         UpdateFilter, equaliser::SetEqualiser, require_in_voice_unsuppressed_and_player,
     },
-    core::model::response::initial::message::create::RespondWithMessage,
+    core::model::{
+        BotStateAware, DatabaseAware, UserIdAware,
+        response::initial::message::create::RespondWithMessage,
+    },
+    error::command::AutocompleteResult,
+    gateway::GuildIdAware,
 };
 
-lyra_proc::read_equaliser_presets_as!(EqualiserPreset);
-
-impl From<EqualiserPreset> for SetEqualiser {
-    fn from(value: EqualiserPreset) -> Self {
-        let gains = value.gains();
-        Self(core::array::from_fn(|i| {
-            lavalink_rs::model::player::Equalizer {
-                band: usize_as_u8(i),
-                gain: gains[i],
-            }
-        }))
-    }
-}
-
 /// Enables the player equaliser from presets.
 #[derive(CommandModel, CreateCommand)]
 #[command(name = "preset")]
 pub struct Preset {
-    /// Which preset to use?
-    preset: EqualiserPreset,
+    /// Which preset to use? [built-in / saved preset]
+    #[command(autocomplete = true)]
+    preset: String,
 }
 
 impl crate::command::model::BotGuildSlashCommand for Preset {
     async fn run(self, mut ctx: GuildSlashCmdCtx) -> crate::error::CommandResult {
         let (_, player) = require_in_voice_unsuppressed_and_player(&ctx)?;
 
-        let preset_name = self.preset.value();
-        let update = Some(SetEqualiser::from(self.preset));
+        let saved = super::SavedPreset::find(
+            ctx.db(),
+            ctx.guild_id().get().cast_signed(),
+            ctx.user_id().get().cast_signed(),
+            &self.preset,
+        )
+        .await?;
+        let (preset_name, update) = if let Some(saved) = saved {
+            (saved.name, SetEqualiser::from_gains(saved.gains))
+        } else if let Some(builtin) = ctx.bot().equaliser_presets().find(&self.preset).await {
+            (builtin.name, SetEqualiser::from_gains(builtin.gains))
+        } else {
+            ctx.wrng(format!(
+                "**No preset named `{}`**; Use the command's autocomplete to pick a preset instead.",
+                self.preset
+            ))
+            .await?;
+            return Ok(());
+        };
 
-        player.update_filter(update).await?;
+        player.update_filter(Some(update)).await?;
         ctx.out(format!(
             "🎛️🟢 Enabled player equaliser (Preset: **`{preset_name}`**).",
         ))
@@ -46,3 +54,18 @@ impl crate::command::model::BotGuildSlashCommand for Preset {
         Ok(())
     }
 }
+
+#[derive(CommandModel)]
+#[command(autocomplete = true)]
+pub struct Autocomplete {
+    preset: AutocompleteValue<String>,
+}
+
+impl BotGuildAutocomplete for Autocomplete {
+    async fn execute(self, ctx: GuildAutocompleteCtx) -> AutocompleteResult {
+        let AutocompleteValue::Focused(preset) = self.preset else {
+            panic!("not exactly one autocomplete option focused")
+        };
+        super::autocomplete_presets(ctx, &preset, true).await
+    }
+}
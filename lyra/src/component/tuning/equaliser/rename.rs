@@ -0,0 +1,85 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+
+use crate::{
+    command::model::{BotGuildSlashCommand, GuildSlashCmdCtx},
+    core::model::{
+        BotStateAware, DatabaseAware, UserIdAware,
+        response::initial::message::create::RespondWithMessage,
+    },
+    error::CommandResult,
+    gateway::GuildIdAware,
+};
+
+use super::SavedPreset;
+
+/// Renames a saved equaliser preset.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "rename")]
+pub struct Rename {
+    /// Which preset?
+    #[command(autocomplete = true)]
+    name: String,
+    /// What to rename the preset to?
+    #[command(min_length = 1, max_length = 100)]
+    new_name: String,
+}
+
+impl BotGuildSlashCommand for Rename {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let guild = ctx.guild_id().get().cast_signed();
+        let user = ctx.user_id().get().cast_signed();
+        let new_name = self.new_name.trim();
+
+        let Some(preset) = SavedPreset::find(ctx.db(), guild, user, &self.name).await? else {
+            ctx.wrng(format!(
+                "**No saved preset named `{}`**; Use the command's autocomplete to pick a preset instead.",
+                self.name
+            ))
+            .await?;
+            return Ok(());
+        };
+        if ctx.bot().equaliser_presets().contains(new_name).await {
+            ctx.wrng(format!(
+                "**`{new_name}` is a built-in preset**; Pick a different name instead."
+            ))
+            .await?;
+            return Ok(());
+        }
+
+        let owner = preset.personal.then_some(user);
+        let taken = sqlx::query_scalar!(
+            r#"SELECT EXISTS(
+                SELECT 1 FROM equaliser_presets
+                WHERE guild = $1 AND owner IS NOT DISTINCT FROM $2 AND name = $3
+            ) as "exists!";"#,
+            guild,
+            owner,
+            new_name,
+        )
+        .fetch_one(ctx.db())
+        .await?;
+        let scope = super::scope_name(preset.personal);
+        if taken {
+            ctx.wrng(format!(
+                "**There is already a {scope} preset named `{new_name}`**; Pick a different name instead."
+            ))
+            .await?;
+            return Ok(());
+        }
+
+        sqlx::query!(
+            "UPDATE equaliser_presets SET name = $2 WHERE id = $1;",
+            preset.id,
+            new_name,
+        )
+        .execute(ctx.db())
+        .await?;
+
+        ctx.out(format!(
+            "✏️ Renamed {scope} preset `{}` to `{new_name}`.",
+            preset.name
+        ))
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+
+use crate::{
+    command::{
+        model::{BotGuildSlashCommand, GuildSlashCmdCtx},
+        require,
+    },
+    core::{
+        konst::misc::EQUALISER_PRESETS_LIMIT,
+        model::{
+            BotStateAware, DatabaseAware, UserIdAware,
+            response::initial::message::create::RespondWithMessage,
+        },
+    },
+    error::CommandResult,
+    gateway::GuildIdAware,
+};
+
+use super::{PresetEntry, SetEqualiser};
+
+/// Saves the currently applied equaliser as a preset.
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "save")]
+pub struct Save {
+    /// What to name the preset? (If a preset with this name exists, it will be overwritten)
+    #[command(min_length = 1, max_length = 100)]
+    name: String,
+    /// Save the preset only for yourself? (If not given, no)
+    personal: Option<bool>,
+}
+
+impl BotGuildSlashCommand for Save {
+    async fn run(self, mut ctx: GuildSlashCmdCtx) -> CommandResult {
+        let player = require::player(&ctx)?;
+        let Some(gains) = SetEqualiser::current_gains(player.info().await?.filters) else {
+            ctx.wrng("**No equaliser is enabled**; Enable an equaliser first before saving it.")
+                .await?;
+            return Ok(());
+        };
+
+        let name = self.name.trim();
+        if ctx.bot().equaliser_presets().contains(name).await {
+            ctx.wrng(format!(
+                "**`{name}` is a built-in preset**; Pick a different name instead."
+            ))
+            .await?;
+            return Ok(());
+        }
+
+        let personal = self.personal.unwrap_or_default();
+        let owner = personal.then(|| ctx.user_id().get().cast_signed());
+        let preset = PresetEntry {
+            name: name.to_owned(),
+            gains,
+        };
+        let guild = ctx.guild_id().get().cast_signed();
+        if !super::save_presets(ctx.db(), guild, owner, &[preset]).await? {
+            ctx.wrng(format!(
+                "**There are already `{EQUALISER_PRESETS_LIMIT}` {} presets**; Delete or overwrite one of them instead.",
+                super::scope_name(personal),
+            ))
+            .await?;
+            return Ok(());
+        }
+
+        ctx.out(format!(
+            "💾 Saved the current equaliser as {} preset `{name}`.",
+            super::scope_name(personal)
+        ))
+        .await?;
+        Ok(())
+    }
+}
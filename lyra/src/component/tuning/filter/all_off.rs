@@ -41,7 +41,11 @@ impl crate::command::model::BotGuildSlashCommand for AllOff {
         let player = require::player(&ctx)?;
 
         player.update_filter(ResetAllExceptSpeed).await?;
-        player.data().write().await.pitch_mut().reset();
+        let data = player.data();
+        let mut data_w = data.write().await;
+        data_w.pitch_mut().reset();
+        data_w.take_effect();
+        drop(data_w);
 
         ctx.out("🪄🔴 Disabled all filters.").await?;
         Ok(())
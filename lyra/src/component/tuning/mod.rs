@@ -1,9 +1,11 @@
+mod effect;
 mod equaliser;
 mod filter;
 mod speed;
 mod volume;
 
-pub use equaliser::Equaliser;
+pub use effect::{ActiveEffect, Effect};
+pub use equaliser::{Autocomplete as EqualiserAutocomplete, Equaliser, EqualiserPresets};
 pub use filter::Filter;
 pub use speed::Speed;
 pub use volume::Volume;
@@ -39,17 +41,23 @@ trait ApplyFilter {
 }
 
 trait UpdateFilter {
-    async fn update_filter(&self, update: impl ApplyFilter + Send + Sync) -> LavalinkResult<()>;
+    /// Applies `update` to the player's filters, returning the filters that were set.
+    async fn update_filter(
+        &self,
+        update: impl ApplyFilter + Send + Sync,
+    ) -> LavalinkResult<Filters>;
 }
 
 impl UpdateFilter for PlayerInterface {
-    async fn update_filter(&self, update: impl ApplyFilter + Send + Sync) -> LavalinkResult<()> {
+    async fn update_filter(
+        &self,
+        update: impl ApplyFilter + Send + Sync,
+    ) -> LavalinkResult<Filters> {
         let old_filter = self.info().await?.filters.unwrap_or_default();
 
-        self.context
-            .set_filters(update.apply_to(old_filter))
-            .await?;
-        Ok(())
+        let filter = update.apply_to(old_filter);
+        self.context.set_filters(filter.clone()).await?;
+        Ok(filter)
     }
 }
 
@@ -41,4 +41,5 @@ generate_emojis![
     (repeat_off, "➡️"),
     (repeat_all, "🔁"),
     (repeat_track, "🔂"),
+    (autoplay_on, "📻"),
 ];
@@ -1,3 +1,4 @@
 pub const BASE_URL: &str = "https://discord.com";
 pub const CDN_URL: &str = "https://cdn.discordapp.com";
 pub const COMMAND_CHOICES_LIMIT: usize = 25;
+pub const SELECT_MENU_OPTIONS_LIMIT: usize = 25;
@@ -3,6 +3,27 @@ use std::time::Duration;
 pub const ADD_TRACKS_WRAP_LIMIT: usize = 3;
 
 pub const WAIT_FOR_NOT_SUPPRESSED_TIMEOUT: Duration = Duration::from_secs(30);
-pub const WAIT_FOR_BOT_EVENTS_TIMEOUT: Duration = Duration::from_millis(1_000);
+pub const WAIT_FOR_BOT_EVENTS_TIMEOUT: Duration = Duration::from_secs(1);
 pub const DESTRUCTIVE_COMMAND_CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(60);
 pub const QUEUE_ADVANCE_DISABLED_TIMEOUT: Duration = Duration::from_millis(250);
+pub const QUEUE_VIEW_TIMEOUT: Duration = Duration::from_secs(180);
+pub const QUEUE_VIEW_PAGE_SIZE: usize = 10;
+pub const PLAYER_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(15);
+pub const LAVALINK_NODE_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+pub const DEFAULT_LAVALINK_SESSION_RESUME_TIMEOUT: u32 = 60;
+pub const PLAY_HISTORY_LIMIT: i64 = 500;
+pub const PLAY_HISTORY_PAGE_SIZE: usize = 10;
+pub const TRACK_RECOVERIES_LIMIT: i64 = 100;
+pub const TRACK_RECOVERIES_VIEW_LIMIT: i64 = 15;
+pub const USER_PLAYLISTS_LIMIT: i64 = 25;
+pub const USER_PLAYLIST_TRACKS_LIMIT: usize = 100;
+pub const EQUALISER_PRESETS_LIMIT: i64 = 25;
+pub const EQUALISER_PRESET_FILES_RELOAD_INTERVAL: Duration = Duration::from_secs(10);
+pub const EQUALISER_PRESET_FILE_SIZE_LIMIT: u64 = 64 * 1024;
+pub const AUTOPLAY_TRACKS_LIMIT: usize = 5;
+pub const AUTOPLAY_HISTORY_LOOKBACK: usize = 50;
+pub const QUEUE_IMPORT_TRACKS_LIMIT: usize = 1000;
+pub const QUEUE_IMPORT_FILE_SIZE_LIMIT: u64 = 4 * 1024 * 1024;
+pub const SEARCH_PICKER_TIMEOUT: Duration = Duration::from_secs(120);
+pub const SEARCH_TRACKS_LIMIT: usize = 10;
+pub const SEARCH_COLLECTIONS_LIMIT: usize = 5;
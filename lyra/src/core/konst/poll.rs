@@ -1,4 +1,9 @@
+use std::time::Duration;
+
 pub const UPVOTE: &str = "🟦";
 pub const DOWNVOTE: &str = "🟥";
 pub const BASE: &str = "⬛";
 pub const RATIO_BAR_SIZE: usize = 16;
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+pub const MIN_TIMEOUT: Duration = Duration::from_secs(10);
+pub const MAX_TIMEOUT: Duration = Duration::from_secs(600);
@@ -8,6 +8,7 @@ use std::{
     time::{Duration, Instant},
 };
 
+use reqwest::Url;
 use sqlx::{Pool, Postgres};
 use tokio::sync::{
     mpsc::{UnboundedReceiver, UnboundedSender},
@@ -25,32 +26,67 @@ use twilight_model::{
 use twilight_standby::Standby;
 
 use crate::{
-    LavalinkAware, core::http::InteractionClient, error::core::DeserialiseBodyFromHttpError,
-    lavalink::Lavalink,
+    LavalinkAware,
+    component::{
+        config::{GuildConfigCache, access::AccessCache},
+        tuning::EqualiserPresets,
+    },
+    core::http::InteractionClient,
+    error::core::{DeserialiseBodyFromHttpError, InvalidLavalinkNodeError},
+    lavalink::{Lavalink, NodeConfig},
 };
 
 use super::statik::application;
 
+/// `node` with its password hidden, so that invalid nodes can be reported without leaking it.
+fn redact_node_password(node: &str) -> String {
+    match Url::parse(node) {
+        Ok(mut url) if url.password().is_some() => {
+            let _ = url.set_password(Some("***"));
+            url.into()
+        }
+        _ => node.to_owned(),
+    }
+}
+
+/// Reads the Lavalink nodes from `LAVALINK_NODES` if it is set, falling back to the single node
+/// from `SERVER_ADDRESS`, `SERVER_PORT` and `LAVALINK_SERVER_PASSWORD` otherwise.
+fn lavalink_nodes() -> Result<Vec<NodeConfig>, InvalidLavalinkNodeError> {
+    let Some(nodes) = env::var("LAVALINK_NODES")
+        .ok()
+        .filter(|n| !n.trim().is_empty())
+    else {
+        let hostname = format!(
+            "{}:{}",
+            env::var("SERVER_ADDRESS").expect("missing SERVER_ADDRESS"),
+            env::var("SERVER_PORT").expect("missing SERVER_PORT")
+        );
+        let password =
+            env::var("LAVALINK_SERVER_PASSWORD").expect("missing LAVALINK_SERVER_PASSWORD");
+        return Ok(Vec::from([NodeConfig::new(hostname, password)]));
+    };
+
+    nodes
+        .split_whitespace()
+        .map(|n| {
+            NodeConfig::parse(n).ok_or_else(|| InvalidLavalinkNodeError(redact_node_password(n)))
+        })
+        .collect()
+}
+
 pub struct Config {
     token: String,
-    lavalink_host: String,
-    lavalink_pwd: String,
+    lavalink_nodes: Vec<NodeConfig>,
     database_url: String,
 }
 
 impl Config {
-    pub fn new() -> Self {
-        Self {
+    pub fn new() -> Result<Self, InvalidLavalinkNodeError> {
+        Ok(Self {
             token: env::var("BOT_TOKEN").expect("missing BOT_TOKEN"),
-            lavalink_host: format!(
-                "{}:{}",
-                env::var("SERVER_ADDRESS").expect("missing SERVER_ADDRESS"),
-                env::var("SERVER_PORT").expect("missing SERVER_PORT")
-            ),
-            lavalink_pwd: env::var("LAVALINK_SERVER_PASSWORD")
-                .expect("missing LAVALINK_SERVER_PASSWORD"),
+            lavalink_nodes: lavalink_nodes()?,
             database_url: env::var("DATABASE_URL").expect("missing DATABASE_URL"),
-        }
+        })
     }
 
     pub fn database_url(&self) -> &str {
@@ -61,8 +97,8 @@ impl Config {
         std::mem::take(&mut self.token)
     }
 
-    pub fn into_lavalink_host_and_pwd(self) -> (String, String) {
-        (self.lavalink_host, self.lavalink_pwd)
+    pub fn into_lavalink_nodes(self) -> Vec<NodeConfig> {
+        self.lavalink_nodes
     }
 }
 
@@ -211,6 +247,11 @@ pub trait UserPermissionsAware {
     fn user_permissions(&self) -> Permissions;
 }
 
+pub trait UserDjAware: UserPermissionsAware {
+    /// Whether the user is a DJ via the guild's DJ settings, regardless of their permissions.
+    fn user_is_configured_dj(&self) -> bool;
+}
+
 pub trait AvatarAware {
     fn avatar(&self) -> Option<ImageHash>;
 }
@@ -340,6 +381,9 @@ pub struct BotState {
     standby: Standby,
     lavalink: Lavalink,
     info: BotInfo,
+    guild_configs: GuildConfigCache,
+    access_cache: AccessCache,
+    equaliser_presets: EqualiserPresets,
 }
 
 impl BotState {
@@ -348,6 +392,8 @@ impl BotState {
         http: Arc<Client>,
         cache: Arc<InMemoryCache>,
         lavalink: Lavalink,
+        guild_configs: GuildConfigCache,
+        equaliser_presets: EqualiserPresets,
     ) -> Self {
         let info = BotInfo {
             started: Instant::now(),
@@ -361,6 +407,9 @@ impl BotState {
             lavalink,
             db,
             info,
+            guild_configs,
+            access_cache: AccessCache::new(10_000),
+            equaliser_presets,
         }
     }
 
@@ -372,6 +421,18 @@ impl BotState {
         &self.info
     }
 
+    pub const fn guild_configs(&self) -> &GuildConfigCache {
+        &self.guild_configs
+    }
+
+    pub const fn access_cache(&self) -> &AccessCache {
+        &self.access_cache
+    }
+
+    pub const fn equaliser_presets(&self) -> &EqualiserPresets {
+        &self.equaliser_presets
+    }
+
     #[inline]
     pub async fn application_emojis(
         &self,
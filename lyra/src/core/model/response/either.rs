@@ -41,7 +41,6 @@ impl<'a, T: Respond> EitherResponse<'a, T> {
         }
     }
 
-    #[expect(unused)]
     pub fn embeds(self, embeds: impl Into<Vec<Embed>>) -> Self {
         Self {
             inner: self.inner.embeds(embeds),
@@ -49,7 +48,6 @@ impl<'a, T: Respond> EitherResponse<'a, T> {
         }
     }
 
-    #[expect(unused)]
     pub fn components(self, components: impl Into<Vec<Component>>) -> Self {
         Self {
             inner: self.inner.components(components),
@@ -8,6 +8,7 @@ pub struct NowPlayingButtonIds {
     pub play_pause: &'static str,
     pub next: &'static str,
     pub repeat: &'static str,
+    pub autoplay: &'static str,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -17,6 +18,7 @@ pub enum NowPlayingButtonType {
     PlayPause,
     Next,
     Repeat,
+    Autoplay,
 }
 
 impl Display for NowPlayingButtonType {
@@ -27,6 +29,7 @@ impl Display for NowPlayingButtonType {
             Self::PlayPause => "play_pause",
             Self::Next => "next",
             Self::Repeat => "repeat",
+            Self::Autoplay => "autoplay",
         };
         f.write_str(s)
     }
@@ -46,6 +49,8 @@ impl TryFrom<&str> for NowPlayingButtonType {
             Ok(Self::Repeat)
         } else if id == NOW_PLAYING_BUTTON_IDS.shuffle {
             Ok(Self::Shuffle)
+        } else if id == NOW_PLAYING_BUTTON_IDS.autoplay {
+            Ok(Self::Autoplay)
         } else {
             Err(())
         }
@@ -71,6 +76,7 @@ impl NowPlayingButtonIds {
             play_pause: button_id_gen(),
             next: button_id_gen(),
             repeat: button_id_gen(),
+            autoplay: button_id_gen(),
         }
     }
 }
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use thiserror::Error;
 
 use crate::error::{
@@ -9,8 +11,7 @@ use crate::error::{
 #[derive(Error, Debug)]
 #[error(transparent)]
 pub enum AccessCalculatorBuildError {
-    Sqlx(#[from] sqlx::Error),
-    TaskJoin(#[from] tokio::task::JoinError),
+    Sqlx(#[from] Arc<sqlx::Error>),
 }
 
 #[derive(Error, Debug)]
@@ -124,6 +125,15 @@ impl<'a> PrettyErrorDisplay<'a> for PollVoidedError {
             crate::command::poll::VoidingEvent::QueueRepeat => {
                 PrettyVoidedErrorDisplayer::QueueRepeat
             }
+            crate::command::poll::VoidingEvent::QueueAdvance => {
+                PrettyVoidedErrorDisplayer::QueueAdvance
+            }
+            crate::command::poll::VoidingEvent::QueueReindex => {
+                PrettyVoidedErrorDisplayer::QueueReindex
+            }
+            crate::command::poll::VoidingEvent::PlayerPause => {
+                PrettyVoidedErrorDisplayer::PlayerPause
+            }
         }
     }
 }
@@ -131,6 +141,9 @@ impl<'a> PrettyErrorDisplay<'a> for PollVoidedError {
 pub enum PrettyVoidedErrorDisplayer {
     QueueClear,
     QueueRepeat,
+    QueueAdvance,
+    QueueReindex,
+    PlayerPause,
 }
 
 impl std::fmt::Display for PrettyVoidedErrorDisplayer {
@@ -138,6 +151,9 @@ impl std::fmt::Display for PrettyVoidedErrorDisplayer {
         let data = match self {
             Self::QueueClear => "the queue had been cleared",
             Self::QueueRepeat => "the queue had been set to repeat in another manner",
+            Self::QueueAdvance => "the current track had been changed",
+            Self::QueueReindex => "the queue had been reordered in another manner",
+            Self::PlayerPause => "the playback had been paused or resumed in another manner",
         };
         f.write_str(data)
     }
@@ -7,6 +7,8 @@ pub enum CommandExecuteError {
     #[error(transparent)]
     CheckUserAllowed(#[from] super::check::UserAllowedError),
     #[error(transparent)]
+    ResolveUserDj(#[from] sqlx::Error),
+    #[error(transparent)]
     InteractionParse(#[from] twilight_interactions::error::ParseError),
     #[error(transparent)]
     Command(#[from] super::Error),
@@ -16,7 +18,6 @@ pub enum CommandExecuteError {
 
 pub enum FlattenedUntilUserNotAllowedCommandExecuteError {
     Sqlx,
-    TaskJoin,
     UserNotAllowed,
     InteractionParse,
     UnknownCommand,
@@ -31,10 +32,10 @@ impl CommandExecuteError {
             Self::CheckUserAllowed(e) => match e {
                 super::check::UserAllowedError::AccessCalculatorBuild(e) => match e {
                     super::check::AccessCalculatorBuildError::Sqlx(_) => Fuunacee::Sqlx,
-                    super::check::AccessCalculatorBuildError::TaskJoin(_) => Fuunacee::TaskJoin,
                 },
                 super::check::UserAllowedError::UserNotAllowed(_) => Fuunacee::UserNotAllowed,
             },
+            Self::ResolveUserDj(_) => Fuunacee::Sqlx,
             Self::InteractionParse(_) => Fuunacee::InteractionParse,
             Self::UnknownCommand(_) => Fuunacee::UnknownCommand,
             Self::Command(_) => Fuunacee::Command,
@@ -40,10 +40,14 @@ pub enum Error {
     Join(Box<super::component::connection::join::ResidualError>),
     PlayPause(Box<super::component::playback::PlayPauseError>),
     Repeat(Box<super::component::queue::repeat::RepeatError>),
+    HandlePoll(Box<check::HandlePollError>),
+    Autoplay(Box<super::component::queue::autoplay::AutoplayError>),
     UpdateNowPlayingMessage(Box<super::lavalink::UpdateNowPlayingMessageError>),
     SeekToWith(Box<require::SeekToWithError>),
     NewNowPlayingMessage(Box<super::lavalink::NewNowPlayingMessageError>),
     Respond(Box<super::core::RespondError>),
+    RespondOrFollowup(Box<super::core::RespondOrFollowupError>),
+    DeserialiseBodyFromHttp(Box<super::core::DeserialiseBodyFromHttpError>),
 }
 
 macro_rules! declare_from_box_impls {
@@ -66,6 +70,8 @@ declare_from_box_impls!(
     Join => super::component::connection::join::ResidualError,
     PlayPause => super::component::playback::PlayPauseError,
     Repeat => super::component::queue::repeat::RepeatError,
+    HandlePoll => check::HandlePollError,
+    Autoplay => super::component::queue::autoplay::AutoplayError,
     Skip => super::component::playback::skip::SkipError,
     Back => super::component::playback::back::BackError,
     Shuffle => super::component::queue::shuffle::ShuffleError,
@@ -73,6 +79,8 @@ declare_from_box_impls!(
     SeekToWith => require::SeekToWithError,
     NewNowPlayingMessage => super::lavalink::NewNowPlayingMessageError,
     Respond => super::core::RespondError,
+    RespondOrFollowup => super::core::RespondOrFollowupError,
+    DeserialiseBodyFromHttp => super::core::DeserialiseBodyFromHttpError,
 );
 
 pub enum FlattenedError<'a> {
@@ -180,7 +188,6 @@ impl<'a> Fe<'a> {
     const fn from_access_calculator_build(error: &'a check::AccessCalculatorBuildError) -> Self {
         match error {
             check::AccessCalculatorBuildError::Sqlx(_) => Self::Sqlx,
-            check::AccessCalculatorBuildError::TaskJoin(_) => Self::TaskJoin,
         }
     }
 
@@ -300,6 +307,7 @@ impl<'a> Fe<'a> {
             super::component::connection::leave::DisconnectCleanupError::Lavalink(e) => {
                 Self::Lavalink(e)
             }
+            super::component::connection::leave::DisconnectCleanupError::Sqlx(_) => Self::Sqlx,
         }
     }
 
@@ -431,6 +439,7 @@ impl<'a> Fe<'a> {
             super::component::queue::play::HandleLoadTrackResultsError::RequireUnsuppressed(e) => Self::from_require_unsuppressed_error(e),
             super::component::queue::play::HandleLoadTrackResultsError::AutoJoinOrCheckInVoiceWithUser(e) => Self::from_auto_join_or_check_in_voice_with_user(e),
             super::component::queue::play::HandleLoadTrackResultsError::UpdateNowPlayingMessage(e) => Self::from_update_now_playing_message(e),
+            super::component::queue::play::HandleLoadTrackResultsError::Sqlx(_) => Self::Sqlx,
         }
     }
 
@@ -455,22 +464,20 @@ impl<'a> Fe<'a> {
             super::component::playback::PlayPauseError::Lavalink(e) => Self::Lavalink(e),
             super::component::playback::PlayPauseError::NotInVoice(_) => Self::NotInVoice,
             super::component::playback::PlayPauseError::NotPlaying(_) => Self::NotPlaying,
+            super::component::playback::PlayPauseError::Cache(_) => Self::Cache,
             super::component::playback::PlayPauseError::InVoiceWithoutUser(e) => {
                 Self::InVoiceWithoutUser(e)
             }
-            super::component::playback::PlayPauseError::Respond(e) => Self::from_respond(e),
+            super::component::playback::PlayPauseError::RespondOrFollowup(e) => {
+                Self::from_respond_or_followup(e)
+            }
             super::component::playback::PlayPauseError::SetPauseWith(e) => {
                 Self::from_set_pause_with(e)
             }
             super::component::playback::PlayPauseError::Unsuppressed(e) => {
                 Self::from_require_unsuppressed_error(e)
             }
-            super::component::playback::PlayPauseError::UserOnlyIn(e) => {
-                Self::from_check_user_only_in(e)
-            }
-            super::component::playback::PlayPauseError::UsersTrack(e) => {
-                Self::from_users_track_error(e)
-            }
+            super::component::playback::PlayPauseError::HandlePoll(e) => Self::from_handle_poll(e),
         }
     }
 
@@ -481,13 +488,14 @@ impl<'a> Fe<'a> {
                 Self::InVoiceWithoutUser(e)
             }
             super::component::playback::back::BackError::Lavalink(e) => Self::Lavalink(e),
-            super::component::playback::back::BackError::UserOnlyIn(e) => {
-                Self::from_check_user_only_in(e)
-            }
+            super::component::playback::back::BackError::Cache(_) => Self::Cache,
+            super::component::playback::back::BackError::HandlePoll(e) => Self::from_handle_poll(e),
             super::component::playback::back::BackError::Unsuppressed(e) => {
                 Self::from_require_unsuppressed_error(e)
             }
-            super::component::playback::back::BackError::Respond(e) => Self::from_respond(e),
+            super::component::playback::back::BackError::RespondOrFollowup(e) => {
+                Self::from_respond_or_followup(e)
+            }
         }
     }
 
@@ -502,10 +510,11 @@ impl<'a> Fe<'a> {
             super::component::playback::skip::SkipError::Unsuppressed(e) => {
                 Self::from_require_unsuppressed_error(e)
             }
-            super::component::playback::skip::SkipError::UsersTrackError(e) => {
-                Self::from_users_track_error(e)
+            super::component::playback::skip::SkipError::Cache(_) => Self::Cache,
+            super::component::playback::skip::SkipError::HandlePoll(e) => Self::from_handle_poll(e),
+            super::component::playback::skip::SkipError::RespondOrFollowup(e) => {
+                Self::from_respond_or_followup(e)
             }
-            super::component::playback::skip::SkipError::Respond(e) => Self::from_respond(e),
         }
     }
 
@@ -515,13 +524,16 @@ impl<'a> Fe<'a> {
             super::component::queue::shuffle::ShuffleError::InVoiceWithoutUser(e) => {
                 Self::InVoiceWithoutUser(e)
             }
-            super::component::queue::shuffle::ShuffleError::UserOnlyIn(e) => {
-                Self::from_check_user_only_in(e)
+            super::component::queue::shuffle::ShuffleError::Cache(_) => Self::Cache,
+            super::component::queue::shuffle::ShuffleError::HandlePoll(e) => {
+                Self::from_handle_poll(e)
             }
             super::component::queue::shuffle::ShuffleError::UpdateNowPlayingMessage(e) => {
                 Self::from_update_now_playing_message(e)
             }
-            super::component::queue::shuffle::ShuffleError::Respond(e) => Self::from_respond(e),
+            super::component::queue::shuffle::ShuffleError::RespondOrFollowup(e) => {
+                Self::from_respond_or_followup(e)
+            }
         }
     }
 
@@ -538,12 +550,85 @@ impl<'a> Fe<'a> {
                 Self::from_update_now_playing_message(e)
             }
             super::component::queue::repeat::RepeatError::Respond(e) => Self::from_respond(e),
-            super::component::queue::repeat::RepeatError::UserOnlyIn(e) => {
-                Self::from_check_user_only_in(e)
+            super::component::queue::repeat::RepeatError::RespondOrFollowup(e) => {
+                Self::from_respond_or_followup(e)
+            }
+            super::component::queue::repeat::RepeatError::Cache(_) => Self::Cache,
+            super::component::queue::repeat::RepeatError::HandlePoll(e) => {
+                Self::from_handle_poll(e)
             }
         }
     }
 
+    const fn from_autoplay(error: &'a super::component::queue::autoplay::AutoplayError) -> Self {
+        match error {
+            super::component::queue::autoplay::AutoplayError::UpdateNowPlayingMessage(e) => {
+                Self::from_update_now_playing_message(e)
+            }
+            super::component::queue::autoplay::AutoplayError::Respond(e) => Self::from_respond(e),
+        }
+    }
+
+    const fn from_handle_poll(error: &'a check::HandlePollError) -> Self {
+        match error {
+            check::HandlePollError::AnotherPollOngoing(e) => Self::AnotherPollOngoing(e),
+            check::HandlePollError::StartPoll(e) => Self::from_start_poll(e),
+            check::HandlePollError::EventSend(_) => Self::EventSend,
+            check::HandlePollError::DeserialiseBodyFromHttp(e) => {
+                Self::from_deserialize_body_from_http_error(e)
+            }
+            check::HandlePollError::PollLoss(e) => Self::PollLoss(e),
+            check::HandlePollError::PollVoided(e) => Self::PollVoided(e),
+            check::HandlePollError::EventRecv(_) => Self::EventRecv,
+        }
+    }
+
+    const fn from_start_poll(error: &'a poll::StartPollError) -> Self {
+        match error {
+            poll::StartPollError::Sqlx(_) => Self::Sqlx,
+            poll::StartPollError::GenerateEmbed(e) => Self::from_generate_poll_embed(e),
+            poll::StartPollError::Cache(_) => Self::Cache,
+            poll::StartPollError::DeserializeBody(_) => Self::DeserializeBody,
+            poll::StartPollError::WaitForVotes(e) => Self::from_wait_for_votes(e),
+            poll::StartPollError::Respond(e) => Self::from_respond(e),
+            poll::StartPollError::DeserialiseBodyFromHttp(e) => {
+                Self::from_deserialize_body_from_http_error(e)
+            }
+        }
+    }
+
+    const fn from_generate_poll_embed(error: &'a poll::GenerateEmbedError) -> Self {
+        match error {
+            poll::GenerateEmbedError::ImageSourceUrl(_) => Self::ImageSourceUrl,
+            poll::GenerateEmbedError::EmbedValidation(_) => Self::EmbedValidation,
+        }
+    }
+
+    const fn from_wait_for_votes(error: &'a poll::WaitForVotesError) -> Self {
+        match error {
+            poll::WaitForVotesError::Respond(e) => Self::from_respond(e),
+            poll::WaitForVotesError::UpdateEmbed(e) => Self::from_update_poll_embed(e),
+            poll::WaitForVotesError::EventRecv(_) => Self::EventRecv,
+        }
+    }
+
+    const fn from_update_poll_embed(error: &'a poll::UpdateEmbedError) -> Self {
+        match error {
+            poll::UpdateEmbedError::Http(_) => Self::TwilightHttp,
+            poll::UpdateEmbedError::EmbedValidation(_) => Self::EmbedValidation,
+            poll::UpdateEmbedError::MessageValidation(_) => Self::MessageValidation,
+            poll::UpdateEmbedError::Followup(e) => Self::from_followup(e),
+            poll::UpdateEmbedError::Respond(e) => Self::from_respond(e),
+        }
+    }
+
+    const fn from_followup(error: &'a super::core::FollowupError) -> Self {
+        match error {
+            super::core::FollowupError::TwilightHttp(_) => Self::TwilightHttp,
+            super::core::FollowupError::MessageValidation(_) => Self::MessageValidation,
+        }
+    }
+
     const fn from_update_now_playing_message(
         error: &'a super::lavalink::UpdateNowPlayingMessageError,
     ) -> Self {
@@ -655,11 +740,15 @@ impl Error {
             Self::Back(e) => Fe::from_back(e),
             Self::Shuffle(e) => Fe::from_shuffle(e),
             Self::Repeat(e) => Fe::from_repeat(e),
+            Self::HandlePoll(e) => Fe::from_handle_poll(e),
+            Self::Autoplay(e) => Fe::from_autoplay(e),
             Self::UpdateNowPlayingMessage(e) => Fe::from_update_now_playing_message(e),
             Self::SeekToWith(e) => Fe::from_seek_to_with(e),
             Self::NewNowPlayingData(e) => Fe::from_new_now_playing_data(e),
             Self::NewNowPlayingMessage(e) => Fe::from_new_now_playing_message(e),
             Self::Respond(e) => Fe::from_respond(e),
+            Self::RespondOrFollowup(e) => Fe::from_respond_or_followup(e),
+            Self::DeserialiseBodyFromHttp(e) => Fe::from_deserialize_body_from_http_error(e),
             Self::RequireInVoiceUnsuppressedAndPlayer(e) => {
                 Fe::from_require_in_voice_unsuppressed_and_player(e)
             }
@@ -675,6 +764,7 @@ pub enum AutocompleteError {
     LoadFailed(#[from] super::LoadFailed),
     TwilightHttp(#[from] twilight_http::Error),
     Lavalink(#[from] lavalink_rs::error::LavalinkError),
+    Sqlx(#[from] sqlx::Error),
 }
 
 pub type AutocompleteResult = core::result::Result<(), AutocompleteError>;
@@ -20,6 +20,7 @@ pub enum UpdateEmbedError {
 #[derive(Error, Debug)]
 #[error(transparent)]
 pub enum StartPollError {
+    Sqlx(#[from] sqlx::Error),
     GenerateEmbed(#[from] GenerateEmbedError),
     Cache(#[from] crate::error::Cache),
     DeserializeBody(#[from] twilight_http::response::DeserializeBodyError),
@@ -3,6 +3,7 @@
 pub enum DisconnectCleanupError {
     EventSend(#[from] tokio::sync::broadcast::error::SendError<crate::lavalink::Event>),
     Lavalink(#[from] lavalink_rs::error::LavalinkError),
+    Sqlx(#[from] sqlx::Error),
 }
 
 #[derive(thiserror::Error, Debug)]
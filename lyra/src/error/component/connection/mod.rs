@@ -1,5 +1,6 @@
 pub mod join;
 pub mod leave;
+pub mod restore;
 
 use thiserror::Error;
 
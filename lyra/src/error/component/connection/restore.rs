@@ -0,0 +1,8 @@
+#[derive(thiserror::Error, Debug)]
+#[error("restoring player failed: {:?}", .0)]
+pub enum Error {
+    Sqlx(#[from] sqlx::Error),
+    GatewaySend(#[from] twilight_gateway::error::ChannelError),
+    Lavalink(#[from] lavalink_rs::error::LavalinkError),
+    DisconnectCleanup(#[from] super::leave::DisconnectCleanupError),
+}
@@ -6,7 +6,8 @@ pub enum BackError {
     NotInVoice(#[from] crate::error::NotInVoice),
     Unsuppressed(#[from] crate::error::command::require::UnsuppressedError),
     InVoiceWithoutUser(#[from] crate::error::InVoiceWithoutUser),
-    UserOnlyIn(#[from] crate::error::command::check::UserOnlyInError),
-    Respond(#[from] crate::error::core::RespondError),
+    Cache(#[from] crate::error::Cache),
+    HandlePoll(#[from] crate::error::command::check::HandlePollError),
+    RespondOrFollowup(#[from] crate::error::core::RespondOrFollowupError),
     Lavalink(#[from] lavalink_rs::error::LavalinkError),
 }
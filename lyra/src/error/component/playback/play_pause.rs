@@ -6,10 +6,10 @@ pub enum Error {
     NotInVoice(#[from] crate::error::NotInVoice),
     Unsuppressed(#[from] crate::error::command::require::UnsuppressedError),
     InVoiceWithoutUser(#[from] crate::error::InVoiceWithoutUser),
-    UserOnlyIn(#[from] crate::error::command::check::UserOnlyInError),
     NotPlaying(#[from] crate::error::NotPlaying),
-    UsersTrack(#[from] crate::error::command::check::UsersTrackError),
+    Cache(#[from] crate::error::Cache),
+    HandlePoll(#[from] crate::error::command::check::HandlePollError),
     Lavalink(#[from] lavalink_rs::error::LavalinkError),
-    Respond(#[from] crate::error::core::RespondError),
+    RespondOrFollowup(#[from] crate::error::core::RespondOrFollowupError),
     SetPauseWith(#[from] crate::error::command::require::SetPauseWithError),
 }
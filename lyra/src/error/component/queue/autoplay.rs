@@ -0,0 +1,6 @@
+#[derive(thiserror::Error, Debug)]
+#[error(transparent)]
+pub enum AutoplayError {
+    UpdateNowPlayingMessage(#[from] crate::error::lavalink::UpdateNowPlayingMessageError),
+    Respond(#[from] crate::error::core::RespondError),
+}
@@ -1,3 +1,4 @@
+pub mod autoplay;
 pub mod play;
 pub mod repeat;
 pub mod shuffle;
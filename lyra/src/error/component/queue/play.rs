@@ -34,4 +34,5 @@ pub enum HandleLoadTrackResultsError {
         #[from] crate::error::command::util::AutoJoinOrCheckInVoiceWithUserError,
     ),
     UpdateNowPlayingMessage(#[from] crate::error::lavalink::UpdateNowPlayingMessageError),
+    Sqlx(#[from] sqlx::Error),
 }
@@ -3,7 +3,9 @@
 pub enum RepeatError {
     NotInVoice(#[from] crate::error::NotInVoice),
     InVoiceWithoutUser(#[from] crate::error::InVoiceWithoutUser),
-    UserOnlyIn(#[from] crate::error::command::check::UserOnlyInError),
+    Cache(#[from] crate::error::Cache),
+    HandlePoll(#[from] crate::error::command::check::HandlePollError),
+    RespondOrFollowup(#[from] crate::error::core::RespondOrFollowupError),
     Respond(#[from] crate::error::core::RespondError),
     UnrecognisedConnection(#[from] crate::error::UnrecognisedConnection),
     UpdateNowPlayingMessage(#[from] crate::error::lavalink::UpdateNowPlayingMessageError),
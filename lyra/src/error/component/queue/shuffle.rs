@@ -3,7 +3,8 @@
 pub enum ShuffleError {
     NotInVoice(#[from] crate::error::NotInVoice),
     InVoiceWithoutUser(#[from] crate::error::InVoiceWithoutUser),
-    UserOnlyIn(#[from] crate::error::command::check::UserOnlyInError),
+    Cache(#[from] crate::error::Cache),
+    HandlePoll(#[from] crate::error::command::check::HandlePollError),
     UpdateNowPlayingMessage(#[from] crate::error::lavalink::UpdateNowPlayingMessageError),
-    Respond(#[from] crate::error::core::RespondError),
+    RespondOrFollowup(#[from] crate::error::core::RespondOrFollowupError),
 }
@@ -9,3 +9,24 @@ pub enum RequireInVoiceUnsuppressedAndPlayerError {
     Unsuppressed(#[from] UnsuppressedError),
     NoPlayer(#[from] NoPlayerError),
 }
+
+#[derive(Error, Debug)]
+pub enum LoadEqualiserPresetsError {
+    #[error("reading the file failed: {}", .0)]
+    Io(#[from] std::io::Error),
+    #[error("parsing the file failed: {}", .0)]
+    Toml(#[from] toml::de::Error),
+    #[error("preset names must be from 1 to 100 characters, but found {:?}", .0)]
+    InvalidName(String),
+    #[error("preset {:?} is defined more than once", .0)]
+    DuplicateName(String),
+    #[error(
+        "band {} of preset {:?} has a gain of {}, but gains must be from -0.25 to 1.0",
+        .band, .name, .gain
+    )]
+    GainOutOfRange {
+        name: String,
+        band: usize,
+        gain: f64,
+    },
+}
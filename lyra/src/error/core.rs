@@ -7,6 +7,10 @@ pub enum FollowupError {
     MessageValidation(#[from] twilight_validate::message::MessageValidationError),
 }
 
+#[derive(Error, Debug)]
+#[error("invalid node in LAVALINK_NODES: `{}`", .0)]
+pub struct InvalidLavalinkNodeError(pub String);
+
 #[derive(Error, Debug)]
 #[error(transparent)]
 pub enum DeserialiseBodyFromHttpError {
@@ -10,6 +10,7 @@ pub enum ControllerError {
     PlayPause(#[from] crate::error::component::playback::PlayPauseError),
     Skip(#[from] crate::error::component::playback::skip::SkipError),
     Repeat(#[from] crate::error::component::queue::repeat::RepeatError),
+    Autoplay(#[from] crate::error::component::queue::autoplay::AutoplayError),
 }
 
 pub enum FlattenedControllerError<'a> {
@@ -17,16 +18,22 @@ pub enum FlattenedControllerError<'a> {
     NotPlaying,
     UnrecognisedConnection,
     Cache,
+    Sqlx,
     TwilightHttp,
     ImageSourceUrl,
     TimestampParse,
     DeserializeBody,
     Builder,
+    EmbedValidation,
+    MessageValidation,
+    EventSend,
+    EventRecv,
     InVoiceWithoutUser(&'a crate::error::InVoiceWithoutUser),
-    InVoiceWithSomeoneElse(&'a crate::error::InVoiceWithSomeoneElse),
     Lavalink(&'a lavalink_rs::error::LavalinkError),
     Suppressed(&'a crate::error::Suppressed),
-    NotUsersTrack(&'a crate::error::NotUsersTrack),
+    AnotherPollOngoing(&'a crate::error::command::check::AnotherPollOngoingError),
+    PollLoss(&'a crate::error::command::check::PollLossError),
+    PollVoided(&'a crate::error::command::check::PollVoidedError),
 }
 
 impl<'a> Fe<'a> {
@@ -40,14 +47,15 @@ impl<'a> Fe<'a> {
             crate::error::component::queue::shuffle::ShuffleError::InVoiceWithoutUser(e) => {
                 Self::InVoiceWithoutUser(e)
             }
-            crate::error::component::queue::shuffle::ShuffleError::UserOnlyIn(e) => {
-                Self::from_check_user_only_in(e)
+            crate::error::component::queue::shuffle::ShuffleError::Cache(_) => Self::Cache,
+            crate::error::component::queue::shuffle::ShuffleError::HandlePoll(e) => {
+                Self::from_handle_poll(e)
             }
             crate::error::component::queue::shuffle::ShuffleError::UpdateNowPlayingMessage(e) => {
                 Self::from_update_now_playing_message(e)
             }
-            crate::error::component::queue::shuffle::ShuffleError::Respond(e) => {
-                Self::from_respond(e)
+            crate::error::component::queue::shuffle::ShuffleError::RespondOrFollowup(e) => {
+                Self::from_respond_or_followup(e)
             }
         }
     }
@@ -59,13 +67,16 @@ impl<'a> Fe<'a> {
                 Self::InVoiceWithoutUser(e)
             }
             crate::error::component::playback::back::BackError::Lavalink(e) => Self::Lavalink(e),
-            crate::error::component::playback::back::BackError::UserOnlyIn(e) => {
-                Self::from_check_user_only_in(e)
+            crate::error::component::playback::back::BackError::Cache(_) => Self::Cache,
+            crate::error::component::playback::back::BackError::HandlePoll(e) => {
+                Self::from_handle_poll(e)
             }
             crate::error::component::playback::back::BackError::Unsuppressed(e) => {
                 Self::from_require_unsuppressed_error(e)
             }
-            crate::error::component::playback::back::BackError::Respond(e) => Self::from_respond(e),
+            crate::error::component::playback::back::BackError::RespondOrFollowup(e) => {
+                Self::from_respond_or_followup(e)
+            }
         }
     }
 
@@ -74,21 +85,21 @@ impl<'a> Fe<'a> {
             crate::error::component::playback::PlayPauseError::Lavalink(e) => Self::Lavalink(e),
             crate::error::component::playback::PlayPauseError::NotInVoice(_) => Self::NotInVoice,
             crate::error::component::playback::PlayPauseError::NotPlaying(_) => Self::NotPlaying,
+            crate::error::component::playback::PlayPauseError::Cache(_) => Self::Cache,
             crate::error::component::playback::PlayPauseError::InVoiceWithoutUser(e) => {
                 Self::InVoiceWithoutUser(e)
             }
-            crate::error::component::playback::PlayPauseError::Respond(e) => Self::from_respond(e),
+            crate::error::component::playback::PlayPauseError::RespondOrFollowup(e) => {
+                Self::from_respond_or_followup(e)
+            }
             crate::error::component::playback::PlayPauseError::SetPauseWith(e) => {
                 Self::from_set_pause_with(e)
             }
             crate::error::component::playback::PlayPauseError::Unsuppressed(e) => {
                 Self::from_require_unsuppressed_error(e)
             }
-            crate::error::component::playback::PlayPauseError::UserOnlyIn(e) => {
-                Self::from_check_user_only_in(e)
-            }
-            crate::error::component::playback::PlayPauseError::UsersTrack(e) => {
-                Self::from_users_track_error(e)
+            crate::error::component::playback::PlayPauseError::HandlePoll(e) => {
+                Self::from_handle_poll(e)
             }
         }
     }
@@ -104,10 +115,13 @@ impl<'a> Fe<'a> {
             crate::error::component::playback::skip::SkipError::Unsuppressed(e) => {
                 Self::from_require_unsuppressed_error(e)
             }
-            crate::error::component::playback::skip::SkipError::UsersTrackError(e) => {
-                Self::from_users_track_error(e)
+            crate::error::component::playback::skip::SkipError::Cache(_) => Self::Cache,
+            crate::error::component::playback::skip::SkipError::HandlePoll(e) => {
+                Self::from_handle_poll(e)
+            }
+            crate::error::component::playback::skip::SkipError::RespondOrFollowup(e) => {
+                Self::from_respond_or_followup(e)
             }
-            crate::error::component::playback::skip::SkipError::Respond(e) => Self::from_respond(e),
         }
     }
 
@@ -126,20 +140,110 @@ impl<'a> Fe<'a> {
             crate::error::component::queue::repeat::RepeatError::Respond(e) => {
                 Self::from_respond(e)
             }
-            crate::error::component::queue::repeat::RepeatError::UserOnlyIn(e) => {
-                Self::from_check_user_only_in(e)
+            crate::error::component::queue::repeat::RepeatError::RespondOrFollowup(e) => {
+                Self::from_respond_or_followup(e)
+            }
+            crate::error::component::queue::repeat::RepeatError::Cache(_) => Self::Cache,
+            crate::error::component::queue::repeat::RepeatError::HandlePoll(e) => {
+                Self::from_handle_poll(e)
+            }
+        }
+    }
+
+    const fn from_autoplay(
+        error: &'a crate::error::component::queue::autoplay::AutoplayError,
+    ) -> Self {
+        match error {
+            crate::error::component::queue::autoplay::AutoplayError::UpdateNowPlayingMessage(e) => {
+                Self::from_update_now_playing_message(e)
+            }
+            crate::error::component::queue::autoplay::AutoplayError::Respond(e) => {
+                Self::from_respond(e)
+            }
+        }
+    }
+
+    const fn from_handle_poll(error: &'a crate::error::command::check::HandlePollError) -> Self {
+        match error {
+            crate::error::command::check::HandlePollError::AnotherPollOngoing(e) => {
+                Self::AnotherPollOngoing(e)
+            }
+            crate::error::command::check::HandlePollError::StartPoll(e) => Self::from_start_poll(e),
+            crate::error::command::check::HandlePollError::EventSend(_) => Self::EventSend,
+            crate::error::command::check::HandlePollError::DeserialiseBodyFromHttp(e) => {
+                Self::from_deserialize_body_from_http_error(e)
+            }
+            crate::error::command::check::HandlePollError::PollLoss(e) => Self::PollLoss(e),
+            crate::error::command::check::HandlePollError::PollVoided(e) => Self::PollVoided(e),
+            crate::error::command::check::HandlePollError::EventRecv(_) => Self::EventRecv,
+        }
+    }
+
+    const fn from_start_poll(error: &'a crate::error::command::poll::StartPollError) -> Self {
+        match error {
+            crate::error::command::poll::StartPollError::Sqlx(_) => Self::Sqlx,
+            crate::error::command::poll::StartPollError::GenerateEmbed(e) => {
+                Self::from_generate_poll_embed(e)
+            }
+            crate::error::command::poll::StartPollError::Cache(_) => Self::Cache,
+            crate::error::command::poll::StartPollError::DeserializeBody(_) => {
+                Self::DeserializeBody
+            }
+            crate::error::command::poll::StartPollError::WaitForVotes(e) => {
+                Self::from_wait_for_votes(e)
+            }
+            crate::error::command::poll::StartPollError::Respond(e) => Self::from_respond(e),
+            crate::error::command::poll::StartPollError::DeserialiseBodyFromHttp(e) => {
+                Self::from_deserialize_body_from_http_error(e)
+            }
+        }
+    }
+
+    const fn from_generate_poll_embed(
+        error: &'a crate::error::command::poll::GenerateEmbedError,
+    ) -> Self {
+        match error {
+            crate::error::command::poll::GenerateEmbedError::ImageSourceUrl(_) => {
+                Self::ImageSourceUrl
             }
+            crate::error::command::poll::GenerateEmbedError::EmbedValidation(_) => {
+                Self::EmbedValidation
+            }
+        }
+    }
+
+    const fn from_wait_for_votes(
+        error: &'a crate::error::command::poll::WaitForVotesError,
+    ) -> Self {
+        match error {
+            crate::error::command::poll::WaitForVotesError::Respond(e) => Self::from_respond(e),
+            crate::error::command::poll::WaitForVotesError::UpdateEmbed(e) => {
+                Self::from_update_poll_embed(e)
+            }
+            crate::error::command::poll::WaitForVotesError::EventRecv(_) => Self::EventRecv,
         }
     }
 
-    const fn from_check_user_only_in(
-        error: &'a crate::error::command::check::UserOnlyInError,
+    const fn from_update_poll_embed(
+        error: &'a crate::error::command::poll::UpdateEmbedError,
     ) -> Self {
         match error {
-            crate::error::command::check::UserOnlyInError::Cache(_) => Self::Cache,
-            crate::error::command::check::UserOnlyInError::InVoiceWithSomeoneElse(e) => {
-                Self::InVoiceWithSomeoneElse(e)
+            crate::error::command::poll::UpdateEmbedError::Http(_) => Self::TwilightHttp,
+            crate::error::command::poll::UpdateEmbedError::EmbedValidation(_) => {
+                Self::EmbedValidation
+            }
+            crate::error::command::poll::UpdateEmbedError::MessageValidation(_) => {
+                Self::MessageValidation
             }
+            crate::error::command::poll::UpdateEmbedError::Followup(e) => Self::from_followup(e),
+            crate::error::command::poll::UpdateEmbedError::Respond(e) => Self::from_respond(e),
+        }
+    }
+
+    const fn from_followup(error: &'a crate::error::core::FollowupError) -> Self {
+        match error {
+            crate::error::core::FollowupError::TwilightHttp(_) => Self::TwilightHttp,
+            crate::error::core::FollowupError::MessageValidation(_) => Self::MessageValidation,
         }
     }
 
@@ -186,6 +290,15 @@ impl<'a> Fe<'a> {
         }
     }
 
+    const fn from_respond_or_followup(
+        error: &'a crate::error::core::RespondOrFollowupError,
+    ) -> Self {
+        match error {
+            crate::error::core::RespondOrFollowupError::Respond(e) => Self::from_respond(e),
+            crate::error::core::RespondOrFollowupError::Followup(_) => Self::TwilightHttp,
+        }
+    }
+
     const fn from_respond(error: &'a crate::error::core::RespondError) -> Self {
         match error {
             crate::error::core::RespondError::TwilightHttp(_) => Self::TwilightHttp,
@@ -217,17 +330,6 @@ impl<'a> Fe<'a> {
             }
         }
     }
-
-    const fn from_users_track_error(
-        error: &'a crate::error::command::check::UsersTrackError,
-    ) -> Self {
-        match error {
-            crate::error::command::check::UsersTrackError::Cache(_) => Self::Cache,
-            crate::error::command::check::UsersTrackError::NotUsersTrack(e) => {
-                Self::NotUsersTrack(e)
-            }
-        }
-    }
 }
 
 impl ControllerError {
@@ -239,6 +341,7 @@ impl ControllerError {
             Self::PlayPause(e) => Fe::from_play_pause(e),
             Self::Skip(e) => Fe::from_skip(e),
             Self::Repeat(e) => Fe::from_repeat(e),
+            Self::Autoplay(e) => Fe::from_autoplay(e),
         }
     }
 }
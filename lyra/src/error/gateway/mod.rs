@@ -17,6 +17,7 @@ pub enum ProcessError {
     ConnectionHandleVoiceStateUpdate(
         #[from] super::component::connection::HandleVoiceStateUpdateError,
     ),
+    ConnectionRestore(#[from] super::component::connection::restore::Error),
     PlaybackHandleVoiceStateUpdate(#[from] super::component::playback::HandleVoiceStateUpdateError),
     #[error("error executing command `/{}`: {:?}", .name, .source)]
     CommandExecute {
@@ -56,4 +56,13 @@ pub enum GetDominantPaletteFromUrlError {
     Image(#[from] lyra_ext::ImageError),
 }
 
+#[derive(Error, Debug)]
+#[error("saving player snapshot failed: {:?}", .0)]
+pub enum SaveSnapshotError {
+    Lavalink(#[from] lavalink_rs::error::LavalinkError),
+    Sqlx(#[from] sqlx::Error),
+    SerdeJson(#[from] serde_json::Error),
+    UnrecognisedConnection(#[from] super::UnrecognisedConnection),
+}
+
 pub type ProcessResult = Result<(), ProcessError>;
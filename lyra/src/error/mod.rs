@@ -143,7 +143,7 @@ pub struct NotUsersTrack {
     pub requester: Id<UserMarker>,
     pub position: std::num::NonZeroUsize,
     pub title: std::sync::Arc<str>,
-    pub channel_id: Id<ChannelMarker>,
+    pub in_voice: PartialInVoice,
 }
 
 impl<'a> PrettyErrorDisplay<'a> for NotUsersTrack {
@@ -166,7 +166,7 @@ impl std::fmt::Display for PrettyNotUsersTrackDisplayer<'_> {
             inner.title,
             inner.position,
             inner.requester.mention(),
-            inner.channel_id.mention(),
+            inner.in_voice.channel_id().mention(),
         )
     }
 }
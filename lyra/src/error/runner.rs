@@ -9,6 +9,7 @@ pub enum StartError {
     DeserializeBody(#[from] twilight_http::response::DeserializeBodyError),
     Http(#[from] twilight_http::Error),
     WaitUntilShutdown(#[from] WaitUntilShutdownError),
+    InvalidLavalinkNode(#[from] super::core::InvalidLavalinkNodeError),
 }
 
 #[derive(Error, Debug)]
@@ -1,19 +1,49 @@
-use twilight_gateway::ShardId;
+use twilight_cache_inmemory::InMemoryCache;
+use twilight_gateway::{MessageSender, ShardId};
 use twilight_model::{
     gateway::payload::incoming::{GuildCreate, GuildDelete},
     guild::Guild,
+    id::{Id, marker::GuildMarker},
 };
 
-use super::model::Process;
+use super::{GuildIdAware, SenderAware, model::Process};
 use crate::{
-    core::model::{BotState, BotStateRef, DatabaseAware},
+    LavalinkAware,
+    component::connection,
+    core::model::{BotState, BotStateRef, CacheAware, DatabaseAware},
     error::gateway::ProcessResult,
+    lavalink::Lavalink,
 };
 
 pub(super) struct CreateContext<'a> {
     inner: &'a GuildCreate,
     shard_id: ShardId,
     bot: BotStateRef<'a>,
+    sender: MessageSender,
+}
+
+impl GuildIdAware for CreateContext<'_> {
+    fn guild_id(&self) -> Id<GuildMarker> {
+        self.inner.id()
+    }
+}
+
+impl SenderAware for CreateContext<'_> {
+    fn sender(&self) -> &MessageSender {
+        &self.sender
+    }
+}
+
+impl CacheAware for CreateContext<'_> {
+    fn cache(&self) -> &InMemoryCache {
+        self.bot.cache()
+    }
+}
+
+impl LavalinkAware for CreateContext<'_> {
+    fn lavalink(&self) -> &Lavalink {
+        self.bot.lavalink()
+    }
 }
 
 impl CreateContext<'_> {
@@ -45,12 +75,16 @@ impl Process for CreateContext<'_> {
         // * GuildCreate::Unavailable(UnavailableGuild { unavailable: false, .. })  (can't-happen: deserialised as #2)
         // * GuildCreate::Unavailable(UnavailableGuild { unavailable: true, .. })   current user joined an unavailable guild
         //
-        // only variants #1 and #5 are of interest, so just early returning upon #2 is sufficient
+        // only variants #1 and #5 are of interest, so just early returning upon #2 is sufficient.
+        //
+        // however, upon #2, the guild may have had a player before the bot restarted, so
+        // it is restored here.
         if let GuildCreate::Available(Guild {
             unavailable: Some(false),
             ..
         }) = self.inner
         {
+            connection::restore(&self).await?;
             return Ok(());
         }
 
@@ -70,11 +104,13 @@ impl BotState {
         &'a self,
         inner: &'a GuildCreate,
         shard_id: ShardId,
+        sender: MessageSender,
     ) -> CreateContext<'a> {
         CreateContext {
             inner,
             shard_id,
             bot: self,
+            sender,
         }
     }
 
@@ -110,6 +146,9 @@ impl Process for DeleteContext<'_> {
         }
 
         self.decrement_guild_count();
+        let guild_id = self.inner.id;
+        self.bot.guild_configs().invalidate(guild_id).await;
+        self.bot.access_cache().invalidate(&guild_id).await;
         Ok(())
     }
 }
@@ -19,7 +19,6 @@ use crate::{
             GuildMessageCmdCtx, GuildSlashCmdCtx, MessageCmdCtx, NonPingInteraction, SlashCmdCtx,
         },
         require,
-        util::MessageLinkAware,
     },
     component::{connection::Join, queue::Play},
     core::{
@@ -37,9 +36,6 @@ use crate::{
         AutoJoinAttemptFailed, PositionOutOfRange, PrettyErrorDisplay,
         command::{
             FlattenedError as Fe,
-            check::{
-                AlternateVoteResponse, AnotherPollOngoingError, PollLossError, PollLossErrorKind,
-            },
             declare::{
                 CommandExecuteError, FlattenedUntilUserNotAllowedCommandExecuteError as Fuunacee,
             },
@@ -50,10 +46,10 @@ use crate::{
 };
 
 use super::{
-    SUPPRESSED_MESSAGE, UnitRespondOrFollowupResult, UnitRespondResult, match_cache,
-    match_in_voice_with_someone_else, match_in_voice_without_user, match_lavalink,
-    match_not_in_voice, match_not_playing, match_not_users_track, match_suppressed,
-    match_unrecognised_connection, match_wildcard,
+    SUPPRESSED_MESSAGE, UnitRespondOrFollowupResult, UnitRespondResult, match_another_poll_ongoing,
+    match_cache, match_in_voice_with_someone_else, match_in_voice_without_user, match_lavalink,
+    match_not_in_voice, match_not_playing, match_not_users_track, match_poll_loss,
+    match_poll_voided, match_suppressed, match_unrecognised_connection, match_wildcard,
 };
 
 impl super::Context {
@@ -295,19 +291,7 @@ async fn match_error(
         Fe::NotUsersTrack(e) => Ok(match_not_users_track(e, i).await?),
         Fe::AnotherPollOngoing(e) => Ok(match_another_poll_ongoing(e, i).await?),
         Fe::PollLoss(e) => Ok(match_poll_loss(e, i).await?),
-        Fe::PollVoided(_e) => {
-            //: TODO #44 {{{
-
-            //out_upd!(
-            //    format!(
-            //        "{WARNING} This poll has been voided as: {}.",
-            //        e.pretty_display()
-            //    ),
-            //    i
-            //);
-            todo!()
-            //: }}}
-        }
+        Fe::PollVoided(e) => Ok(match_poll_voided(e, i).await?),
         Fe::ConfirmationTimedOut => {
             i.suspf("Confirmation timed out.").await?;
             Ok(())
@@ -415,66 +399,3 @@ async fn match_position_out_of_range(
     i.wrng(message).await?;
     Ok(())
 }
-
-async fn match_another_poll_ongoing(
-    error: &AnotherPollOngoingError,
-    mut i: CtxHead,
-) -> UnitRespondResult {
-    let message_link = error.message.link();
-
-    match error.alternate_vote {
-        Some(AlternateVoteResponse::Casted) => {
-            i.note(format!(
-                "The ongoing poll at {message_link} may resolve this. \
-                Your vote has automatically been casted."
-            ))
-            .await?;
-        }
-        Some(AlternateVoteResponse::DjCasted) => {
-            i.hid(format!(
-                "Superseded the ongoing poll at {message_link} to win."
-            ))
-            .await?;
-        }
-        Some(AlternateVoteResponse::CastDenied) => {
-            i.nope(format!(
-                "The ongoing poll at {message_link} may resolve this, \
-                although you are not eligible to cast a vote there."
-            ))
-            .await?;
-        }
-        Some(AlternateVoteResponse::CastedAlready(casted)) => {
-            i.warn(format!(
-                "The ongoing poll at {message_link} may resolve this, \
-                although you've already casted a vote: **{casted}**."
-            ))
-            .await?;
-        }
-        None => {
-            i.susp(format!(
-                "Another poll is needed to resolve that. \
-                Please resolve the ongoing poll at {message_link} first."
-            ))
-            .await?;
-        }
-    }
-    Ok(())
-}
-
-#[expect(clippy::unused_async)]
-async fn match_poll_loss(error: &PollLossError, _: CtxHead) -> UnitRespondResult {
-    let PollLossError { source: _, kind } = error;
-
-    let _source_txt = match kind {
-        PollLossErrorKind::UnanimousLoss => "",
-        PollLossErrorKind::TimedOut => "Poll timed out: ",
-        PollLossErrorKind::SupersededLossViaDj => "The poll was superseded to lose by a DJ: ",
-    };
-
-    // TODO: #44
-    //out_upd!(
-    //    format!("{PROHIBITED} {source_txt}{}", source.pretty_display()),
-    //    i
-    //);
-    todo!()
-}
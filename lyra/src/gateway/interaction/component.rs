@@ -2,7 +2,7 @@ use tokio::sync::oneshot;
 use twilight_model::application::interaction::InteractionData;
 
 use crate::{
-    command::{model::GuildComponentCtx, require},
+    command::{check, model::GuildComponentCtx, require},
     core::{
         model::{ctx_head::CtxHead, response::Respond},
         statik::component::NowPlayingButtonType,
@@ -15,8 +15,8 @@ use crate::{
 };
 
 use super::{
-    match_cache, match_in_voice_with_someone_else, match_in_voice_without_user, match_lavalink,
-    match_not_in_voice, match_not_playing, match_not_users_track, match_suppressed,
+    match_another_poll_ongoing, match_cache, match_in_voice_without_user, match_lavalink,
+    match_not_in_voice, match_not_playing, match_poll_loss, match_poll_voided, match_suppressed,
     match_unrecognised_connection, match_wildcard,
 };
 
@@ -59,6 +59,7 @@ impl super::Context {
         let Some(now_playing_button) = ctx.take_custom_id_into_now_playing_button_type() else {
             return Ok(());
         };
+        check::resolve_user_dj(&mut ctx).await?;
 
         let Err(error) = execute_controller(ctx, player, player_data, now_playing_button).await
         else {
@@ -96,6 +97,9 @@ async fn execute_controller(
             let mode = crate::component::queue::get_next_repeat_mode(&ctx).await;
             crate::component::queue::repeat(&mut ctx, player_data.clone(), mode, true).await?;
         }
+        NowPlayingButtonType::Autoplay => {
+            crate::component::queue::autoplay(&mut ctx, player_data.clone(), false, true).await?;
+        }
     }
     Ok(())
 }
@@ -119,10 +123,11 @@ async fn match_error(
         )
         .await?),
         Fe::NotInVoice => Ok(match_not_in_voice(i).await?),
-        Fe::InVoiceWithSomeoneElse(e) => Ok(match_in_voice_with_someone_else(e, i).await?),
         Fe::NotPlaying => Ok(match_not_playing(i).await?),
         Fe::UnrecognisedConnection => Ok(match_unrecognised_connection(i).await?),
-        Fe::NotUsersTrack(e) => Ok(match_not_users_track(e, i).await?),
+        Fe::AnotherPollOngoing(e) => Ok(match_another_poll_ongoing(e, i).await?),
+        Fe::PollLoss(e) => Ok(match_poll_loss(e, i).await?),
+        Fe::PollVoided(e) => Ok(match_poll_voided(e, i).await?),
         _ => Ok(match_wildcard(
             error,
             |e| ProcessError::ControllerExecute {
@@ -16,7 +16,7 @@ use twilight_model::{
 use super::model::Process;
 use crate::{
     CommandError,
-    command::common::PlaySource,
+    command::{common::PlaySource, util::MessageLinkAware},
     component::{connection::Join, queue::Play},
     core::{
         http::InteractionClient,
@@ -28,7 +28,13 @@ use crate::{
     },
     error::{
         InVoiceWithSomeoneElse, InVoiceWithoutUser, NotUsersTrack, PrettyErrorDisplay, Suppressed,
-        command::FlattenedError,
+        command::{
+            FlattenedError,
+            check::{
+                AlternateVoteResponse, AnotherPollOngoingError, PollLossError, PollLossErrorKind,
+                PollVoidedError,
+            },
+        },
         core::{RespondError, RespondOrFollowupError},
         gateway::{
             ProcessError, ProcessResult,
@@ -208,3 +214,71 @@ async fn match_not_users_track(error: &NotUsersTrack, mut i: CtxHead) -> UnitRes
     i.nope(error.pretty_display().to_string()).await?;
     Ok(())
 }
+
+async fn match_another_poll_ongoing(
+    error: &AnotherPollOngoingError,
+    mut i: CtxHead,
+) -> UnitRespondResult {
+    let message_link = error.message.link();
+
+    match error.alternate_vote {
+        Some(AlternateVoteResponse::Casted) => {
+            i.note(format!(
+                "The ongoing poll at {message_link} may resolve this. \
+                Your vote has automatically been casted."
+            ))
+            .await?;
+        }
+        Some(AlternateVoteResponse::DjCasted) => {
+            i.hid(format!(
+                "Superseded the ongoing poll at {message_link} to win."
+            ))
+            .await?;
+        }
+        Some(AlternateVoteResponse::CastDenied) => {
+            i.nope(format!(
+                "The ongoing poll at {message_link} may resolve this, \
+                although you are not eligible to cast a vote there."
+            ))
+            .await?;
+        }
+        Some(AlternateVoteResponse::CastedAlready(casted)) => {
+            i.warn(format!(
+                "The ongoing poll at {message_link} may resolve this, \
+                although you've already casted a vote: **{casted}**."
+            ))
+            .await?;
+        }
+        None => {
+            i.susp(format!(
+                "Another poll is needed to resolve that. \
+                Please resolve the ongoing poll at {message_link} first."
+            ))
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+async fn match_poll_loss(error: &PollLossError, mut i: CtxHead) -> UnitRespondOrFollowupResult {
+    let PollLossError { source, kind } = error;
+
+    let source_txt = match kind {
+        PollLossErrorKind::UnanimousLoss => "",
+        PollLossErrorKind::TimedOut => "Poll timed out: ",
+        PollLossErrorKind::SupersededLossViaDj => "The poll was superseded to lose by a DJ: ",
+    };
+
+    i.nope_f(format!("{source_txt}{}", source.pretty_display()))
+        .await?;
+    Ok(())
+}
+
+async fn match_poll_voided(error: &PollVoidedError, mut i: CtxHead) -> UnitRespondOrFollowupResult {
+    i.warn_f(format!(
+        "This poll has been voided as {}.",
+        error.pretty_display()
+    ))
+    .await?;
+    Ok(())
+}
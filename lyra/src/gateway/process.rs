@@ -15,7 +15,11 @@ pub async fn process(
 ) -> ProcessResult {
     match event {
         Event::Ready(ref e) => bot.as_ready_context(e, shard_id).process().await,
-        Event::GuildCreate(ref e) => bot.as_guild_create_context(e, shard_id).process().await,
+        Event::GuildCreate(ref e) => {
+            bot.as_guild_create_context(e, shard_id, sender)
+                .process()
+                .await
+        }
         Event::GuildDelete(ref e) => bot.as_guild_delete_context(e, shard_id).process().await,
         Event::InteractionCreate(e) => {
             bot.into_interaction_create_context(e, latency, sender)
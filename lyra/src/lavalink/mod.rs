@@ -7,11 +7,15 @@ mod track;
 pub use self::{
     model::{
         ClientAndGuildIdAware, ClientAware, ClientData, Connection, ConnectionHead,
-        CorrectPlaylistInfo, CorrectTrackInfo, DelegateMethods, Event, EventRecvResult,
-        IndexerType, Lavalink, NowPlayingData, OwnedPlayerData, Pitch, PlayerDataRead,
-        PlayerDataWrite, PlaylistAwareTrackData, PlaylistMetadata, Queue, QueueItem, RepeatMode,
-        UnwrappedData, UnwrappedPlayerInfoUri, make_playlist_aware, wait_for_with,
+        CorrectPlaylistInfo, CorrectTrackInfo, DelegateMethods, Event, EventRecvResult, Fairness,
+        HistoryEntry, IndexerType, Lavalink, NodeConfig, NowPlayingData, OwnedPlayerData, Pitch,
+        PlayerDataRead, PlayerDataWrite, PlaylistAwareTrackData, PlaylistMetadata, Queue,
+        QueueItem, RecoveryAttempt, RecoveryStep, RepeatMode, SeenTracks, Snapshot, UnwrappedData,
+        UnwrappedPlayerInfoUri, make_playlist_aware, select_node, wait_for_with,
+    },
+    plugin::{
+        lava_search::{LoadSearch, SearchResult},
+        lava_src::{PluginInfo, PluginPlaylistInfo},
     },
-    plugin::lava_src::{PluginInfo, PluginPlaylistInfo},
     process::handlers,
 };
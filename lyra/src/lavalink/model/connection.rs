@@ -154,6 +154,9 @@ impl From<AlternateVoteCastUserId> for Id<UserMarker> {
 pub enum Event {
     QueueClear,
     QueueRepeat,
+    QueueAdvance,
+    QueueReindex,
+    PlayerPause,
     AlternateVoteCast(AlternateVoteCastUserId),
     AlternateVoteDjCast,
     AlternateVoteCastedAlready(crate::command::poll::Vote),
@@ -0,0 +1,37 @@
+use std::collections::HashSet;
+
+use lavalink_rs::model::track::TrackInfo;
+
+/// The tracks that have been seen so far, for detecting duplicates of them.
+///
+/// Two tracks are considered duplicates if they have the same identifier from the same source,
+/// the same URI, or the same ISRC.
+#[derive(Default)]
+pub struct SeenTracks {
+    identifiers: HashSet<(String, String)>,
+    uris: HashSet<String>,
+    isrcs: HashSet<String>,
+}
+
+impl SeenTracks {
+    pub fn contains(&self, info: &TrackInfo) -> bool {
+        self.identifiers
+            .contains(&(info.source_name.clone(), info.identifier.clone()))
+            || info.uri.as_ref().is_some_and(|u| self.uris.contains(u))
+            || info.isrc.as_ref().is_some_and(|i| self.isrcs.contains(i))
+    }
+
+    /// Marks `info` as seen, returning whether a duplicate of it has already been seen.
+    pub fn insert(&mut self, info: &TrackInfo) -> bool {
+        let seen = self.contains(info);
+        self.identifiers
+            .insert((info.source_name.clone(), info.identifier.clone()));
+        if let Some(ref uri) = info.uri {
+            self.uris.insert(uri.clone());
+        }
+        if let Some(ref isrc) = info.isrc {
+            self.isrcs.insert(isrc.clone());
+        }
+        seen
+    }
+}
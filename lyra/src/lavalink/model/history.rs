@@ -0,0 +1,310 @@
+use std::time::Duration;
+
+use sqlx::{Pool, Postgres};
+use twilight_model::id::{
+    Id,
+    marker::{GuildMarker, UserMarker},
+};
+
+use crate::core::konst::misc::PLAY_HISTORY_LIMIT;
+
+use super::{CorrectTrackInfo, QueueItem, RawPlayerData};
+
+/// A record of a track that has been played in a guild.
+pub struct HistoryEntry {
+    id: i64,
+    encoded: String,
+    title: String,
+    uri: Option<String>,
+    length: Duration,
+    requester: Option<Id<UserMarker>>,
+    started: Duration,
+    skipped: bool,
+}
+
+impl HistoryEntry {
+    fn from_row(row: HistoryRow) -> Self {
+        Self {
+            id: row.id,
+            encoded: row.encoded,
+            title: row.title,
+            uri: row.uri,
+            length: Duration::from_millis(row.length.max(0).cast_unsigned()),
+            requester: Id::new_checked(row.requester.cast_unsigned()),
+            started: Duration::from_millis(row.started.max(0).cast_unsigned()),
+            skipped: row.skipped,
+        }
+    }
+
+    pub const fn id(&self) -> i64 {
+        self.id
+    }
+
+    pub fn encoded(&self) -> &str {
+        &self.encoded
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn uri(&self) -> Option<&str> {
+        self.uri.as_deref()
+    }
+
+    pub const fn length(&self) -> Duration {
+        self.length
+    }
+
+    pub const fn requester(&self) -> Option<Id<UserMarker>> {
+        self.requester
+    }
+
+    /// When this track started playing, as a duration since the Unix epoch.
+    pub const fn started(&self) -> Duration {
+        self.started
+    }
+
+    pub const fn skipped(&self) -> bool {
+        self.skipped
+    }
+
+    /// Records `item` as having just started playing, discarding the oldest entries of the guild
+    /// beyond [`PLAY_HISTORY_LIMIT`], returning the ID of the recorded entry.
+    pub async fn record(
+        db: &Pool<Postgres>,
+        guild_id: Id<GuildMarker>,
+        item: &QueueItem,
+    ) -> Result<i64, sqlx::Error> {
+        let guild = guild_id.get().cast_signed();
+        let track = item.data();
+        let started = i64::try_from(lyra_ext::unix_time().as_millis()).unwrap_or(i64::MAX);
+
+        let id = sqlx::query_scalar!(
+            "INSERT INTO play_history
+                (guild, encoded, title, author, uri, length, requester, started)
+            VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id;",
+            guild,
+            track.encoded,
+            track.info.corrected_title(),
+            track.info.corrected_author(),
+            track.info.uri,
+            track.info.length.cast_signed(),
+            item.requester().get().cast_signed(),
+            started,
+        )
+        .fetch_one(db)
+        .await?;
+
+        sqlx::query!(
+            "DELETE FROM play_history
+            WHERE guild = $1 AND id NOT IN (
+                SELECT id FROM play_history WHERE guild = $1 ORDER BY id DESC LIMIT $2
+            );",
+            guild,
+            PLAY_HISTORY_LIMIT,
+        )
+        .execute(db)
+        .await?;
+        Ok(id)
+    }
+
+    /// Marks the entry with ID `id` as skipped.
+    pub async fn mark_skipped(db: &Pool<Postgres>, id: i64) -> Result<(), sqlx::Error> {
+        sqlx::query!("UPDATE play_history SET skipped = true WHERE id = $1;", id)
+            .execute(db)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn count(
+        db: &Pool<Postgres>,
+        guild_id: Id<GuildMarker>,
+    ) -> Result<usize, sqlx::Error> {
+        let count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!" FROM play_history WHERE guild = $1;"#,
+            guild_id.get().cast_signed(),
+        )
+        .fetch_one(db)
+        .await?;
+        Ok(usize::try_from(count).unwrap_or_default())
+    }
+
+    /// Fetches at most `limit` entries of the guild, most recent first, skipping the first
+    /// `offset` entries.
+    pub async fn fetch(
+        db: &Pool<Postgres>,
+        guild_id: Id<GuildMarker>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let rows = sqlx::query_as!(
+            HistoryRow,
+            "SELECT id, encoded, title, uri, length, requester, started, skipped
+            FROM play_history
+            WHERE guild = $1
+            ORDER BY id DESC
+            OFFSET $2 LIMIT $3;",
+            guild_id.get().cast_signed(),
+            i64::try_from(offset).unwrap_or(i64::MAX),
+            i64::try_from(limit).unwrap_or(i64::MAX),
+        )
+        .fetch_all(db)
+        .await?;
+        Ok(rows.into_iter().map(Self::from_row).collect())
+    }
+
+    /// Searches for at most `limit` entries of the guild whose author and title contain every
+    /// word of `query`, excluding the entries with the given `excluded` IDs, most recent first.
+    ///
+    /// Each entry is returned along with its number in the history, with `1` being the most
+    /// recent entry.
+    pub async fn search(
+        db: &Pool<Postgres>,
+        guild_id: Id<GuildMarker>,
+        query: &str,
+        excluded: &[i64],
+        limit: usize,
+    ) -> Result<Vec<(usize, Self)>, sqlx::Error> {
+        let patterns = query
+            .split_whitespace()
+            .map(|word| {
+                let escaped = word
+                    .replace('\\', "\\\\")
+                    .replace('%', "\\%")
+                    .replace('_', "\\_");
+                format!("%{escaped}%")
+            })
+            .collect::<Vec<_>>();
+
+        let rows = sqlx::query!(
+            r#"SELECT number as "number!", id, encoded, title, uri, length, requester, started, skipped
+            FROM (
+                SELECT row_number() OVER (ORDER BY id DESC) as number, *
+                FROM play_history
+                WHERE guild = $1
+            ) numbered
+            WHERE id <> ALL($2) AND (author || ' ' || title) ILIKE ALL($3)
+            ORDER BY id DESC
+            LIMIT $4;"#,
+            guild_id.get().cast_signed(),
+            excluded,
+            &patterns,
+            i64::try_from(limit).unwrap_or(i64::MAX),
+        )
+        .fetch_all(db)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| {
+                let entry = Self::from_row(HistoryRow {
+                    id: r.id,
+                    encoded: r.encoded,
+                    title: r.title,
+                    uri: r.uri,
+                    length: r.length,
+                    requester: r.requester,
+                    started: r.started,
+                    skipped: r.skipped,
+                });
+                (usize::try_from(r.number).unwrap_or_default(), entry)
+            })
+            .collect())
+    }
+
+    /// Fetches the entries of the guild with the given `ids`, in the order of the given `ids`.
+    pub async fn fetch_by_ids(
+        db: &Pool<Postgres>,
+        guild_id: Id<GuildMarker>,
+        ids: &[i64],
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let rows = sqlx::query_as!(
+            HistoryRow,
+            "SELECT id, encoded, title, uri, length, requester, started, skipped
+            FROM play_history
+            WHERE guild = $1 AND id = ANY($2);",
+            guild_id.get().cast_signed(),
+            ids,
+        )
+        .fetch_all(db)
+        .await?;
+
+        let mut entries = rows.into_iter().map(Self::from_row).collect::<Vec<_>>();
+        entries.sort_by_key(|e| ids.iter().position(|&i| i == e.id));
+        Ok(entries)
+    }
+}
+
+struct HistoryRow {
+    id: i64,
+    encoded: String,
+    title: String,
+    uri: Option<String>,
+    length: i64,
+    requester: i64,
+    started: i64,
+    skipped: bool,
+}
+
+/// The history entry recorded for the queue item currently playing.
+pub(super) struct RecordedPlay {
+    id: i64,
+    index: usize,
+    encoded: String,
+}
+
+impl RawPlayerData {
+    /// Records the current track in the play history as having just started playing.
+    ///
+    /// If the current track is the same queue item as the one last recorded, such as when it is
+    /// being retried or resumed, it is not recorded again. Failures are only logged, as they
+    /// should not stop the track from playing.
+    pub async fn record_current_play(&mut self, db: &Pool<Postgres>, guild_id: Id<GuildMarker>) {
+        let Some(item) = self.queue.current() else {
+            return;
+        };
+        let index = self.queue.index();
+        let encoded = &item.data().encoded;
+        if self
+            .recorded_play
+            .as_ref()
+            .is_some_and(|p| p.index == index && p.encoded == *encoded)
+        {
+            return;
+        }
+
+        match HistoryEntry::record(db, guild_id, item).await {
+            Ok(id) => {
+                self.recorded_play = Some(RecordedPlay {
+                    id,
+                    index,
+                    encoded: encoded.clone(),
+                });
+            }
+            Err(error) => {
+                tracing::warn!(guild_id = guild_id.get(), %error, "recording play history failed");
+            }
+        }
+    }
+
+    /// Ends the play recorded via [`Self::record_current_play`], so that the next track to start
+    /// is recorded anew even if it is the same queue item, returning the ID of its entry.
+    #[inline]
+    pub fn end_recorded_play(&mut self) -> Option<i64> {
+        self.recorded_play.take().map(|p| p.id)
+    }
+
+    /// Ends the recorded play as with [`Self::end_recorded_play`], marking it as skipped.
+    /// Failures are only logged, as they should not stop the track from being skipped.
+    pub async fn skip_recorded_play(&mut self, db: &Pool<Postgres>) {
+        let Some(id) = self.end_recorded_play() else {
+            return;
+        };
+        if let Err(error) = HistoryEntry::mark_skipped(db, id).await {
+            tracing::warn!(id, %error, "marking play history entry as skipped failed");
+        }
+    }
+}
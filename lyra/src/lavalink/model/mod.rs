@@ -1,17 +1,22 @@
 mod connection;
 mod correct_info;
 mod delegate;
+mod duplicate;
+mod history;
+mod node;
 mod now_playing;
 mod pitch;
 mod playlist;
 mod queue;
 mod queue_indexer;
+mod recovery;
+mod snapshot;
 
 use std::{
     env,
     num::{NonZeroU16, NonZeroUsize},
-    sync::Arc,
-    time::Duration,
+    sync::{Arc, Mutex, OnceLock, atomic::Ordering},
+    time::{Duration, Instant},
 };
 
 use connection::{Awaitable, ConnectionHandle, ConnectionsActor, Instruction};
@@ -32,7 +37,11 @@ use twilight_model::id::{
 
 use crate::{
     command::util::is_message_at_bottom,
-    core::model::{CacheAware, DatabaseAware, HttpAware, OwnedHttpAware},
+    component::{config::GuildConfigCache, tuning::ActiveEffect},
+    core::{
+        konst::misc::DEFAULT_LAVALINK_SESSION_RESUME_TIMEOUT,
+        model::{CacheAware, DatabaseAware, HttpAware, OwnedHttpAware},
+    },
     error::{
         UnrecognisedConnection,
         lavalink::{NewNowPlayingMessageError, UpdateNowPlayingMessageError},
@@ -44,13 +53,18 @@ pub use self::{
     connection::{Connection, ConnectionHead, Event, EventRecvResult, wait_for_with},
     correct_info::{CorrectPlaylistInfo, CorrectTrackInfo},
     delegate::DelegateMethods,
+    duplicate::SeenTracks,
+    history::HistoryEntry,
+    node::{NodeConfig, players_on, resume_or_delete_player, resume_players_on, select_node},
     now_playing::{
         Data as NowPlayingData, Message as NowPlayingMessage, Update as NowPlayingDataUpdate,
     },
     pitch::Pitch,
     playlist::{PlaylistAwareTrackData, PlaylistMetadata, make_playlist_aware},
     queue::{Item as QueueItem, Queue, RepeatMode},
-    queue_indexer::IndexerType,
+    queue_indexer::{Fairness, IndexerType},
+    recovery::{RecoveryAttempt, RecoveryStep},
+    snapshot::Snapshot,
 };
 
 pub type PlayerData = RwLock<RawPlayerData>;
@@ -117,6 +131,13 @@ pub struct RawPlayerData {
     track_timestamp: TrackTimestamp,
     text_channel_id: Id<ChannelMarker>,
     now_playing_message: Option<NowPlayingMessage>,
+    snapshot_outdated: bool,
+    snapshot_queue_outdated: bool,
+    saved_snapshot: Option<snapshot::SavedSnapshot>,
+    pending_resume: Option<snapshot::PendingResume>,
+    stuck_recovery: Option<recovery::StuckRecovery>,
+    effect: Option<ActiveEffect>,
+    recorded_play: Option<history::RecordedPlay>,
 }
 
 pub type UpdateNowPlayingMessageResult = Result<(), UpdateNowPlayingMessageError>;
@@ -130,6 +151,13 @@ impl RawPlayerData {
             queue: Queue::new(),
             track_timestamp: TrackTimestamp::new(),
             now_playing_message: None,
+            snapshot_outdated: true,
+            snapshot_queue_outdated: true,
+            saved_snapshot: None,
+            pending_resume: None,
+            stuck_recovery: None,
+            effect: None,
+            recorded_play: None,
         }
     }
 
@@ -137,13 +165,9 @@ impl RawPlayerData {
         &self.queue
     }
 
-    #[inline]
-    pub fn reset_track_timestamp(&mut self) {
-        self.track_timestamp.reset();
-    }
-
     #[inline]
     pub const fn queue_mut(&mut self) -> &mut Queue {
+        self.mark_snapshot_queue_outdated();
         &mut self.queue
     }
 
@@ -154,13 +178,31 @@ impl RawPlayerData {
     #[inline]
     pub const fn set_volume(&mut self, volume: NonZeroU16) {
         self.volume = volume;
+        self.mark_snapshot_outdated();
     }
 
     #[inline]
     pub const fn pitch_mut(&mut self) -> &mut Pitch {
+        self.mark_snapshot_outdated();
         &mut self.pitch
     }
 
+    #[inline]
+    pub fn set_effect(&mut self, effect: ActiveEffect) {
+        self.effect = Some(effect);
+        self.mark_snapshot_outdated();
+    }
+
+    pub const fn effect(&self) -> Option<&ActiveEffect> {
+        self.effect.as_ref()
+    }
+
+    #[inline]
+    pub const fn take_effect(&mut self) -> Option<ActiveEffect> {
+        self.mark_snapshot_outdated();
+        self.effect.take()
+    }
+
     pub const fn paused(&self) -> bool {
         self.track_timestamp.paused()
     }
@@ -173,11 +215,13 @@ impl RawPlayerData {
     #[inline]
     pub fn set_pause(&mut self, state: bool) {
         self.track_timestamp.set_pause(state);
+        self.mark_snapshot_outdated();
     }
 
     #[inline]
     pub fn seek_to(&mut self, timestamp: Duration) {
         self.track_timestamp.seek_to(timestamp);
+        self.mark_snapshot_outdated();
     }
 
     #[inline]
@@ -188,6 +232,7 @@ impl RawPlayerData {
     #[inline]
     pub fn set_speed(&mut self, multiplier: f64) {
         self.track_timestamp.set_speed(multiplier);
+        self.mark_snapshot_outdated();
     }
 
     pub const fn text_channel_id(&self) -> Id<ChannelMarker> {
@@ -196,6 +241,7 @@ impl RawPlayerData {
 
     pub const fn set_text_channel_id(&mut self, text_channel_id: Id<ChannelMarker>) {
         self.text_channel_id = text_channel_id;
+        self.mark_snapshot_outdated();
     }
 
     pub const fn now_playing_message_id(&self) -> Option<Id<MessageMarker>> {
@@ -307,6 +353,16 @@ impl RawPlayerData {
             .await
     }
 
+    #[inline]
+    pub async fn set_autoplay_then_update_and_apply_to_now_playing(
+        &mut self,
+        autoplay: bool,
+    ) -> UpdateNowPlayingMessageResult {
+        self.queue_mut().set_autoplay(autoplay);
+        self.update_and_apply_now_playing_data(NowPlayingDataUpdate::Autoplay(autoplay))
+            .await
+    }
+
     #[inline]
     pub async fn set_indexer_then_update_and_apply_to_now_playing(
         &mut self,
@@ -370,6 +426,8 @@ impl RawPlayerData {
 pub struct Lavalink {
     inner: LavalinkClient,
     sender: Option<mpsc::UnboundedSender<Instruction>>,
+    /// When each node went down, if it is currently down.
+    node_down_since: Box<[Mutex<Option<Instant>>]>,
 }
 
 impl Lavalink {
@@ -379,6 +437,7 @@ impl Lavalink {
 
     pub fn start(&mut self) {
         let (sender, receiver) = mpsc::unbounded_channel();
+        let _ = self.inner.data_unwrapped().connections.set(sender.clone());
         self.sender = Some(sender);
         let mut actor = ConnectionsActor::new(receiver);
         tokio::spawn(async move {
@@ -427,18 +486,21 @@ impl Lavalink {
     }
 
     pub fn iter_player_data(&self) -> impl Iterator<Item = OwnedPlayerData> + use<'_> {
-        self.inner
-            .players
-            .iter()
-            .filter_map(|p| p.value().0.load().as_ref().map(|ctx| ctx.data_unwrapped()))
+        self.iter_players().map(|ctx| ctx.data_unwrapped())
     }
 }
 
 impl From<LavalinkClient> for Lavalink {
     fn from(value: LavalinkClient) -> Self {
+        let node_down_since = value
+            .nodes
+            .iter()
+            .map(|n| Mutex::new((!n.is_running.load(Ordering::SeqCst)).then(Instant::now)))
+            .collect();
         let mut lava = Self {
             inner: value,
             sender: None,
+            node_down_since,
         };
         lava.start();
         lava
@@ -486,7 +548,11 @@ pub struct ClientData {
     http: Arc<Client>,
     cache: Arc<InMemoryCache>,
     artwork_cache: ArtworkCache,
+    guild_configs: GuildConfigCache,
+    node_regions: Box<[Box<[String]>]>,
+    session_resume_timeout: u32,
     oauth_enabled: bool,
+    connections: OnceLock<mpsc::UnboundedSender<Instruction>>,
 }
 
 impl HttpAware for ClientData {
@@ -514,24 +580,64 @@ impl DatabaseAware for ClientData {
 }
 
 impl ClientData {
-    pub fn new(http: Arc<Client>, cache: Arc<InMemoryCache>, db: Pool<Postgres>) -> Self {
+    pub fn new(
+        http: Arc<Client>,
+        cache: Arc<InMemoryCache>,
+        db: Pool<Postgres>,
+        guild_configs: GuildConfigCache,
+        node_regions: Box<[Box<[String]>]>,
+    ) -> Self {
         Self {
             http,
             cache,
             db,
             artwork_cache: Cache::new(10_000),
+            guild_configs,
+            node_regions,
 
-            // we can afford to parse the env var without memoisation, as this function
+            // we can afford to parse the env vars without memoisation, as this function
             // will only be called once when the lavalink client is initialised.
+            session_resume_timeout: env::var("LAVALINK_SESSION_RESUME_TIMEOUT")
+                .ok()
+                .and_then(|x| x.parse().ok())
+                .unwrap_or(DEFAULT_LAVALINK_SESSION_RESUME_TIMEOUT),
             oauth_enabled: env::var("PLUGINS_YOUTUBE_OAUTH_ENABLED")
                 .is_ok_and(|x| x.parse::<bool>().is_ok_and(|y| y)),
+            connections: OnceLock::new(),
         }
     }
 
+    /// Dispatches `event` to all subscribers of the connection in the guild, if there is one.
+    ///
+    /// This is for where only the Lavalink client is available, such as in Lavalink event
+    /// handlers. Otherwise, [`ConnectionHandle::dispatch`] should be used instead.
+    pub fn dispatch_to(&self, guild_id: Id<GuildMarker>, event: Event) {
+        let Some(sender) = self.connections.get() else {
+            return;
+        };
+        let (response, _) = oneshot::channel();
+        let _ = sender.send(Instruction::Dispatch(guild_id, event, response));
+    }
+
     pub const fn artwork_cache(&self) -> &ArtworkCache {
         &self.artwork_cache
     }
 
+    pub const fn guild_configs(&self) -> &GuildConfigCache {
+        &self.guild_configs
+    }
+
+    /// The voice regions the node with ID `node_id` is preferred for.
+    pub fn node_regions(&self, node_id: usize) -> &[String] {
+        self.node_regions.get(node_id).map_or(&[], |r| r)
+    }
+
+    /// How long in seconds a Lavalink node keeps a session's players after its websocket
+    /// disconnects, with `0` disabling session resuming.
+    pub const fn session_resume_timeout(&self) -> u32 {
+        self.session_resume_timeout
+    }
+
     pub const fn oauth_enabled(&self) -> bool {
         self.oauth_enabled
     }
@@ -0,0 +1,228 @@
+use std::{
+    sync::{Arc, atomic::Ordering},
+    time::{Duration, Instant},
+};
+
+use lavalink_rs::{
+    client::LavalinkClient, model::BoxFuture, node::Node, player_context::PlayerContext,
+    prelude::GuildId,
+};
+use reqwest::Url;
+use twilight_model::id::Id;
+
+use crate::{core::model::CacheAware, error::lavalink::ProcessResult};
+
+use super::{Lavalink, UnwrappedData};
+
+/// A Lavalink node to connect to, along with the voice regions it is preferred for.
+pub struct NodeConfig {
+    hostname: String,
+    password: String,
+    is_ssl: bool,
+    regions: Box<[String]>,
+}
+
+impl NodeConfig {
+    pub fn new(hostname: String, password: String) -> Self {
+        Self {
+            hostname,
+            password,
+            is_ssl: false,
+            regions: Box::new([]),
+        }
+    }
+
+    /// Parses a node from a URL like `ws://:password@host:2333/?regions=us-east,us-central`,
+    /// using `wss` for nodes behind SSL.
+    pub fn parse(url: &str) -> Option<Self> {
+        let url = Url::parse(url).ok()?;
+        let is_ssl = match url.scheme() {
+            "ws" => false,
+            "wss" => true,
+            _ => return None,
+        };
+        let hostname = format!("{}:{}", url.host_str()?, url.port_or_known_default()?);
+        let password = urlencoding::decode(url.password()?).ok()?.into_owned();
+        let regions = url
+            .query_pairs()
+            .filter(|(key, _)| key == "regions")
+            .flat_map(|(_, value)| {
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|r| !r.is_empty())
+                    .map(String::from)
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        Some(Self {
+            hostname,
+            password,
+            is_ssl,
+            regions,
+        })
+    }
+
+    pub fn into_builder_and_regions(
+        self,
+        user_id: impl Into<lavalink_rs::model::UserId>,
+    ) -> (lavalink_rs::node::NodeBuilder, Box<[String]>) {
+        let builder = lavalink_rs::node::NodeBuilder {
+            hostname: self.hostname,
+            password: self.password,
+            is_ssl: self.is_ssl,
+            user_id: user_id.into(),
+            ..Default::default()
+        };
+        (builder, self.regions)
+    }
+}
+
+fn is_healthy(node: &Node) -> bool {
+    node.is_running.load(Ordering::SeqCst)
+}
+
+fn player_count(lavalink: &LavalinkClient, node: &Node) -> usize {
+    lavalink
+        .players
+        .iter()
+        .filter(|p| p.value().1.id == node.id)
+        .count()
+}
+
+/// The voice region of the channel the bot is connected to in a guild, if known.
+fn voice_region(lavalink: &LavalinkClient, guild_id: GuildId) -> Option<String> {
+    let data = lavalink.data_unwrapped();
+    let cache = data.cache();
+    let user_id = cache.current_user()?.id;
+    let channel_id = cache
+        .voice_state(user_id, Id::new(guild_id.0))?
+        .channel_id();
+    cache.channel(channel_id)?.rtc_region.clone()
+}
+
+/// Selects the node a new player should be created on.
+///
+/// Healthy nodes are preferred over unhealthy ones, then nodes tagged with the voice region of the
+/// player's channel over other nodes, then nodes with the fewest players.
+pub fn select_node(lavalink: &LavalinkClient, guild_id: GuildId) -> BoxFuture<'_, Arc<Node>> {
+    Box::pin(async move {
+        let healthy = lavalink
+            .nodes
+            .iter()
+            .filter(|n| is_healthy(n))
+            .collect::<Vec<_>>();
+        let candidates = if healthy.is_empty() {
+            lavalink.nodes.iter().collect()
+        } else {
+            healthy
+        };
+
+        let data = lavalink.data_unwrapped();
+        let regional = voice_region(lavalink, guild_id)
+            .map(|region| {
+                candidates
+                    .iter()
+                    .copied()
+                    .filter(|n| data.node_regions(n.id).contains(&region))
+                    .collect::<Vec<_>>()
+            })
+            .filter(|nodes| !nodes.is_empty())
+            .unwrap_or(candidates);
+
+        regional
+            .into_iter()
+            .min_by_key(|n| player_count(lavalink, n))
+            .cloned()
+            .expect("there must be at least one node")
+    })
+}
+
+/// Recreates a player on whichever node it is assigned to next, keeping all of its data intact and
+/// resuming its current track from where it was last at.
+pub async fn resume_player(lavalink: &LavalinkClient, player: PlayerContext) -> ProcessResult {
+    let guild_id = player.guild_id;
+    let info = player.get_player().await?;
+    let data = player.data_unwrapped();
+
+    // the previous node will likely no longer have the player, so deleting it will very
+    // likely fail, but the stale player context is still removed regardless.
+    let _ = lavalink.delete_player(guild_id).await;
+
+    let player = lavalink
+        .create_player_context_with_data(guild_id, info.voice, data.clone())
+        .await?;
+    data.write().await.resume_on(&player, info.filters).await?;
+
+    tracing::info!("guild {} resumed player", guild_id.0);
+    Ok(())
+}
+
+/// Resumes `player`, deleting it if it could not be resumed.
+pub async fn resume_or_delete_player(lavalink: &LavalinkClient, player: PlayerContext) {
+    let guild_id = player.guild_id;
+    if let Err(error) = resume_player(lavalink, player).await {
+        tracing::warn!(guild_id = guild_id.0, ?error, "resuming player failed");
+        let _ = lavalink.delete_player(guild_id).await;
+    }
+}
+
+/// All players on the node with ID `node_id`, collected so that no locks on them are held across
+/// awaits.
+pub fn players_on(lavalink: &LavalinkClient, node_id: usize) -> Vec<PlayerContext> {
+    lavalink
+        .players
+        .iter()
+        .filter(|p| p.value().1.id == node_id)
+        .filter_map(|p| p.value().0.load().as_deref().cloned())
+        .collect()
+}
+
+/// Resumes all players on the node with ID `node_id`, deleting the ones that could not be resumed.
+pub async fn resume_players_on(lavalink: &LavalinkClient, node_id: usize) {
+    for player in players_on(lavalink, node_id) {
+        resume_or_delete_player(lavalink, player).await;
+    }
+}
+
+impl Lavalink {
+    /// Logs every node that went down or came back up since this was last called, then migrates
+    /// the players of nodes that have been down for longer than the session resume timeout to
+    /// healthy nodes, if there are any.
+    ///
+    /// Until the timeout has passed, a node that comes back up can still resume its session with
+    /// its players intact, so they are left on it until then.
+    pub async fn check_node_health(&self) {
+        let timeout =
+            Duration::from_secs(self.inner.data_unwrapped().session_resume_timeout().into());
+        let mut timed_out = Vec::new();
+        for (node, down_since) in self.inner.nodes.iter().zip(&self.node_down_since) {
+            let healthy = is_healthy(node);
+            let mut down_since = down_since
+                .lock()
+                .expect("node health lock must not be poisoned");
+            match (*down_since, healthy) {
+                (None, false) => {
+                    tracing::warn!(node = node.id, "node went down");
+                    *down_since = Some(Instant::now());
+                }
+                (Some(_), true) => {
+                    tracing::info!(node = node.id, "node came back up");
+                    *down_since = None;
+                }
+                (Some(since), false) if since.elapsed() >= timeout => timed_out.push(node.id),
+                _ => {}
+            }
+        }
+
+        // without a healthy node to take them over, the players are left for the ready hook to
+        // resume once their node comes back up.
+        if !self.inner.nodes.iter().any(|n| is_healthy(n)) {
+            return;
+        }
+        for node_id in timed_out {
+            resume_players_on(&self.inner, node_id).await;
+        }
+    }
+}
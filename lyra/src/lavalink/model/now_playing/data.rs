@@ -68,6 +68,7 @@ pub(super) struct Queue {
     position: NonZeroUsize,
     indexer: IndexerType,
     repeat_mode: RepeatMode,
+    autoplay: bool,
 }
 
 impl Queue {
@@ -102,6 +103,14 @@ impl Queue {
     pub(super) const fn set_repeat_mode(&mut self, repeat_mode: RepeatMode) {
         self.repeat_mode = repeat_mode;
     }
+
+    pub(super) const fn autoplay(&self) -> bool {
+        self.autoplay
+    }
+
+    pub(super) const fn set_autoplay(&mut self, autoplay: bool) {
+        self.autoplay = autoplay;
+    }
 }
 
 pub(super) struct Requester {
@@ -278,6 +287,7 @@ impl Data {
                 position: queue.position(),
                 indexer: queue.indexer_type(),
                 repeat_mode: queue.repeat_mode(),
+                autoplay: queue.autoplay(),
             },
             requester: Requester {
                 name: requester_name,
@@ -32,7 +32,7 @@ use crate::{
             BuildNowPlayingEmbedError, NewNowPlayingMessageError, UpdateNowPlayingMessageError,
         },
     },
-    lavalink::{IndexerType, RepeatMode},
+    lavalink::{Fairness, IndexerType, RepeatMode},
 };
 
 use super::{Data, data::Playlist};
@@ -41,6 +41,7 @@ use super::{Data, data::Playlist};
 pub enum Update {
     Indexer(IndexerType),
     Repeat(RepeatMode),
+    Autoplay(bool),
     Paused(bool),
     QueueLen(usize),
     QueuePosition(NonZeroUsize),
@@ -56,7 +57,10 @@ struct DurationLeft {
 impl From<&Data> for DurationLeft {
     fn from(value: &Data) -> Self {
         Self {
-            inner: (value.duration - value.timestamp).div_f64(value.speed),
+            inner: value
+                .duration
+                .saturating_sub(value.timestamp)
+                .div_f64(value.speed),
             total: value.duration,
             paused: value.paused,
         }
@@ -67,7 +71,10 @@ impl std::fmt::Display for DurationLeft {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.paused {
             f.write_char('`')?;
-            (self.total - self.inner).pretty_display().fmt(f)?;
+            self.total
+                .saturating_sub(self.inner)
+                .pretty_display()
+                .fmt(f)?;
             f.write_char('`')
         } else {
             let unix = (lyra_ext::unix_time() + self.inner).as_secs();
@@ -132,6 +139,14 @@ impl std::fmt::Display for Description<'_> {
         DurationLeft::from(data).fmt(f)?;
         f.write_str(" / ")?;
         data.duration.pretty_display().fmt(f)?;
+        match data.queue().indexer().fairness() {
+            Some(Fairness::TrackCount) => f.write_str(" ⚖️")?,
+            Some(Fairness::PlayTime) => f.write_str(" ⚖️⏱️")?,
+            None => {}
+        }
+        if data.queue().autoplay() {
+            f.write_str(" 📻")?;
+        }
         if let Some(p) = data.preview()
             && (p.is_preview || p.url().is_some())
         {
@@ -178,7 +193,7 @@ impl Message {
             .create_message(channel_id)
             .content(msg.build_content())
             .embeds(&[msg.build_embeds()?])
-            .components(&[msg.build_components().await?])
+            .components(&msg.build_components().await?)
             .await?;
         msg.id = req.model().await?.id; // ...to be updated later here.
 
@@ -197,6 +212,7 @@ impl Message {
         match update {
             Update::Indexer(idx) => self.data.queue_mut().set_indexer(idx),
             Update::Repeat(mode) => self.data.queue_mut().set_repeat_mode(mode),
+            Update::Autoplay(autoplay) => self.data.queue_mut().set_autoplay(autoplay),
             Update::Paused(paused) => self.data.paused = paused,
             Update::QueueLen(i) => self.data.queue_mut().set_len(i),
             Update::QueuePosition(i) => self.data.queue_mut().set_position(i),
@@ -217,7 +233,7 @@ impl Message {
             .update_message(self.channel_id, self.id)
             .content(Some(self.build_content()))
             .embeds(Some(&[self.build_embeds()?]))
-            .components(Some(&[self.build_components().await?]))
+            .components(Some(&self.build_components().await?))
             .await?;
         Ok(())
     }
@@ -246,8 +262,8 @@ impl Message {
         "🎵 **Now Playing**"
     }
 
-    async fn build_components(&self) -> Result<Component, DeserialiseBodyFromHttpError> {
-        Ok(Component::ActionRow(ActionRow {
+    async fn build_components(&self) -> Result<Vec<Component>, DeserialiseBodyFromHttpError> {
+        let mut rows = vec![Component::ActionRow(ActionRow {
             components: vec![
                 self.shuffle().await?,
                 self.previous().await?,
@@ -255,7 +271,30 @@ impl Message {
                 self.next().await?,
                 self.repeat().await?,
             ],
-        }))
+        })];
+        // the autoplay button is only shown while autoplay is enabled, for turning it off
+        if self.data.queue().autoplay() {
+            rows.push(Component::ActionRow(ActionRow {
+                components: vec![self.autoplay().await?],
+            }));
+        }
+        Ok(rows)
+    }
+
+    #[inline]
+    async fn autoplay(&self) -> Result<Component, DeserialiseBodyFromHttpError> {
+        let emoji = emoji::autoplay_on(self).await?.clone();
+        let custom_id = NOW_PLAYING_BUTTON_IDS.autoplay.to_owned();
+        let autoplay_button = Component::Button(Button {
+            disabled: false,
+            style: ButtonStyle::Secondary,
+            custom_id: Some(custom_id),
+            emoji: Some(emoji),
+            label: Some(String::from("Autoplay")),
+            url: None,
+            sku_id: None,
+        });
+        Ok(autoplay_button)
     }
 
     #[inline]
@@ -301,16 +340,14 @@ impl Message {
 
     #[inline]
     async fn shuffle(&self) -> Result<Component, DeserialiseBodyFromHttpError> {
-        let (emoji, disabled) = {
-            let (shuffle_emoji, shuffle_disabled) = match self.data.queue().indexer() {
-                IndexerType::Standard => (emoji::shuffle_off(self).await, false),
-                IndexerType::Fair => (emoji::shuffle_off(self).await, true),
-                IndexerType::Shuffled => (emoji::shuffle_on(self).await, false),
-            };
-            (shuffle_emoji?.clone(), shuffle_disabled)
-        };
+        let emoji = if self.data.queue().indexer().shuffled() {
+            emoji::shuffle_on(self).await
+        } else {
+            emoji::shuffle_off(self).await
+        }?
+        .clone();
         let custom_id = NOW_PLAYING_BUTTON_IDS.shuffle.to_owned();
-        let shuffle_button = Self::button(custom_id, disabled, emoji, ButtonStyle::Danger);
+        let shuffle_button = Self::button(custom_id, false, emoji, ButtonStyle::Danger);
         Ok(shuffle_button)
     }
 
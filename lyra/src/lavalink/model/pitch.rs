@@ -18,6 +18,21 @@ impl Pitch {
         }
     }
 
+    pub(super) const fn from_parts(multiplier: f64, half_tone_shifts: i64) -> Self {
+        Self {
+            multiplier,
+            half_tone_shifts,
+        }
+    }
+
+    pub(super) const fn multiplier(&self) -> f64 {
+        self.multiplier
+    }
+
+    pub(super) const fn half_tone_shifts(&self) -> i64 {
+        self.half_tone_shifts
+    }
+
     pub const fn set(&mut self, multiplier: f64) {
         self.multiplier = multiplier;
         self.half_tone_shifts = 0;
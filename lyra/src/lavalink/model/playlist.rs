@@ -26,6 +26,10 @@ impl PlaylistMetadata {
         self.info.corrected_name()
     }
 
+    pub(super) const fn info(&self) -> &PlaylistInfo {
+        &self.info
+    }
+
     pub const fn plugin_info(&self) -> Option<&PluginPlaylistInfo> {
         self.plugin_info.as_ref()
     }
@@ -63,6 +67,10 @@ impl PlaylistAwareTrackData {
     pub fn playlist(&self) -> Option<&PlaylistMetadata> {
         self.playlist.as_deref()
     }
+
+    pub(super) const fn playlist_shared(&self) -> Option<&Arc<PlaylistMetadata>> {
+        self.playlist.as_ref()
+    }
 }
 
 impl From<TrackData> for PlaylistAwareTrackData {
@@ -1,4 +1,4 @@
-use std::{collections::VecDeque, num::NonZeroUsize, time::Duration};
+use std::{collections::VecDeque, num::NonZeroUsize, sync::Arc, time::Duration};
 
 use lavalink_rs::model::track::TrackData;
 use rayon::iter::{IntoParallelIterator, ParallelExtend, ParallelIterator};
@@ -7,7 +7,7 @@ use twilight_model::id::{Id, marker::UserMarker};
 
 use super::{
     PlaylistAwareTrackData, PlaylistMetadata,
-    queue_indexer::{Indexer, IndexerType},
+    queue_indexer::{Fairness, Indexer, IndexerType, ShuffledIndexer, WeightedFairIndexer},
 };
 
 #[derive(Hash, Copy, Clone)]
@@ -65,6 +65,18 @@ impl Item {
         }
     }
 
+    pub(super) const fn new_enqueued_at(
+        track: PlaylistAwareTrackData,
+        requester: Id<UserMarker>,
+        enqueued: Duration,
+    ) -> Self {
+        Self {
+            track,
+            enqueued,
+            requester,
+        }
+    }
+
     pub const fn requester(&self) -> Id<UserMarker> {
         self.requester
     }
@@ -73,6 +85,14 @@ impl Item {
         self.track.inner()
     }
 
+    /// Replaces the track of this item with `track`, keeping the playlist it was added from.
+    pub fn replace_track(&mut self, track: TrackData) {
+        self.track = match self.track.playlist_shared() {
+            Some(playlist) => PlaylistAwareTrackData::new(track, playlist.clone()),
+            None => PlaylistAwareTrackData::from(track),
+        };
+    }
+
     pub fn playlist_data(&self) -> Option<&PlaylistMetadata> {
         self.track.playlist()
     }
@@ -84,6 +104,10 @@ impl Item {
     pub fn into_data(self) -> TrackData {
         self.track.into_inner()
     }
+
+    pub(super) const fn playlist_data_shared(&self) -> Option<&Arc<PlaylistMetadata>> {
+        self.track.playlist_shared()
+    }
 }
 
 pub struct Queue {
@@ -91,6 +115,7 @@ pub struct Queue {
     index: usize,
     indexer: Indexer,
     repeat_mode: RepeatMode,
+    autoplay: bool,
     advancing_enabler: watch::Sender<bool>,
 }
 
@@ -101,10 +126,60 @@ impl Queue {
             indexer: Indexer::Standard,
             index: 0,
             repeat_mode: RepeatMode::Off,
+            autoplay: false,
             advancing_enabler: watch::channel(true).0,
         }
     }
 
+    /// Replaces the contents of this queue with previously persisted items.
+    ///
+    /// The indexer is reset to the standard indexer, and should be restored separately
+    /// afterwards via [`Self::restore_fair_indexer`], [`Self::restore_weighted_fair_indexer`] or
+    /// [`Self::restore_shuffled_indexer`].
+    pub(super) fn restore(
+        &mut self,
+        items: impl IntoIterator<Item = Item>,
+        index: usize,
+        repeat_mode: RepeatMode,
+    ) {
+        self.inner = items.into_iter().collect();
+        self.index = index;
+        self.repeat_mode = repeat_mode;
+        self.indexer = Indexer::Standard;
+    }
+
+    pub(super) fn restore_weighted_fair_indexer(
+        &mut self,
+        starting_index: usize,
+        order: Vec<usize>,
+        fairness: Fairness,
+        shuffled: bool,
+    ) {
+        let indexer =
+            WeightedFairIndexer::from_order(&self.inner, starting_index, order, fairness, shuffled)
+                .unwrap_or_else(|| {
+                    WeightedFairIndexer::new(
+                        &self.inner,
+                        self.frozen_order(),
+                        starting_index,
+                        fairness,
+                        shuffled,
+                    )
+                });
+        self.indexer = Indexer::WeightedFair(indexer);
+    }
+
+    pub(super) fn restore_shuffled_indexer(&mut self, indices: Vec<usize>) {
+        self.indexer = ShuffledIndexer::from_indices(indices, self.len()).map_or_else(
+            || Indexer::Shuffled(ShuffledIndexer::new(self.frozen_order(), self.len())),
+            Indexer::Shuffled,
+        );
+    }
+
+    pub(super) const fn indexer(&self) -> &Indexer {
+        &self.indexer
+    }
+
     fn position_from(&self, current: Option<&Item>) -> NonZeroUsize {
         let d = usize::from(current.is_some() || self.index == 0);
         NonZeroUsize::new(self.index + d).expect("normalised queue position must be non-zero")
@@ -125,7 +200,7 @@ impl Queue {
     fn map_index(&self, index: usize) -> Option<usize> {
         match self.indexer {
             Indexer::Standard => Some(index),
-            Indexer::Fair(ref indexer) => indexer.current(index),
+            Indexer::WeightedFair(ref indexer) => indexer.current(index),
             Indexer::Shuffled(ref indexer) => indexer.current(index),
         }
     }
@@ -135,6 +210,16 @@ impl Queue {
         self.map_index(index).expect("track at index exists")
     }
 
+    /// The index that the item at storage `index` will be played at according to the current
+    /// indexer, or [`None`] if there is no such item.
+    pub fn unmap_index(&self, index: usize) -> Option<usize> {
+        match self.indexer {
+            Indexer::Standard => (index < self.len()).then_some(index),
+            Indexer::WeightedFair(ref indexer) => indexer.order().iter().position(|&i| i == index),
+            Indexer::Shuffled(ref indexer) => indexer.indices().iter().position(|&i| i == index),
+        }
+    }
+
     fn get_mapped(&self, index: usize) -> Option<&Item> {
         self.inner.get(self.map_index(index)?)
     }
@@ -144,6 +229,11 @@ impl Queue {
         self.get_mapped(self.index)
     }
 
+    pub fn current_mut(&mut self) -> Option<&mut Item> {
+        let index = self.map_index(self.index)?;
+        self.inner.get_mut(index)
+    }
+
     pub fn current_index(&self) -> Option<usize> {
         self.current().map(|_| self.index)
     }
@@ -160,13 +250,65 @@ impl Queue {
     }
 
     pub fn enqueue(&mut self, tracks: Vec<PlaylistAwareTrackData>, requester: Id<UserMarker>) {
+        let additional = tracks.len();
         match self.indexer {
-            Indexer::Fair(ref mut indexer) => indexer.enqueue(tracks.len(), requester),
-            Indexer::Shuffled(ref mut indexer) => indexer.enqueue(tracks.len(), self.index),
-            Indexer::Standard => {}
+            Indexer::Shuffled(ref mut indexer) => indexer.enqueue(additional, self.index),
+            Indexer::WeightedFair(_) | Indexer::Standard => {}
         }
         let queues = tracks.into_par_iter().map(|t| Item::new(t, requester));
         self.inner.par_extend(queues);
+
+        // the weighted fair indexer needs the lengths and requesters of the newly enqueued
+        // tracks, so it can only be updated after the tracks were added.
+        if let Indexer::WeightedFair(ref mut indexer) = self.indexer {
+            indexer.enqueue(&self.inner, additional, self.index);
+        }
+    }
+
+    /// Enqueues `tracks` so that they will be played starting from `index`, in the order that
+    /// tracks are played according to the current indexer.
+    ///
+    /// Fair indexers decide the order of the tracks by themselves, so if one is in use, `tracks`
+    /// are enqueued as usual instead. Returns whether the tracks were inserted at `index`.
+    pub fn enqueue_at(
+        &mut self,
+        tracks: Vec<PlaylistAwareTrackData>,
+        requester: Id<UserMarker>,
+        index: usize,
+    ) -> bool {
+        if matches!(self.indexer, Indexer::WeightedFair(_)) {
+            self.enqueue(tracks, requester);
+            return false;
+        }
+
+        let len = self.len();
+        let has_current = self.current().is_some();
+        // if nothing is playing, the tracks before the queue index have all been played, so the
+        // tracks are never inserted before them to not have them replayed.
+        let index = if has_current {
+            index.min(len)
+        } else {
+            index.clamp(self.index.min(len), len)
+        };
+        let additional = tracks.len();
+        let items = tracks.into_iter().map(|t| Item::new(t, requester));
+        if let Indexer::Shuffled(ref mut indexer) = self.indexer {
+            let old_len = self.inner.len();
+            self.inner.extend(items);
+            indexer.insert(index, old_len..old_len + additional);
+        } else {
+            let tail = self.inner.split_off(index);
+            self.inner.extend(items);
+            self.inner.extend(tail);
+        }
+
+        if !has_current {
+            // nothing is playing, so the inserted tracks are to be played right away.
+            self.index = index;
+        } else if index <= self.index {
+            self.index += additional;
+        }
+        true
     }
 
     pub fn dequeue<'a>(
@@ -210,6 +352,15 @@ impl Queue {
         self.repeat_mode = mode;
     }
 
+    /// Whether related tracks should be automatically enqueued once the queue runs out.
+    pub const fn autoplay(&self) -> bool {
+        self.autoplay
+    }
+
+    pub const fn set_autoplay(&mut self, autoplay: bool) {
+        self.autoplay = autoplay;
+    }
+
     pub fn downgrade_repeat_mode(&mut self) {
         if let RepeatMode::All | RepeatMode::Track = self.repeat_mode {
             self.repeat_mode = if self.len() > 1 {
@@ -224,25 +375,40 @@ impl Queue {
         self.indexer.kind()
     }
 
+    /// The storage indices of the tracks up to and including the current track, in the order
+    /// they are mapped to by the current indexer.
+    fn frozen_order(&self) -> Vec<usize> {
+        (0..(self.index + 1).min(self.len()))
+            .filter_map(|i| self.map_index(i))
+            .collect()
+    }
+
     pub fn set_indexer_type(&mut self, kind: IndexerType) {
-        match (self.indexer.kind(), kind) {
-            (IndexerType::Fair | IndexerType::Shuffled, IndexerType::Standard) => {
-                self.indexer = Indexer::Standard;
-            }
-            (IndexerType::Standard | IndexerType::Shuffled, IndexerType::Fair) => {
-                self.indexer = Indexer::Fair(super::queue_indexer::FairIndexer::new(
-                    self.inner.iter(),
-                    self.index,
-                ));
+        if self.indexer.kind() == kind {
+            return;
+        }
+        self.indexer = match (kind.fairness(), kind.shuffled()) {
+            (None, false) => Indexer::Standard,
+            (None, true) => {
+                Indexer::Shuffled(ShuffledIndexer::new(self.frozen_order(), self.len()))
             }
-            (IndexerType::Standard | IndexerType::Fair, IndexerType::Shuffled) => {
-                self.indexer = Indexer::Shuffled(super::queue_indexer::ShuffledIndexer::new(
-                    self.len(),
-                    self.index,
-                ));
+            (Some(fairness), shuffled) => {
+                // keep the fairness accumulated so far if only toggling shuffle
+                let starting_index = match self.indexer.fair_starting_index() {
+                    Some(starting_index) if self.indexer.kind().fairness() == Some(fairness) => {
+                        starting_index
+                    }
+                    _ => self.index,
+                };
+                Indexer::WeightedFair(WeightedFairIndexer::new(
+                    &self.inner,
+                    self.frozen_order(),
+                    starting_index,
+                    fairness,
+                    shuffled,
+                ))
             }
-            _ => {}
-        }
+        };
     }
 
     fn next_index(&self) -> usize {
@@ -322,6 +488,22 @@ impl Queue {
             .enumerate()
             .filter_map(|(i, t)| NonZeroUsize::new(i + 1).map(|i| (i, t)))
     }
+
+    /// Iterates over the items within `range`, in the order that they will be played according to
+    /// the current indexer.
+    ///
+    /// The positions are those of the items in the queue's storage, as with
+    /// [`Self::iter_positions_and_items`], rather than in the order that they will be played.
+    pub fn iter_mapped_positions_and_items(
+        &self,
+        range: std::ops::Range<usize>,
+    ) -> impl Iterator<Item = (NonZeroUsize, &Item)> {
+        range.filter_map(|i| {
+            let index = self.map_index(i)?;
+            Some((NonZeroUsize::new(index + 1)?, self.inner.get(index)?))
+        })
+    }
+
     #[inline]
     pub fn is_empty(&self) -> bool {
         self.inner.is_empty()
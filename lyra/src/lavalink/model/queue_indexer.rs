@@ -1,20 +1,63 @@
-use std::{collections::HashSet, ops::RangeBounds};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    ops::RangeBounds,
+};
 
-use itertools::Itertools;
-use lyra_ext::iter::{chunked_range::chunked_range, multi_interleave::multi_interleave};
 use rand::{Rng, seq::SliceRandom};
 use twilight_model::id::{Id, marker::UserMarker};
 
-#[derive(Clone, Copy)]
+use super::queue::Item;
+
+/// How a fair queue balances the tracks of its requesters.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Fairness {
+    /// Balances requesters by the number of tracks they have requested.
+    TrackCount,
+    /// Balances requesters by the accumulated play time of the tracks they have requested.
+    PlayTime,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum IndexerType {
     Standard,
     Fair,
+    DurationFair,
     Shuffled,
+    ShuffledFair,
+    ShuffledDurationFair,
+}
+
+impl IndexerType {
+    pub const fn new(fairness: Option<Fairness>, shuffled: bool) -> Self {
+        match (fairness, shuffled) {
+            (None, false) => Self::Standard,
+            (None, true) => Self::Shuffled,
+            (Some(Fairness::TrackCount), false) => Self::Fair,
+            (Some(Fairness::TrackCount), true) => Self::ShuffledFair,
+            (Some(Fairness::PlayTime), false) => Self::DurationFair,
+            (Some(Fairness::PlayTime), true) => Self::ShuffledDurationFair,
+        }
+    }
+
+    pub const fn fairness(self) -> Option<Fairness> {
+        match self {
+            Self::Standard | Self::Shuffled => None,
+            Self::Fair | Self::ShuffledFair => Some(Fairness::TrackCount),
+            Self::DurationFair | Self::ShuffledDurationFair => Some(Fairness::PlayTime),
+        }
+    }
+
+    pub const fn shuffled(self) -> bool {
+        matches!(
+            self,
+            Self::Shuffled | Self::ShuffledFair | Self::ShuffledDurationFair
+        )
+    }
 }
 
 pub(super) enum Indexer {
     Standard,
-    Fair(FairIndexer),
+    WeightedFair(WeightedFairIndexer),
     Shuffled(ShuffledIndexer),
 }
 
@@ -22,105 +65,204 @@ impl Indexer {
     pub(super) const fn kind(&self) -> IndexerType {
         match self {
             Self::Standard => IndexerType::Standard,
-            Self::Fair(_) => IndexerType::Fair,
+            Self::WeightedFair(indexer) => {
+                IndexerType::new(Some(indexer.fairness), indexer.shuffled)
+            }
             Self::Shuffled(_) => IndexerType::Shuffled,
         }
     }
 
+    /// The index the fairness of this indexer is accumulated from, if this indexer is fair.
+    pub(super) const fn fair_starting_index(&self) -> Option<usize> {
+        match self {
+            Self::WeightedFair(indexer) => Some(indexer.starting_index),
+            Self::Standard | Self::Shuffled(_) => None,
+        }
+    }
+
     pub(super) fn dequeue(&mut self, indices: impl Iterator<Item = usize>) {
         match self {
-            Self::Fair(indexer) => indexer.dequeue_or_drain(indices),
-            Self::Shuffled(indexer) => indexer.dequeue(&indices.collect()),
+            Self::WeightedFair(indexer) => indexer.dequeue_or_drain(indices),
+            Self::Shuffled(indexer) => remove_and_renumber(&mut indexer.0, indices),
             Self::Standard => {}
         }
     }
 
     pub(super) fn drain(&mut self, range: impl RangeBounds<usize> + Iterator<Item = usize>) {
         match self {
-            Self::Fair(indexer) => indexer.dequeue_or_drain(range),
-            Self::Shuffled(indexer) => indexer.drain(range),
+            Self::WeightedFair(indexer) => indexer.dequeue_or_drain(range),
+            Self::Shuffled(indexer) => remove_and_renumber(&mut indexer.0, range),
             Self::Standard => {}
         }
     }
 
     pub(super) fn clear(&mut self) {
         match self {
-            Self::Fair(indexer) => indexer.clear(),
-            Self::Shuffled(indexer) => indexer.clear(),
+            Self::WeightedFair(indexer) => indexer.clear(),
+            Self::Shuffled(indexer) => indexer.0.clear(),
             Self::Standard => {}
         }
     }
 }
 
-pub(super) struct FairIndexer {
+/// Removes the storage `indices` from an explicit indexing `order`, then shifts the remaining
+/// entries down so that they point to the same items after the removal.
+fn remove_and_renumber(order: &mut Vec<usize>, indices: impl Iterator<Item = usize>) {
+    let mut removed = indices.collect::<Vec<_>>();
+    removed.sort_unstable();
+    order.retain(|i| removed.binary_search(i).is_err());
+    for i in order {
+        *i -= removed.partition_point(|r| r < i);
+    }
+}
+
+/// Extends `prefix` into a complete indexing order of `0..size`, with the storage indices not
+/// already in `prefix` appended in storage order.
+fn complete_order(mut prefix: Vec<usize>, size: usize) -> Vec<usize> {
+    let mut seen = vec![false; size];
+    prefix.retain(|&i| i < size && !std::mem::replace(&mut seen[i], true));
+    prefix.extend((0..size).filter(|&i| !seen[i]));
+    prefix
+}
+
+fn is_permutation(order: &[usize], size: usize) -> bool {
+    let unique = order.iter().collect::<HashSet<_>>();
+    order.len() == size && unique.len() == size && unique.iter().all(|&&i| i < size)
+}
+
+/// A fair queue indexer that keeps an explicit indexing order, supporting balancing requesters
+/// by their accumulated play time and shuffling the tracks of each requester.
+///
+/// The order of the tracks up to and including the current track is kept as-is, while the order
+/// of the tracks after the current track is rescheduled upon every enqueue: the requester who has
+/// accumulated the least weight since the fair queue was enabled is always next. If shuffled,
+/// newly enqueued tracks are inserted at random positions among their requester's tracks.
+pub(super) struct WeightedFairIndexer {
     starting_index: usize,
-    inner: Vec<(Id<UserMarker>, usize)>,
+    order: Vec<usize>,
+    fairness: Fairness,
+    shuffled: bool,
 }
 
-impl FairIndexer {
-    pub(super) fn new<'a>(
-        items: impl Iterator<Item = &'a super::queue::Item>,
+impl WeightedFairIndexer {
+    /// Creates a weighted fair indexer, keeping the order of `prefix` as the order of the tracks
+    /// up to and including the current track.
+    pub(super) fn new(
+        items: &VecDeque<Item>,
+        prefix: Vec<usize>,
         starting_index: usize,
+        fairness: Fairness,
+        shuffled: bool,
     ) -> Self {
-        let inner = items
-            .skip(starting_index)
-            .chunk_by(|c| c.requester)
-            .into_iter()
-            .map(|(r, g)| (r, g.count()))
-            .collect();
-
-        Self {
+        let split = prefix.len();
+        let mut indexer = Self {
             starting_index,
-            inner,
-        }
+            order: complete_order(prefix, items.len()),
+            fairness,
+            shuffled,
+        };
+        indexer.reschedule(items, split, 0);
+        indexer
     }
 
-    fn iter_bucket_lens(&self) -> impl Iterator<Item = usize> + Clone + use<'_> {
-        self.inner.iter().map(|(_, l)| l).copied()
-    }
-
-    fn iter_bucket_ranges(&self) -> impl Iterator<Item = std::ops::Range<usize>> + use<'_> {
-        self.inner.iter().scan(self.starting_index, |i, (_, l)| {
-            let j = *i;
-            *i += l;
-            Some(j..*i)
+    /// Creates a weighted fair indexer from a previously persisted order, returning [`None`] if
+    /// the order is not a permutation of `0..items.len()`.
+    pub(super) fn from_order(
+        items: &VecDeque<Item>,
+        starting_index: usize,
+        order: Vec<usize>,
+        fairness: Fairness,
+        shuffled: bool,
+    ) -> Option<Self> {
+        is_permutation(&order, items.len()).then_some(Self {
+            starting_index,
+            order,
+            fairness,
+            shuffled,
         })
     }
 
-    fn iter_indices(&self) -> impl Iterator<Item = usize> + use<'_> {
-        multi_interleave(chunked_range(self.starting_index, self.iter_bucket_lens()))
+    pub(super) fn order(&self) -> &[usize] {
+        &self.order
     }
 
     pub(super) fn current(&self, current_index: usize) -> Option<usize> {
-        self.iter_indices().nth(current_index - self.starting_index)
+        self.order.get(current_index).copied()
     }
 
-    pub(super) fn enqueue(&mut self, additional: usize, requester: Id<UserMarker>) {
-        match self.inner.last_mut() {
-            Some((last_requester, last_size)) if *last_requester == requester => {
-                *last_size += additional;
-            }
-            _ => self.inner.push((requester, additional)),
+    const fn weight(&self, item: &Item) -> u64 {
+        match self.fairness {
+            Fairness::TrackCount => 1,
+            Fairness::PlayTime => item.data().info.length,
         }
     }
 
-    fn dequeue_or_drain(&mut self, mut indices: impl Iterator<Item = usize>) {
-        let bucket_ranges = self.iter_bucket_ranges().collect::<Box<_>>();
-        let mut iter_bucket_ranges = bucket_ranges.iter().peekable();
-        self.inner.retain_mut(|(_, l)| {
-            if indices
-                .next()
-                .is_some_and(|i| iter_bucket_ranges.peek().is_some_and(|r| r.contains(&i)))
-            {
-                *l -= 1;
-                iter_bucket_ranges.next();
+    /// Reschedules the tracks from `split` onwards, with the tracks at storage indices of at least
+    /// `fresh_from` being considered as newly enqueued.
+    fn reschedule(&mut self, items: &VecDeque<Item>, split: usize, fresh_from: usize) {
+        let mut accumulated = HashMap::<Id<UserMarker>, u64>::new();
+        for &i in &self.order[self.starting_index.min(split)..split] {
+            let weight = accumulated.entry(items[i].requester).or_default();
+            *weight = weight.saturating_add(self.weight(&items[i]));
+        }
+
+        let mut rng = rand::rng();
+        let mut buckets = Vec::<(Id<UserMarker>, VecDeque<usize>)>::new();
+        for i in self.order.drain(split..) {
+            let requester = items[i].requester;
+            let b = buckets
+                .iter()
+                .position(|(r, _)| *r == requester)
+                .unwrap_or_else(|| {
+                    buckets.push((requester, VecDeque::new()));
+                    buckets.len() - 1
+                });
+            let bucket = &mut buckets[b].1;
+            if self.shuffled && i >= fresh_from {
+                bucket.insert(rng.random_range(0..=bucket.len()), i);
+            } else {
+                bucket.push_back(i);
             }
-            *l == 0
-        });
+        }
+
+        while let Some((requester, i)) = buckets
+            .iter_mut()
+            .filter(|(_, b)| !b.is_empty())
+            .min_by_key(|(r, _)| accumulated.get(r).copied().unwrap_or_default())
+            .and_then(|(r, b)| b.pop_front().map(|i| (*r, i)))
+        {
+            let weight = accumulated.entry(requester).or_default();
+            *weight = weight.saturating_add(self.weight(&items[i]));
+            self.order.push(i);
+        }
+    }
+
+    pub(super) fn enqueue(
+        &mut self,
+        items: &VecDeque<Item>,
+        additional: usize,
+        current_index: usize,
+    ) {
+        let old_len = self.order.len();
+        self.order.extend(old_len..old_len + additional);
+        self.reschedule(items, (current_index + 1).min(self.order.len()), old_len);
+    }
+
+    fn dequeue_or_drain(&mut self, indices: impl Iterator<Item = usize>) {
+        let mut removed = indices.collect::<Vec<_>>();
+        removed.sort_unstable();
+        // the fairness is accumulated from the starting position, which is shifted back by the
+        // tracks removed before it.
+        let removed_before_start = self.order[..self.starting_index.min(self.order.len())]
+            .iter()
+            .filter(|i| removed.binary_search(i).is_ok())
+            .count();
+        self.starting_index -= removed_before_start;
+        remove_and_renumber(&mut self.order, removed.into_iter());
     }
 
     fn clear(&mut self) {
-        self.inner.clear();
+        self.order.clear();
         self.starting_index = 0;
     }
 }
@@ -128,19 +270,35 @@ impl FairIndexer {
 pub(super) struct ShuffledIndexer(Vec<usize>);
 
 impl ShuffledIndexer {
-    pub(super) fn new(size: usize, starting_index: usize) -> Self {
-        let mut rest = (0..size).collect::<Vec<_>>();
-        let mut next = rest.drain(starting_index + 1..).collect::<Vec<_>>();
-        next.shuffle(&mut rand::rng());
-        rest.extend_from_slice(&next);
+    /// Creates a shuffled indexer, keeping the order of `prefix` as the order of the tracks up
+    /// to and including the current track.
+    pub(super) fn new(prefix: Vec<usize>, size: usize) -> Self {
+        let split = prefix.len();
+        let mut order = complete_order(prefix, size);
+        order[split..].shuffle(&mut rand::rng());
 
-        Self(rest)
+        Self(order)
+    }
+
+    /// Creates a shuffled indexer from a previously persisted mapping, returning [`None`] if
+    /// the mapping is not a permutation of `0..size`.
+    pub(super) fn from_indices(indices: Vec<usize>, size: usize) -> Option<Self> {
+        is_permutation(&indices, size).then_some(Self(indices))
+    }
+
+    pub(super) fn indices(&self) -> &[usize] {
+        &self.0
     }
 
     pub(super) fn current(&self, current_index: usize) -> Option<usize> {
         self.0.get(current_index).copied()
     }
 
+    /// Inserts the newly enqueued storage indices `new` into the indexing order at `index`.
+    pub(super) fn insert(&mut self, index: usize, new: std::ops::Range<usize>) {
+        self.0.splice(index..index, new);
+    }
+
     pub(super) fn enqueue(&mut self, additional: usize, current_index: usize) {
         let old_len = self.0.len();
         self.0.reserve(additional);
@@ -151,16 +309,152 @@ impl ShuffledIndexer {
             .zip(old_len..old_len + additional)
             .for_each(|(i, e)| self.0.insert(i, e));
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::VecDeque, time::Duration};
+
+    use lavalink_rs::model::track::{TrackData, TrackInfo};
+    use twilight_model::id::Id;
+
+    use super::{
+        Fairness, Item, ShuffledIndexer, WeightedFairIndexer, complete_order, is_permutation,
+    };
+
+    /// Creates the queue items of `(requester, length)` pairs.
+    fn items(tracks: &[(u64, u64)]) -> VecDeque<Item> {
+        tracks
+            .iter()
+            .map(|&(requester, length)| {
+                let track = TrackData {
+                    info: TrackInfo {
+                        length,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                };
+                Item::new_enqueued_at(track.into(), Id::new(requester), Duration::ZERO)
+            })
+            .collect()
+    }
 
-    fn dequeue(&mut self, indices: &HashSet<usize>) {
-        self.0.retain(|i| !indices.contains(i));
+    fn requesters(items: &VecDeque<Item>, order: &[usize]) -> Vec<u64> {
+        order.iter().map(|&i| items[i].requester().get()).collect()
     }
 
-    fn drain(&mut self, range: impl RangeBounds<usize>) {
-        self.0.drain(range);
+    #[test]
+    fn is_permutation_of_size() {
+        assert!(is_permutation(&[2, 0, 1], 3));
+        assert!(is_permutation(&[], 0));
+        assert!(!is_permutation(&[0, 0, 1], 3));
+        assert!(!is_permutation(&[0, 1, 3], 3));
+        assert!(!is_permutation(&[1, 0], 3));
+        assert!(!is_permutation(&[1, 0, 2], 2));
     }
 
-    fn clear(&mut self) {
-        self.0.clear();
+    #[test]
+    fn complete_order_appends_missing_indices() {
+        assert_eq!(complete_order(vec![3, 1, 3, 7], 5), [3, 1, 0, 2, 4]);
+        assert_eq!(complete_order(Vec::new(), 3), [0, 1, 2]);
+    }
+
+    #[test]
+    fn shuffled_indexer_from_indices() {
+        let indexer = ShuffledIndexer::from_indices(vec![2, 0, 1], 3);
+        assert_eq!(
+            indexer.as_ref().map(ShuffledIndexer::indices),
+            Some(&[2, 0, 1][..])
+        );
+        assert_eq!(indexer.and_then(|i| i.current(1)), Some(0));
+
+        assert!(ShuffledIndexer::from_indices(vec![1, 1, 0], 3).is_none());
+        assert!(ShuffledIndexer::from_indices(vec![0, 1], 3).is_none());
+    }
+
+    #[test]
+    fn fair_indexer_interleaves_requesters() {
+        let items = items(&[(1, 1), (1, 1), (1, 1), (2, 1), (2, 1), (3, 1)]);
+        let indexer = WeightedFairIndexer::new(&items, Vec::new(), 0, Fairness::TrackCount, false);
+
+        assert_eq!(indexer.order(), [0, 3, 5, 1, 4, 2]);
+    }
+
+    #[test]
+    fn fair_indexer_keeps_prefix() {
+        let items = items(&[(1, 1), (1, 1), (1, 1), (2, 1), (2, 1)]);
+        let indexer = WeightedFairIndexer::new(&items, vec![0, 1], 0, Fairness::TrackCount, false);
+
+        // requester 1 already has two tracks played, so requester 2 is caught up first
+        assert_eq!(indexer.order(), [0, 1, 3, 4, 2]);
+    }
+
+    #[test]
+    fn fair_indexer_enqueue_reschedules_after_current() {
+        let mut items = items(&[(1, 1), (1, 1), (2, 1)]);
+        let mut indexer =
+            WeightedFairIndexer::new(&items, Vec::new(), 0, Fairness::TrackCount, false);
+        assert_eq!(indexer.order(), [0, 2, 1]);
+
+        items.extend(self::items(&[(3, 1), (3, 1)]));
+        indexer.enqueue(&items, 2, 1);
+
+        assert_eq!(indexer.order(), [0, 2, 3, 1, 4]);
+        assert_eq!(requesters(&items, indexer.order()), [1, 2, 3, 1, 3]);
+    }
+
+    #[test]
+    fn fair_indexer_balances_play_time() {
+        let items = items(&[(1, 300), (1, 300), (2, 100), (2, 100), (2, 100), (2, 100)]);
+        let indexer = WeightedFairIndexer::new(&items, Vec::new(), 0, Fairness::PlayTime, false);
+
+        assert_eq!(indexer.order(), [0, 2, 3, 4, 1, 5]);
+    }
+
+    #[test]
+    fn fair_indexer_shuffles_within_requesters() {
+        let items = items(&[(1, 1), (1, 1), (1, 1), (2, 1), (2, 1), (2, 1)]);
+        let indexer = WeightedFairIndexer::new(&items, vec![0], 0, Fairness::TrackCount, true);
+        let order = indexer.order();
+
+        assert!(is_permutation(order, items.len()));
+        assert_eq!(order[0], 0);
+        assert_eq!(requesters(&items, order), [1, 2, 1, 2, 1, 2]);
+    }
+
+    #[test]
+    fn fair_indexer_dequeue_renumbers_order() {
+        // requesters: [1] [1 1 1] [2 2 2] [3 3]
+        let items = items(&[
+            (4, 1),
+            (1, 1),
+            (1, 1),
+            (1, 1),
+            (2, 1),
+            (2, 1),
+            (2, 1),
+            (3, 1),
+            (3, 1),
+        ]);
+        let mut indexer = WeightedFairIndexer::new(&items, vec![0], 1, Fairness::TrackCount, false);
+        assert_eq!(indexer.order(), [0, 1, 4, 7, 2, 5, 8, 3, 6]);
+
+        indexer.dequeue_or_drain([6, 2, 3, 5].into_iter());
+
+        assert_eq!(indexer.order(), [0, 1, 2, 3, 4]);
+        assert_eq!(indexer.starting_index, 1);
+    }
+
+    #[test]
+    fn fair_indexer_dequeue_shifts_starting_index() {
+        let items = items(&[(1, 1), (1, 1), (1, 1), (2, 1), (2, 1)]);
+        let mut indexer =
+            WeightedFairIndexer::new(&items, vec![0, 1], 2, Fairness::TrackCount, false);
+        assert_eq!(indexer.order(), [0, 1, 2, 3, 4]);
+
+        indexer.dequeue_or_drain([0, 3, 2].into_iter());
+
+        assert_eq!(indexer.order(), [0, 1]);
+        assert_eq!(indexer.starting_index, 1);
     }
 }
@@ -0,0 +1,203 @@
+use std::{num::NonZeroUsize, time::Duration};
+
+use sqlx::{Pool, Postgres};
+use twilight_model::id::{
+    Id,
+    marker::{GuildMarker, MessageMarker},
+};
+
+use crate::core::konst::misc::TRACK_RECOVERIES_LIMIT;
+
+use super::RawPlayerData;
+
+/// A step taken to recover a stuck track.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RecoveryStep {
+    /// Retrying the same track from where it was last at.
+    Retry,
+    /// Substituting the track with the same track from another source.
+    Fallback,
+    /// Skipping the track.
+    Skip,
+}
+
+impl RecoveryStep {
+    const fn from_i16(value: i16) -> Self {
+        match value {
+            1 => Self::Fallback,
+            2 => Self::Skip,
+            _ => Self::Retry,
+        }
+    }
+
+    const fn as_i16(self) -> i16 {
+        match self {
+            Self::Retry => 0,
+            Self::Fallback => 1,
+            Self::Skip => 2,
+        }
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Retry => "Retry",
+            Self::Fallback => "Fallback",
+            Self::Skip => "Skip",
+        }
+    }
+}
+
+/// The ongoing recovery of the track at a queue position from getting stuck.
+pub struct StuckRecovery {
+    position: NonZeroUsize,
+    failures: u8,
+    message_id: Option<Id<MessageMarker>>,
+    lines: Vec<String>,
+}
+
+impl StuckRecovery {
+    /// Counts another time the track got stuck, returning how many times it has so far.
+    pub const fn fail(&mut self) -> u8 {
+        self.failures = self.failures.saturating_add(1);
+        self.failures
+    }
+
+    pub const fn message_id(&self) -> Option<Id<MessageMarker>> {
+        self.message_id
+    }
+
+    pub const fn set_message_id(&mut self, message_id: Id<MessageMarker>) {
+        self.message_id = Some(message_id);
+    }
+
+    /// Adds a line to the status message, returning the status message's new content.
+    pub fn report(&mut self, line: String) -> String {
+        self.lines.push(line);
+        self.lines.join("\n")
+    }
+}
+
+impl RawPlayerData {
+    /// The recovery of the track at queue position `position`, starting a new one if the track
+    /// is not already being recovered.
+    pub fn stuck_recovery_at(&mut self, position: NonZeroUsize) -> &mut StuckRecovery {
+        if self
+            .stuck_recovery
+            .as_ref()
+            .is_some_and(|r| r.position != position)
+        {
+            self.stuck_recovery = None;
+        }
+        self.stuck_recovery.get_or_insert_with(|| StuckRecovery {
+            position,
+            failures: 0,
+            message_id: None,
+            lines: Vec::new(),
+        })
+    }
+
+    /// Ends the recovery of the current track, as it has played through without getting stuck.
+    pub fn end_stuck_recovery(&mut self) {
+        self.stuck_recovery = None;
+    }
+}
+
+/// A record of a step taken to recover a stuck track in a guild.
+pub struct RecoveryAttempt {
+    title: String,
+    attempt: u8,
+    step: RecoveryStep,
+    detail: Option<String>,
+    at: Duration,
+}
+
+impl RecoveryAttempt {
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub const fn attempt(&self) -> u8 {
+        self.attempt
+    }
+
+    pub const fn step(&self) -> RecoveryStep {
+        self.step
+    }
+
+    pub fn detail(&self) -> Option<&str> {
+        self.detail.as_deref()
+    }
+
+    /// When this step was taken, as a duration since the Unix epoch.
+    pub const fn at(&self) -> Duration {
+        self.at
+    }
+
+    /// Records a step taken to recover the stuck track titled `title`, discarding the oldest
+    /// records of the guild beyond [`TRACK_RECOVERIES_LIMIT`].
+    pub async fn record(
+        db: &Pool<Postgres>,
+        guild_id: Id<GuildMarker>,
+        title: &str,
+        attempt: u8,
+        step: RecoveryStep,
+        detail: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        let guild = guild_id.get().cast_signed();
+        let at = i64::try_from(lyra_ext::unix_time().as_millis()).unwrap_or(i64::MAX);
+
+        sqlx::query!(
+            "INSERT INTO track_recoveries (guild, title, attempt, step, detail, at)
+            VALUES ($1, $2, $3, $4, $5, $6);",
+            guild,
+            title,
+            i16::from(attempt),
+            step.as_i16(),
+            detail,
+            at,
+        )
+        .execute(db)
+        .await?;
+
+        sqlx::query!(
+            "DELETE FROM track_recoveries
+            WHERE guild = $1 AND id NOT IN (
+                SELECT id FROM track_recoveries WHERE guild = $1 ORDER BY id DESC LIMIT $2
+            );",
+            guild,
+            TRACK_RECOVERIES_LIMIT,
+        )
+        .execute(db)
+        .await?;
+        Ok(())
+    }
+
+    /// Fetches at most `limit` records of the guild, most recent first.
+    pub async fn fetch(
+        db: &Pool<Postgres>,
+        guild_id: Id<GuildMarker>,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let rows = sqlx::query!(
+            "SELECT title, attempt, step, detail, at FROM track_recoveries
+            WHERE guild = $1
+            ORDER BY id DESC
+            LIMIT $2;",
+            guild_id.get().cast_signed(),
+            limit,
+        )
+        .fetch_all(db)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Self {
+                title: row.title,
+                attempt: u8::try_from(row.attempt).unwrap_or(u8::MAX),
+                step: RecoveryStep::from_i16(row.step),
+                detail: row.detail,
+                at: Duration::from_millis(row.at.max(0).cast_unsigned()),
+            })
+            .collect())
+    }
+}
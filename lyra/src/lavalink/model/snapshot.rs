@@ -0,0 +1,823 @@
+use std::{collections::HashMap, num::NonZeroU16, sync::Arc, time::Duration};
+
+use lavalink_rs::{
+    error::LavalinkResult,
+    model::{
+        http::{UpdatePlayer, UpdatePlayerTrack},
+        player::{Filters, Player},
+        track::{PlaylistData, PlaylistInfo, TrackData},
+    },
+    player_context::PlayerContext,
+};
+use sqlx::{Pool, Postgres, Transaction};
+use twilight_model::id::{
+    Id,
+    marker::{ChannelMarker, GuildMarker},
+};
+
+use crate::{
+    component::tuning::ActiveEffect, core::model::DatabaseAware, error::lavalink::SaveSnapshotError,
+};
+
+use super::{
+    IndexerType, Lavalink, Pitch, PlaylistAwareTrackData, PlaylistMetadata, QueueItem,
+    RawPlayerData, RepeatMode, UnwrappedData, UpdateNowPlayingMessageResult,
+    queue_indexer::Indexer,
+};
+
+fn into_i64(n: usize) -> i64 {
+    i64::try_from(n).expect("queue sizes must fit in an `i64`")
+}
+
+fn into_usize(n: i64) -> Option<usize> {
+    usize::try_from(n).ok()
+}
+
+fn into_millis_i64(duration: Duration) -> i64 {
+    i64::try_from(duration.as_millis()).unwrap_or(i64::MAX)
+}
+
+fn into_millis_duration(millis: i64) -> Duration {
+    Duration::from_millis(millis.max(0).cast_unsigned())
+}
+
+const fn repeat_mode_into_i16(mode: RepeatMode) -> i16 {
+    match mode {
+        RepeatMode::Off => 0,
+        RepeatMode::All => 1,
+        RepeatMode::Track => 2,
+    }
+}
+
+const fn repeat_mode_from_i16(value: i16) -> RepeatMode {
+    match value {
+        1 => RepeatMode::All,
+        2 => RepeatMode::Track,
+        _ => RepeatMode::Off,
+    }
+}
+
+const fn indexer_type_into_i16(kind: IndexerType) -> i16 {
+    match kind {
+        IndexerType::Standard => 0,
+        IndexerType::Fair => 1,
+        IndexerType::Shuffled => 2,
+        IndexerType::DurationFair => 3,
+        IndexerType::ShuffledFair => 4,
+        IndexerType::ShuffledDurationFair => 5,
+    }
+}
+
+const fn indexer_type_from_i16(value: i16) -> IndexerType {
+    match value {
+        1 => IndexerType::Fair,
+        2 => IndexerType::Shuffled,
+        3 => IndexerType::DurationFair,
+        4 => IndexerType::ShuffledFair,
+        5 => IndexerType::ShuffledDurationFair,
+        _ => IndexerType::Standard,
+    }
+}
+
+struct SnapshotPlaylist {
+    id: i32,
+    uri: String,
+    name: String,
+    selected_track: Option<i64>,
+    plugin_info: Option<String>,
+}
+
+impl SnapshotPlaylist {
+    fn into_metadata(self) -> PlaylistMetadata {
+        let data = PlaylistData {
+            info: PlaylistInfo {
+                name: self.name,
+                selected_track: self.selected_track.and_then(|t| u32::try_from(t).ok()),
+            },
+            tracks: Vec::new(),
+            plugin_info: self.plugin_info.and_then(|i| serde_json::from_str(&i).ok()),
+        };
+        PlaylistMetadata::new(self.uri.into(), data)
+    }
+}
+
+struct SnapshotTrack {
+    encoded: String,
+    requester: i64,
+    enqueued: i64,
+    playlist: Option<i32>,
+}
+
+struct SnapshotQueue {
+    playlists: Vec<SnapshotPlaylist>,
+    tracks: Vec<SnapshotTrack>,
+}
+
+impl SnapshotQueue {
+    fn new<'a>(items: impl Iterator<Item = &'a QueueItem>) -> Result<Self, serde_json::Error> {
+        let mut playlist_ids = HashMap::<*const PlaylistMetadata, i32>::new();
+        let mut playlists = Vec::new();
+        let mut tracks = Vec::new();
+
+        for item in items {
+            let playlist = match item.playlist_data_shared() {
+                Some(p) => {
+                    let next_id = i32::try_from(playlists.len())
+                        .expect("playlist count must fit in an `i32`");
+                    let id = *playlist_ids.entry(Arc::as_ptr(p)).or_insert(next_id);
+                    if id == next_id {
+                        playlists.push(SnapshotPlaylist {
+                            id,
+                            uri: p.uri.to_string(),
+                            name: p.info().name.clone(),
+                            selected_track: p.info().selected_track.map(i64::from),
+                            plugin_info: p.plugin_info().map(serde_json::to_string).transpose()?,
+                        });
+                    }
+                    Some(id)
+                }
+                None => None,
+            };
+
+            tracks.push(SnapshotTrack {
+                encoded: item.data().encoded.clone(),
+                requester: item.requester().get().cast_signed(),
+                enqueued: into_millis_i64(item.enqueued()),
+                playlist,
+            });
+        }
+
+        Ok(Self { playlists, tracks })
+    }
+
+    async fn save(self, guild: i64, tx: &mut Transaction<'_, Postgres>) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "DELETE FROM player_snapshot_tracks WHERE guild = $1;",
+            guild
+        )
+        .execute(&mut **tx)
+        .await?;
+        sqlx::query!(
+            "DELETE FROM player_snapshot_playlists WHERE guild = $1;",
+            guild
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        let (ids, uris, names, selected_tracks, plugin_infos) = self
+            .playlists
+            .into_iter()
+            .map(|p| (p.id, p.uri, p.name, p.selected_track, p.plugin_info))
+            .collect::<(Vec<_>, Vec<_>, Vec<_>, Vec<_>, Vec<_>)>();
+        sqlx::query!(
+            "INSERT INTO player_snapshot_playlists
+                (guild, id, uri, name, selected_track, plugin_info)
+            SELECT $1, * FROM UNNEST($2::integer[], $3::text[], $4::text[], $5::bigint[], $6::text[]);",
+            guild,
+            &ids,
+            &uris,
+            &names,
+            &selected_tracks as &[Option<i64>],
+            &plugin_infos as &[Option<String>],
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        let (positions, (encoded, (requesters, (enqueued, playlists)))) = self
+            .tracks
+            .into_iter()
+            .enumerate()
+            .map(|(i, t)| {
+                (
+                    into_i64(i),
+                    (t.encoded, (t.requester, (t.enqueued, t.playlist))),
+                )
+            })
+            .collect::<(Vec<_>, (Vec<_>, (Vec<_>, (Vec<_>, Vec<_>))))>();
+        sqlx::query!(
+            "INSERT INTO player_snapshot_tracks
+                (guild, position, encoded, requester, enqueued, playlist)
+            SELECT $1, * FROM UNNEST($2::bigint[], $3::text[], $4::bigint[], $5::bigint[], $6::integer[]);",
+            guild,
+            &positions,
+            &encoded,
+            &requesters,
+            &enqueued,
+            &playlists as &[Option<i32>],
+        )
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+}
+
+/// A persistable copy of a player's state, used for restoring players after a restart.
+pub struct Snapshot {
+    guild_id: Id<GuildMarker>,
+    voice_channel_id: Id<ChannelMarker>,
+    text_channel_id: Id<ChannelMarker>,
+    queue_index: usize,
+    repeat_mode: RepeatMode,
+    autoplay: bool,
+    indexer: IndexerType,
+    fair_starting_index: Option<usize>,
+    indexer_mapping: Option<Vec<usize>>,
+    volume: NonZeroU16,
+    pitch: Pitch,
+    speed: f64,
+    filters: Option<String>,
+    effect_profile: Option<i16>,
+    effect_previous_filters: Option<String>,
+    paused: bool,
+    timestamp: Duration,
+    queue: Option<SnapshotQueue>,
+}
+
+impl Snapshot {
+    pub const fn voice_channel_id(&self) -> Id<ChannelMarker> {
+        self.voice_channel_id
+    }
+
+    pub const fn text_channel_id(&self) -> Id<ChannelMarker> {
+        self.text_channel_id
+    }
+
+    pub fn filters(&self) -> Option<Filters> {
+        serde_json::from_str(self.filters.as_deref()?).ok()
+    }
+
+    pub fn encoded_tracks(&self) -> Vec<String> {
+        self.queue
+            .iter()
+            .flat_map(|q| q.tracks.iter().map(|t| t.encoded.clone()))
+            .collect()
+    }
+
+    pub async fn load(
+        db: &Pool<Postgres>,
+        guild_id: Id<GuildMarker>,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let guild = guild_id.get().cast_signed();
+        let Some(row) = sqlx::query!("SELECT * FROM player_snapshots WHERE guild = $1;", guild)
+            .fetch_optional(db)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let playlists = sqlx::query_as!(
+            SnapshotPlaylist,
+            "SELECT id, uri, name, selected_track, plugin_info
+            FROM player_snapshot_playlists
+            WHERE guild = $1
+            ORDER BY id;",
+            guild
+        )
+        .fetch_all(db)
+        .await?;
+        let tracks = sqlx::query_as!(
+            SnapshotTrack,
+            "SELECT encoded, requester, enqueued, playlist
+            FROM player_snapshot_tracks
+            WHERE guild = $1
+            ORDER BY position;",
+            guild
+        )
+        .fetch_all(db)
+        .await?;
+
+        let (Some(voice_channel_id), Some(text_channel_id)) = (
+            Id::new_checked(row.voice_channel.cast_unsigned()),
+            Id::new_checked(row.text_channel.cast_unsigned()),
+        ) else {
+            return Ok(None);
+        };
+
+        Ok(Some(Self {
+            guild_id,
+            voice_channel_id,
+            text_channel_id,
+            queue_index: into_usize(row.queue_index).unwrap_or_default(),
+            repeat_mode: repeat_mode_from_i16(row.repeat_mode),
+            autoplay: row.autoplay,
+            indexer: indexer_type_from_i16(row.indexer),
+            fair_starting_index: row.fair_starting_index.and_then(into_usize),
+            indexer_mapping: row
+                .indexer_mapping
+                .map(|v| v.into_iter().filter_map(into_usize).collect()),
+            volume: u16::try_from(row.volume)
+                .ok()
+                .and_then(NonZeroU16::new)
+                .unwrap_or(NonZeroU16::new(100).expect("100 must be non-zero")),
+            pitch: Pitch::from_parts(row.pitch_multiplier, row.pitch_half_tone_shifts),
+            speed: row.speed,
+            filters: row.filters,
+            effect_profile: row.effect_profile,
+            effect_previous_filters: row.effect_previous_filters,
+            paused: row.paused,
+            timestamp: into_millis_duration(row.track_timestamp),
+            queue: Some(SnapshotQueue { playlists, tracks }),
+        }))
+    }
+
+    pub async fn delete(db: &Pool<Postgres>, guild_id: Id<GuildMarker>) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "DELETE FROM player_snapshots WHERE guild = $1;",
+            guild_id.get().cast_signed()
+        )
+        .execute(db)
+        .await?;
+        Ok(())
+    }
+
+    /// Saves this snapshot, only rewriting the persisted queue if it has been modified since the
+    /// last snapshot was taken.
+    pub async fn save(self, db: &Pool<Postgres>) -> Result<(), sqlx::Error> {
+        let guild = self.guild_id.get().cast_signed();
+        let indexer_mapping = self
+            .indexer_mapping
+            .map(|v| v.into_iter().map(into_i64).collect::<Vec<_>>());
+        let mut tx = db.begin().await?;
+
+        sqlx::query!(
+            "INSERT INTO player_snapshots
+                (guild, voice_channel, text_channel, queue_index, repeat_mode, indexer,
+                fair_starting_index, indexer_mapping, volume, pitch_multiplier,
+                pitch_half_tone_shifts, speed, filters, paused, track_timestamp, autoplay,
+                effect_profile, effect_previous_filters)
+            VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18)
+            ON CONFLICT (guild) DO UPDATE SET
+                voice_channel = EXCLUDED.voice_channel,
+                text_channel = EXCLUDED.text_channel,
+                queue_index = EXCLUDED.queue_index,
+                repeat_mode = EXCLUDED.repeat_mode,
+                indexer = EXCLUDED.indexer,
+                fair_starting_index = EXCLUDED.fair_starting_index,
+                indexer_mapping = EXCLUDED.indexer_mapping,
+                volume = EXCLUDED.volume,
+                pitch_multiplier = EXCLUDED.pitch_multiplier,
+                pitch_half_tone_shifts = EXCLUDED.pitch_half_tone_shifts,
+                speed = EXCLUDED.speed,
+                filters = EXCLUDED.filters,
+                paused = EXCLUDED.paused,
+                track_timestamp = EXCLUDED.track_timestamp,
+                autoplay = EXCLUDED.autoplay,
+                effect_profile = EXCLUDED.effect_profile,
+                effect_previous_filters = EXCLUDED.effect_previous_filters;",
+            guild,
+            self.voice_channel_id.get().cast_signed(),
+            self.text_channel_id.get().cast_signed(),
+            into_i64(self.queue_index),
+            repeat_mode_into_i16(self.repeat_mode),
+            indexer_type_into_i16(self.indexer),
+            self.fair_starting_index.map(into_i64),
+            indexer_mapping.as_deref(),
+            i32::from(self.volume.get()),
+            self.pitch.multiplier(),
+            self.pitch.half_tone_shifts(),
+            self.speed,
+            self.filters,
+            self.paused,
+            into_millis_i64(self.timestamp),
+            self.autoplay,
+            self.effect_profile,
+            self.effect_previous_filters,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        if let Some(queue) = self.queue {
+            queue.save(guild, &mut tx).await?;
+        }
+
+        tx.commit().await
+    }
+}
+
+/// Maps the storage indices of a snapshot's queue to those of the restored queue, in which the
+/// tracks that could not be restored are left out.
+struct Remapping(Vec<Option<usize>>);
+
+impl Remapping {
+    fn index(&self, index: usize) -> Option<usize> {
+        self.0.get(index).copied().flatten()
+    }
+
+    /// Maps an indexing order of storage indices, leaving out those of unrestored tracks.
+    fn order(&self, order: &[usize]) -> Vec<usize> {
+        order.iter().filter_map(|&i| self.index(i)).collect()
+    }
+
+    /// Maps a position within `order` to the same position within the mapped order, which is
+    /// shifted back by the unrestored tracks before it.
+    fn position(&self, order: &[usize], position: usize) -> usize {
+        order
+            .iter()
+            .take(position)
+            .filter(|&&i| self.index(i).is_some())
+            .count()
+    }
+}
+
+/// The parts of the last saved snapshot that are not tracked by the player's data.
+pub(super) struct SavedSnapshot {
+    voice_channel_id: Id<ChannelMarker>,
+    filters: Option<String>,
+}
+
+/// The position and pause state to resume a restored track from, once it has started.
+pub(super) struct PendingResume {
+    encoded: String,
+    position: Duration,
+    paused: bool,
+}
+
+impl RawPlayerData {
+    #[inline]
+    pub(super) const fn mark_snapshot_outdated(&mut self) {
+        self.snapshot_outdated = true;
+    }
+
+    #[inline]
+    pub(super) const fn mark_snapshot_queue_outdated(&mut self) {
+        self.snapshot_outdated = true;
+        self.snapshot_queue_outdated = true;
+    }
+
+    /// Takes a snapshot of this player's state, returning [`None`] if nothing has changed since
+    /// the last snapshot was taken.
+    ///
+    /// A snapshot is always taken while a track is playing, as its timestamp keeps advancing. The
+    /// queue is only included in the snapshot if it has been modified since the last snapshot was
+    /// taken.
+    pub fn take_snapshot(
+        &mut self,
+        guild_id: Id<GuildMarker>,
+        voice_channel_id: Id<ChannelMarker>,
+        filters: Option<&Filters>,
+    ) -> Result<Option<Snapshot>, SaveSnapshotError> {
+        let filters = filters.map(serde_json::to_string).transpose()?;
+        let effect_previous_filters = self
+            .effect
+            .as_ref()
+            .map(|e| serde_json::to_string(e.previous()))
+            .transpose()?;
+
+        let playing = self.queue.current().is_some() && !self.paused();
+        let unchanged = self
+            .saved_snapshot
+            .as_ref()
+            .is_some_and(|s| s.voice_channel_id == voice_channel_id && s.filters == filters);
+        if !self.snapshot_outdated && !playing && unchanged {
+            return Ok(None);
+        }
+
+        let queue = self
+            .snapshot_queue_outdated
+            .then(|| SnapshotQueue::new(self.queue.iter()))
+            .transpose()?;
+        self.snapshot_outdated = false;
+        self.snapshot_queue_outdated = false;
+        self.saved_snapshot = Some(SavedSnapshot {
+            voice_channel_id,
+            filters: filters.clone(),
+        });
+
+        let indexer = self.queue.indexer();
+        let indexer_mapping = match indexer {
+            Indexer::Standard => None,
+            Indexer::WeightedFair(indexer) => Some(indexer.order().to_vec()),
+            Indexer::Shuffled(indexer) => Some(indexer.indices().to_vec()),
+        };
+        let fair_starting_index = indexer.fair_starting_index();
+
+        Ok(Some(Snapshot {
+            guild_id,
+            voice_channel_id,
+            text_channel_id: self.text_channel_id,
+            queue_index: self.queue.index(),
+            repeat_mode: self.queue.repeat_mode(),
+            autoplay: self.queue.autoplay(),
+            indexer: self.queue.indexer_type(),
+            fair_starting_index,
+            indexer_mapping,
+            volume: self.volume,
+            pitch: self.pitch.clone(),
+            speed: self.speed(),
+            filters,
+            effect_profile: self.effect.as_ref().map(ActiveEffect::profile_i16),
+            effect_previous_filters,
+            paused: self.paused(),
+            timestamp: self.timestamp(),
+            queue,
+        }))
+    }
+
+    /// Restores the state of this player from a snapshot, with `tracks` being the decoded tracks
+    /// of [`Snapshot::encoded_tracks`].
+    ///
+    /// Tracks that could not be decoded are left out, with the queue position and the indexer
+    /// adjusted accordingly.
+    pub fn restore_snapshot(&mut self, snapshot: Snapshot, tracks: Vec<Option<TrackData>>) {
+        let queue = snapshot.queue.unwrap_or(SnapshotQueue {
+            playlists: Vec::new(),
+            tracks: Vec::new(),
+        });
+        let playlists = queue
+            .playlists
+            .into_iter()
+            .map(|p| (p.id, Arc::new(p.into_metadata())))
+            .collect::<HashMap<_, _>>();
+        let mut items = Vec::with_capacity(queue.tracks.len());
+        let mut remapping = Vec::with_capacity(queue.tracks.len());
+        for (t, data) in queue.tracks.into_iter().zip(tracks) {
+            let item =
+                data.zip(Id::new_checked(t.requester.cast_unsigned()))
+                    .map(|(data, requester)| {
+                        let track = match t.playlist.and_then(|p| playlists.get(&p)) {
+                            Some(playlist) => PlaylistAwareTrackData::new(data, playlist.clone()),
+                            None => data.into(),
+                        };
+                        QueueItem::new_enqueued_at(
+                            track,
+                            requester,
+                            into_millis_duration(t.enqueued),
+                        )
+                    });
+            remapping.push(item.is_some().then_some(items.len()));
+            items.extend(item);
+        }
+        let remapping = Remapping(remapping);
+
+        // positions are within the indexing order, which is the storage order for indexers without
+        // a persisted mapping
+        let order = snapshot
+            .indexer_mapping
+            .clone()
+            .unwrap_or_else(|| (0..remapping.0.len()).collect());
+        let current_restored = order
+            .get(snapshot.queue_index)
+            .is_none_or(|&i| remapping.index(i).is_some());
+        let queue_index = remapping.position(&order, snapshot.queue_index);
+        let fair_starting_index = snapshot
+            .fair_starting_index
+            .map(|i| remapping.position(&order, i));
+        let indexer_mapping = snapshot.indexer_mapping.map(|m| remapping.order(&m));
+
+        let queue = &mut self.queue;
+        queue.restore(items, queue_index, snapshot.repeat_mode);
+        queue.set_autoplay(snapshot.autoplay);
+        let starting_index = fair_starting_index.unwrap_or_else(|| queue.index());
+        let kind = snapshot.indexer;
+        match (kind.fairness(), indexer_mapping) {
+            (None, _) if !kind.shuffled() => {}
+            (None, mapping) => queue.restore_shuffled_indexer(mapping.unwrap_or_default()),
+            (Some(fairness), mapping) => queue.restore_weighted_fair_indexer(
+                starting_index,
+                mapping.unwrap_or_default(),
+                fairness,
+                kind.shuffled(),
+            ),
+        }
+
+        self.effect = snapshot.effect_profile.and_then(|profile| {
+            let previous =
+                serde_json::from_str(snapshot.effect_previous_filters.as_deref()?).ok()?;
+            ActiveEffect::from_parts(profile, previous)
+        });
+        self.volume = snapshot.volume;
+        self.pitch = snapshot.pitch;
+        self.text_channel_id = snapshot.text_channel_id;
+        self.track_timestamp.set_speed(snapshot.speed);
+        // the current track is now a different one if it could not be restored
+        if current_restored {
+            self.track_timestamp.seek_to(snapshot.timestamp);
+        }
+        self.track_timestamp.set_pause(snapshot.paused);
+        self.mark_snapshot_queue_outdated();
+    }
+
+    /// Resumes the current track of this player on a newly created Lavalink player, from where
+    /// it was last at.
+    pub async fn resume_on(
+        &mut self,
+        player: &PlayerContext,
+        filters: Option<Filters>,
+    ) -> LavalinkResult<()> {
+        let position = self.timestamp();
+        let paused = self.paused();
+        let track = self.queue.current().map(|t| t.data().encoded.clone());
+
+        self.pending_resume = track.as_ref().map(|encoded| PendingResume {
+            encoded: encoded.clone(),
+            position,
+            paused,
+        });
+
+        let update = UpdatePlayer {
+            track: track.map(|encoded| UpdatePlayerTrack {
+                encoded: Some(encoded),
+                ..Default::default()
+            }),
+            position: Some(u64::try_from(position.as_millis()).unwrap_or(u64::MAX)),
+            paused: Some(paused),
+            volume: Some(self.volume.get()),
+            filters,
+            ..Default::default()
+        };
+        player.update_player(&update, false).await?;
+        Ok(())
+    }
+
+    /// Re-syncs the track timestamp and the now playing message with `player`, as reported by the
+    /// Lavalink node after its session was resumed.
+    pub async fn resync_with(&mut self, player: &Player) -> UpdateNowPlayingMessageResult {
+        self.track_timestamp
+            .seek_to(Duration::from_millis(player.state.position));
+        self.track_timestamp.set_pause(player.paused);
+        self.mark_snapshot_outdated();
+        self.update_and_apply_now_playing_pause(player.paused).await
+    }
+
+    /// Resets the track timestamp as a track starts, resuming from a saved position instead if
+    /// the track is being resumed via [`Self::resume_on`].
+    ///
+    /// Returns whether the track was resumed from a restored snapshot, rather than started anew.
+    pub fn start_track_timestamp(&mut self, encoded: &str) -> bool {
+        self.track_timestamp.reset();
+        let Some(resume) = self.pending_resume.take().filter(|r| r.encoded == encoded) else {
+            return false;
+        };
+        self.track_timestamp.seek_to(resume.position);
+        self.track_timestamp.set_pause(resume.paused);
+        true
+    }
+}
+
+impl Lavalink {
+    pub fn iter_players(&self) -> impl Iterator<Item = PlayerContext> + use<'_> {
+        self.inner
+            .players
+            .iter()
+            .filter_map(|p| p.value().0.load().as_deref().cloned())
+    }
+
+    pub async fn save_snapshot(&self, player: &PlayerContext) -> Result<(), SaveSnapshotError> {
+        let guild_id = Id::new(player.guild_id.0);
+        let voice_channel_id = self.handle_for(guild_id).get_head().await?.channel_id();
+        let filters = player.get_player().await?.filters;
+
+        let data = player.data_unwrapped();
+        let Some(snapshot) =
+            data.write()
+                .await
+                .take_snapshot(guild_id, voice_channel_id, filters.as_ref())?
+        else {
+            return Ok(());
+        };
+        let has_queue = snapshot.queue.is_some();
+
+        if let Err(e) = snapshot.save(self.inner.data_unwrapped().db()).await {
+            let mut data_w = data.write().await;
+            if has_queue {
+                data_w.mark_snapshot_queue_outdated();
+            } else {
+                data_w.mark_snapshot_outdated();
+            }
+            drop(data_w);
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    /// Saves the snapshots of all players, logging any failures.
+    pub async fn save_all_snapshots(&self) {
+        // the players are collected first, so that no locks on them are held across awaits
+        let players = self.iter_players().collect::<Vec<_>>();
+        for player in players {
+            if let Err(error) = self.save_snapshot(&player).await {
+                tracing::warn!(guild_id = player.guild_id.0, %error, "saving player snapshot failed");
+            }
+        }
+    }
+
+    pub async fn delete_snapshot(&self, guild_id: Id<GuildMarker>) -> Result<(), sqlx::Error> {
+        Snapshot::delete(self.inner.data_unwrapped().db(), guild_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU16;
+
+    use lavalink_rs::model::track::{TrackData, TrackInfo};
+    use twilight_model::id::Id;
+
+    use super::{IndexerType, RawPlayerData, Remapping, Snapshot};
+
+    fn track(n: usize) -> TrackData {
+        TrackData {
+            encoded: n.to_string(),
+            info: TrackInfo {
+                title: format!("Track {n}"),
+                length: 60_000,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn player_with_queue(len: usize, index: usize) -> RawPlayerData {
+        let mut data = RawPlayerData::new(Id::new(1));
+        let queue = data.queue_mut();
+        queue.enqueue((0..len).map(|n| track(n).into()).collect(), Id::new(2));
+        *queue.index_mut() = index;
+        data
+    }
+
+    fn try_take_snapshot(data: &mut RawPlayerData) -> Option<Snapshot> {
+        data.take_snapshot(Id::new(1), Id::new(3), None)
+            .expect("snapshot must serialise")
+    }
+
+    fn take_snapshot(data: &mut RawPlayerData) -> Snapshot {
+        try_take_snapshot(data).expect("snapshot must be taken")
+    }
+
+    fn play_order(data: &RawPlayerData) -> Vec<String> {
+        let queue = data.queue();
+        queue
+            .iter_mapped_positions_and_items(0..queue.len())
+            .map(|(_, item)| item.data().encoded.clone())
+            .collect()
+    }
+
+    #[test]
+    fn remapping_leaves_out_unrestored_tracks() {
+        let remapping = Remapping(vec![Some(0), None, Some(1), Some(2)]);
+        let order = [2, 1, 3, 0];
+
+        assert_eq!(remapping.order(&order), [1, 2, 0]);
+        assert_eq!(remapping.position(&order, 0), 0);
+        assert_eq!(remapping.position(&order, 2), 1);
+        assert_eq!(remapping.position(&order, 4), 3);
+    }
+
+    #[test]
+    fn snapshot_round_trip() {
+        let mut data = player_with_queue(6, 2);
+        data.queue_mut().set_indexer_type(IndexerType::Shuffled);
+        let snapshot = take_snapshot(&mut data);
+        let tracks = data
+            .queue()
+            .iter()
+            .map(|t| Some(t.data().clone()))
+            .collect();
+
+        let mut restored = RawPlayerData::new(Id::new(1));
+        restored.restore_snapshot(snapshot, tracks);
+
+        assert_eq!(play_order(&restored), play_order(&data));
+        assert_eq!(restored.queue().index(), 2);
+        assert!(restored.queue().indexer_type() == IndexerType::Shuffled);
+    }
+
+    #[test]
+    fn restore_snapshot_skips_undecodable_tracks() {
+        let snapshot = take_snapshot(&mut player_with_queue(5, 3));
+        let mut tracks = (0..5).map(|n| Some(track(n))).collect::<Vec<_>>();
+        tracks[1] = None;
+
+        let mut restored = RawPlayerData::new(Id::new(1));
+        restored.restore_snapshot(snapshot, tracks);
+
+        assert_eq!(play_order(&restored), ["0", "2", "3", "4"]);
+        assert_eq!(restored.queue().index(), 2);
+        assert_eq!(
+            restored
+                .queue()
+                .current()
+                .map(|t| t.data().encoded.as_str()),
+            Some("3")
+        );
+    }
+
+    #[test]
+    fn snapshot_only_taken_when_outdated() {
+        let mut data = RawPlayerData::new(Id::new(1));
+        data.set_pause(true);
+
+        assert!(try_take_snapshot(&mut data).is_some());
+        assert!(try_take_snapshot(&mut data).is_none());
+
+        data.set_volume(NonZeroU16::new(50).expect("50 must be non-zero"));
+        assert!(try_take_snapshot(&mut data).is_some());
+        assert!(try_take_snapshot(&mut data).is_none());
+
+        let moved = data
+            .take_snapshot(Id::new(1), Id::new(4), None)
+            .expect("snapshot must serialise");
+        assert!(moved.is_some());
+    }
+}
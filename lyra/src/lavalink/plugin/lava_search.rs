@@ -1 +1,58 @@
+use http::Method;
+use lavalink_rs::{
+    client::LavalinkClient,
+    error::LavalinkResult,
+    model::{
+        GuildId,
+        track::{PlaylistData, TrackData},
+    },
+};
+use serde::Deserialize;
 
+/// The result of a search via the Lavasearch plugin.
+#[derive(Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct SearchResult {
+    /// The tracks found
+    pub tracks: Vec<TrackData>,
+    /// The albums found, without their tracks
+    pub albums: Vec<PlaylistData>,
+    /// The artists found, without their tracks
+    pub artists: Vec<PlaylistData>,
+    /// The playlists found, without their tracks
+    pub playlists: Vec<PlaylistData>,
+}
+
+pub trait LoadSearch {
+    /// Searches for tracks, albums, artists and playlists matching `query` via the Lavasearch
+    /// plugin, where `query` must be prefixed with the search source, such as `spsearch:`.
+    async fn load_search(
+        &self,
+        guild_id: impl Into<GuildId> + Send,
+        query: &str,
+    ) -> LavalinkResult<SearchResult>;
+}
+
+impl LoadSearch for LavalinkClient {
+    async fn load_search(
+        &self,
+        guild_id: impl Into<GuildId> + Send,
+        query: &str,
+    ) -> LavalinkResult<SearchResult> {
+        let node = self.get_node_for_guild(guild_id).await;
+        let uri = node.http.path_to_uri(
+            &format!(
+                "/loadsearch?query={}&types=track,album,artist,playlist",
+                urlencoding::encode(query)
+            ),
+            true,
+        )?;
+
+        let body = node.http.raw_request(Method::GET, uri, None::<&()>).await?;
+        // the plugin responds with no content if nothing was found
+        if body.trim().is_empty() {
+            return Ok(SearchResult::default());
+        }
+        Ok(serde_json::from_str(&body)?)
+    }
+}
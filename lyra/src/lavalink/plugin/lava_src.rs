@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -17,7 +17,7 @@ pub struct PluginTrackInfo {
     pub is_preview: bool,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct PluginPlaylistInfo {
     /// The type of the playlist
@@ -33,7 +33,7 @@ pub struct PluginPlaylistInfo {
     pub total_tracks: Option<usize>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub enum PlaylistType {
     /// The playlist is an album
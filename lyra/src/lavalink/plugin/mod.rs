@@ -1,2 +1,2 @@
-mod lava_search;
+pub(super) mod lava_search;
 pub(super) mod lava_src;
@@ -1,17 +1,82 @@
-#[tracing::instrument(err, skip_all)]
-async fn impl_ready(
-    lavalink: lavalink_rs::client::LavalinkClient,
-) -> crate::error::lavalink::ProcessResult {
-    lavalink.delete_all_player_contexts().await?;
+use lavalink_rs::{
+    client::LavalinkClient, model::http::ResumingState, node::Node, player_context::PlayerContext,
+};
+
+use crate::{
+    error::lavalink::ProcessResult,
+    lavalink::{
+        UnwrappedData,
+        model::{players_on, resume_or_delete_player, resume_players_on},
+    },
+};
+
+/// Enables resuming for a new session, so that a node keeps its players for a while should its
+/// websocket disconnect.
+async fn enable_resuming(
+    lavalink: &LavalinkClient,
+    node: &Node,
+    session_id: &str,
+) -> ProcessResult {
+    let timeout = lavalink.data_unwrapped().session_resume_timeout();
+    let state = ResumingState {
+        resuming: Some(timeout != 0),
+        timeout: Some(timeout),
+    };
+    node.http.set_resuming_state(session_id, &state).await?;
+    Ok(())
+}
 
+/// Re-syncs a player on a resumed session with the state the node reports for it.
+async fn resync_player(node: &Node, session_id: &str, player: &PlayerContext) -> ProcessResult {
+    let info = node.http.get_player(player.guild_id, session_id).await?;
+    player.update_player_data(info.clone())?;
+    player
+        .data_unwrapped()
+        .write()
+        .await
+        .resync_with(&info)
+        .await?;
+
+    tracing::info!("guild {} resynced player", player.guild_id.0);
     Ok(())
 }
 
+#[tracing::instrument(skip_all)]
+async fn impl_ready(lavalink: LavalinkClient, session_id: &str, resumed: bool) {
+    let Some(node) = lavalink
+        .nodes
+        .iter()
+        .find(|n| **n.session_id.load() == session_id)
+    else {
+        return;
+    };
+
+    if resumed {
+        // the node kept its players, so only the state that may have drifted while it was
+        // disconnected is re-synced, recreating the players that can no longer be found instead.
+        for player in players_on(&lavalink, node.id) {
+            if let Err(error) = resync_player(node, session_id, &player).await {
+                let guild_id = player.guild_id.0;
+                tracing::warn!(guild_id, ?error, "resyncing player failed");
+                resume_or_delete_player(&lavalink, player).await;
+            }
+        }
+        return;
+    }
+
+    if let Err(error) = enable_resuming(&lavalink, node, session_id).await {
+        tracing::warn!(node = node.id, ?error, "enabling session resuming failed");
+    }
+    // only the players on the (restarted) node that became ready need resuming, as the players on
+    // other nodes are still intact.
+    resume_players_on(&lavalink, node.id).await;
+}
+
 #[lavalink_rs::hook]
 pub(super) async fn ready(
     lavalink: lavalink_rs::client::LavalinkClient,
-    _: String,
-    _: &lavalink_rs::model::events::Ready,
+    session_id: String,
+    event: &lavalink_rs::model::events::Ready,
 ) {
-    let _ = impl_ready(lavalink).await;
+    impl_ready(lavalink, &session_id, event.resumed).await;
 }
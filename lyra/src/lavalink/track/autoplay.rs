@@ -0,0 +1,145 @@
+use std::collections::HashSet;
+
+use lavalink_rs::{
+    client::LavalinkClient,
+    model::{
+        GuildId,
+        track::{Track, TrackData, TrackLoadData},
+    },
+    player_context::PlayerContext,
+};
+use twilight_model::id::Id;
+
+use crate::{
+    command::{
+        common::{DEEZER_ENABLED, SPOTIFY_ENABLED},
+        require::cleanup_now_playing_message_and_play,
+    },
+    core::{
+        konst::misc::{AUTOPLAY_HISTORY_LOOKBACK, AUTOPLAY_TRACKS_LIMIT},
+        model::{CacheAware, DatabaseAware},
+    },
+    error::lavalink::{ProcessError, ProcessResult},
+    lavalink::{CorrectTrackInfo, HistoryEntry, PlaylistAwareTrackData, UnwrappedData},
+};
+
+pub(super) fn into_tracks(loaded: Track) -> Vec<TrackData> {
+    match loaded.data {
+        Some(TrackLoadData::Track(track)) => vec![track],
+        Some(TrackLoadData::Playlist(playlist)) => playlist.tracks,
+        Some(TrackLoadData::Search(tracks)) => tracks,
+        Some(TrackLoadData::Error(_)) | None => Vec::new(),
+    }
+}
+
+fn youtube_mix(identifier: &str) -> String {
+    format!("https://www.youtube.com/watch?v={identifier}&list=RD{identifier}")
+}
+
+/// Resolves a query for tracks related to `seed`.
+///
+/// Tracks from Spotify, Deezer and Youtube are directly used as the seed of a recommendation
+/// query of the same source. Tracks from other sources are first matched against one of those
+/// sources, by ISRC if possible, or by their author and title otherwise.
+async fn related_tracks_query(
+    lavalink: &LavalinkClient,
+    guild_id: GuildId,
+    seed: &TrackData,
+) -> Result<Option<String>, ProcessError> {
+    let info = &seed.info;
+    let query = match info.source_name.as_str() {
+        "spotify" if *SPOTIFY_ENABLED => format!("sprec:seed_tracks={}", info.identifier),
+        "deezer" if *DEEZER_ENABLED => format!("dzrec:{}", info.identifier),
+        "youtube" => youtube_mix(&info.identifier),
+        _ => {
+            let (search, source) = match info.isrc {
+                Some(ref isrc) if *DEEZER_ENABLED => (format!("dzisrc:{isrc}"), "deezer"),
+                _ => (
+                    format!(
+                        "ytsearch:{} - {}",
+                        info.corrected_author(),
+                        info.corrected_title()
+                    ),
+                    "youtube",
+                ),
+            };
+            let matched = into_tracks(lavalink.load_tracks(guild_id, &search).await?);
+            let Some(matched) = matched.first().filter(|t| t.info.source_name == source) else {
+                return Ok(None);
+            };
+            match source {
+                "deezer" => format!("dzrec:{}", matched.info.identifier),
+                _ => youtube_mix(&matched.info.identifier),
+            }
+        }
+    };
+    Ok(Some(query))
+}
+
+/// Enqueues tracks related to `seed`, the last track of the queue which has just ended, and
+/// plays the first one of them.
+///
+/// Tracks that are already in the queue or have recently been played are skipped. The tracks
+/// are enqueued as requested by the bot itself.
+pub(super) async fn enqueue_related_tracks(
+    lavalink: &LavalinkClient,
+    player: &PlayerContext,
+    seed: &TrackData,
+) -> ProcessResult {
+    let guild_id = player.guild_id;
+    let Some(query) = related_tracks_query(lavalink, guild_id, seed).await? else {
+        tracing::debug!(?guild_id, "no related tracks query for autoplay");
+        return Ok(());
+    };
+
+    let cdata = lavalink.data_unwrapped();
+    let recent = HistoryEntry::fetch(
+        cdata.db(),
+        Id::new(guild_id.0),
+        0,
+        AUTOPLAY_HISTORY_LOOKBACK,
+    )
+    .await?;
+    let data = player.data_unwrapped();
+    let mut excluded = recent
+        .iter()
+        .filter_map(|e| e.uri().map(ToOwned::to_owned))
+        .collect::<HashSet<_>>();
+    excluded.extend(
+        data.read()
+            .await
+            .queue()
+            .iter()
+            .filter_map(|t| t.data().info.uri.clone()),
+    );
+
+    let tracks = into_tracks(lavalink.load_tracks(guild_id, &query).await?)
+        .into_iter()
+        .filter(|t| {
+            t.info.identifier != seed.info.identifier
+                && t.info.uri.as_ref().is_none_or(|u| !excluded.contains(u))
+        })
+        .take(AUTOPLAY_TRACKS_LIMIT)
+        .map(PlaylistAwareTrackData::from)
+        .collect::<Vec<_>>();
+    let Some(requester) = cdata.cache().current_user().map(|u| u.id) else {
+        return Ok(());
+    };
+    if tracks.is_empty() {
+        tracing::debug!(?guild_id, %query, "no related tracks found for autoplay");
+        return Ok(());
+    }
+
+    let mut data_w = data.write().await;
+    // the queue may have been changed while the related tracks were being loaded
+    if !data_w.queue().autoplay() || data_w.queue().current().is_some() {
+        return Ok(());
+    }
+    let queue = data_w.queue_mut();
+    queue.enqueue(tracks, requester);
+    if let Some(index) = queue.current_index() {
+        cleanup_now_playing_message_and_play(player, &*cdata, index, &mut data_w).await?;
+    }
+    drop(data_w);
+    Ok(())
+}
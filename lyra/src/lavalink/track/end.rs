@@ -1,9 +1,10 @@
 use lavalink_rs::{client::LavalinkClient, model::events::TrackEnd};
+use twilight_model::id::Id;
 
 use crate::{
     command::require::cleanup_now_playing_message_and_play,
     error::lavalink::ProcessResult,
-    lavalink::{CorrectTrackInfo, UnwrappedData},
+    lavalink::{CorrectTrackInfo, Event, UnwrappedData},
 };
 
 #[tracing::instrument(err, skip_all, name = "track_end")]
@@ -32,14 +33,22 @@ pub(super) async fn impl_end(
     } else {
         tracing::debug!(?guild_id, "track ended normally");
         let mut data_w = data.write().await;
+        data_w.end_stuck_recovery();
+        data_w.end_recorded_play();
 
         let cdata = &*lavalink.data_unwrapped();
         data_w.cleanup_now_playing_message(cdata).await;
 
         let queue = data_w.queue_mut();
         queue.advance();
+        // polls on the current track are voided, be the queue advanced to a next track or
+        // to autoplayed tracks.
+        cdata.dispatch_to(Id::new(guild_id.0), Event::QueueAdvance);
         if let Some(index) = queue.current_index() {
             cleanup_now_playing_message_and_play(&player, cdata, index, &mut data_w).await?;
+        } else if queue.autoplay() {
+            drop(data_w);
+            return super::autoplay::enqueue_related_tracks(&lavalink, &player, &event.track).await;
         }
         drop(data_w);
     }
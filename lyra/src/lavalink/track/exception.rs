@@ -1,6 +1,14 @@
 use lavalink_rs::{client::LavalinkClient, model::events::TrackException};
+use twilight_model::id::Id;
 
-use crate::{core::model::HttpAware, error::lavalink::ProcessResult, lavalink::UnwrappedData};
+use crate::{
+    command::require::cleanup_now_playing_message_and_play,
+    core::model::HttpAware,
+    error::lavalink::ProcessResult,
+    lavalink::{CorrectTrackInfo, UnwrappedData},
+};
+
+use super::{fallback, stuck::skip};
 
 #[tracing::instrument(err, skip_all, name = "track_exception")]
 pub(super) async fn impl_exception(
@@ -17,27 +25,90 @@ pub(super) async fn impl_exception(
 
     let data = player.data_unwrapped();
     let cdata = lavalink.data_unwrapped();
-    data.write()
-        .await
-        .cleanup_now_playing_message(&*cdata)
-        .await;
+    let fallback_enabled = cdata
+        .guild_configs()
+        .get(Id::new(guild_id.0))
+        .await?
+        .recovery()
+        .fallback();
+
+    let data_r = data.read().await;
+    let is_current = data_r
+        .queue()
+        .current()
+        .is_some_and(|t| t.data().encoded == event.track.encoded);
+    if fallback_enabled && is_current {
+        // CORRECTNESS: the current track is ending, as it failed to play. The queue is advanced
+        // here instead, once it is known whether the track can be substituted.
+        data_r.queue().disable_advancing();
+    }
+    drop(data_r);
 
-    let note = if cdata.oauth_enabled() {
-        "contact the bot developers to report the issue."
+    let substitute = if fallback_enabled && is_current {
+        fallback::resolve(&lavalink, guild_id, &event.track)
+            .await
+            .unwrap_or_else(|error| {
+                tracing::warn!(?error, "resolving substitute track failed");
+                None
+            })
     } else {
-        "contact the bot host to **enable YouTube OAuth**."
+        None
+    };
+
+    let mut data_w = data.write().await;
+    data_w.cleanup_now_playing_message(&*cdata).await;
+    let text_channel_id = data_w.text_channel_id();
+
+    // the queue may have been changed while the substitute track was being resolved
+    let still_current = is_current
+        && data_w
+            .queue()
+            .current()
+            .is_some_and(|t| t.data().encoded == event.track.encoded);
+    let Some(substitute) = substitute.filter(|_| still_current) else {
+        if fallback_enabled && still_current {
+            skip(&player, &*cdata, &mut data_w).await?;
+        }
+        drop(data_w);
+
+        let note = if cdata.oauth_enabled() {
+            "contact the bot developers to report the issue."
+        } else {
+            "contact the bot host to **enable YouTube OAuth**."
+        };
+
+        cdata
+            .http()
+            .create_message(text_channel_id)
+            .content(&format!(
+                "💔**`ー`** ~~`{}`~~ (Unable to play track)\n\
+                -# Please ensure this track is available. \
+                If you believe it should be playable, {}",
+                event.track.info.title, note
+            ))
+            .await?;
+        return Ok(());
     };
 
+    let content = format!(
+        "🔀 ~~`{}`~~ could not be played, so `{}` from **{}** was played instead.",
+        event.track.info.corrected_title(),
+        substitute.info.corrected_title(),
+        fallback::source_name(&substitute),
+    );
+    let queue = data_w.queue_mut();
+    queue
+        .current_mut()
+        .expect("current track must exist")
+        .replace_track(substitute);
+    let index = queue.current_index().expect("current track must exist");
+    cleanup_now_playing_message_and_play(&player, &*cdata, index, &mut data_w).await?;
+    drop(data_w);
+
     cdata
         .http()
-        .create_message(data.read().await.text_channel_id())
-        .content(&format!(
-            "💔**`ー`** ~~`{}`~~ (Unable to play track)\n\
-            -# Please ensure this track is available. \
-            If you believe it should be playable, {}",
-            event.track.info.title, note
-        ))
+        .create_message(text_channel_id)
+        .content(&content)
         .await?;
-
     Ok(())
 }
@@ -0,0 +1,84 @@
+use std::{env, sync::LazyLock};
+
+use lavalink_rs::{
+    client::LavalinkClient,
+    model::{GuildId, track::TrackData},
+};
+
+use crate::{
+    command::common::DEEZER_ENABLED, error::lavalink::ProcessError, lavalink::CorrectTrackInfo,
+};
+
+use super::autoplay::into_tracks;
+
+const ISRC_PLACEHOLDER: &str = "%ISRC%";
+const QUERY_PLACEHOLDER: &str = "%QUERY%";
+
+// we cannot afford to parse the env var without any memoisation, as this will be called on
+// every track that needs a substitute.
+/// The queries to search for substitute tracks with, in order of preference, much like the
+/// `providers` of `LavaSrc`: `%ISRC%` is replaced by the ISRC of the track, skipping the query if
+/// the track has none, and `%QUERY%` by its author and title.
+///
+/// These are read from `FALLBACK_PROVIDERS` as a comma-separated list if set, defaulting to by
+/// ISRC on Deezer if enabled, then by author and title on `SoundCloud`.
+static PROVIDERS: LazyLock<Box<[String]>> = LazyLock::new(|| {
+    if let Ok(providers) = env::var("FALLBACK_PROVIDERS")
+        && !providers.trim().is_empty()
+    {
+        return providers
+            .split(',')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(String::from)
+            .collect();
+    }
+
+    let mut providers = Vec::new();
+    if *DEEZER_ENABLED {
+        providers.push(format!("dzisrc:{ISRC_PLACEHOLDER}"));
+    }
+    providers.push(format!("scsearch:{QUERY_PLACEHOLDER}"));
+    providers.into()
+});
+
+fn fallback_queries(track: &TrackData) -> impl Iterator<Item = String> + use<'_> {
+    let info = &track.info;
+    let query = format!("{} - {}", info.corrected_author(), info.corrected_title());
+    PROVIDERS.iter().filter_map(move |provider| {
+        if provider.contains(ISRC_PLACEHOLDER) {
+            let isrc = info.isrc.as_deref()?;
+            return Some(provider.replace(ISRC_PLACEHOLDER, isrc));
+        }
+        Some(provider.replace(QUERY_PLACEHOLDER, &query))
+    })
+}
+
+/// Resolves the same track as `track` from a source other than its own, picking the result
+/// closest in length to `track` from the first provider with any results.
+pub(super) async fn resolve(
+    lavalink: &LavalinkClient,
+    guild_id: GuildId,
+    track: &TrackData,
+) -> Result<Option<TrackData>, ProcessError> {
+    for query in fallback_queries(track) {
+        let found = into_tracks(lavalink.load_tracks(guild_id, &query).await?)
+            .into_iter()
+            .filter(|t| !t.info.is_stream && t.info.source_name != track.info.source_name)
+            .min_by_key(|t| t.info.length.abs_diff(track.info.length));
+        if found.is_some() {
+            return Ok(found);
+        }
+    }
+    Ok(None)
+}
+
+pub(super) fn source_name(track: &TrackData) -> &str {
+    match track.info.source_name.as_str() {
+        "deezer" => "Deezer",
+        "soundcloud" => "SoundCloud",
+        "youtube" => "YouTube",
+        "spotify" => "Spotify",
+        other => other,
+    }
+}
@@ -1,5 +1,7 @@
+mod autoplay;
 mod end;
 mod exception;
+mod fallback;
 mod start;
 mod stuck;
 
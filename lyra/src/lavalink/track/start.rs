@@ -1,4 +1,5 @@
 use lavalink_rs::{client::LavalinkClient, model::events::TrackStart};
+use twilight_model::id::Id;
 
 use crate::{
     core::model::{DatabaseAware, OwnedHttpAware},
@@ -24,28 +25,28 @@ pub(super) async fn impl_start(
 
         return Ok(());
     };
-    player
-        .data_unwrapped()
-        .write()
-        .await
-        .reset_track_timestamp();
-
     let data = player.data_unwrapped();
+    let lavalink_data = lavalink.data_unwrapped();
+    let mut data_w = data.write().await;
+    let resumed = data_w.start_track_timestamp(&event.track.encoded);
+    if !resumed {
+        data_w
+            .record_current_play(lavalink_data.db(), Id::new(guild_id.0))
+            .await;
+    }
+    drop(data_w);
+
     let data_r = data.read().await;
     let queue = data_r.queue();
     let Some(track) = queue.current() else {
         return Ok(());
     };
 
-    let lavalink_data = lavalink.data_unwrapped();
-    let rec = sqlx::query!(
-        "SELECT now_playing FROM guild_configs WHERE id = $1;",
-        guild_id.0.cast_signed()
-    )
-    .fetch_one(lavalink_data.db())
-    .await?;
-
-    if !rec.now_playing {
+    let config = lavalink_data
+        .guild_configs()
+        .get(Id::new(guild_id.0))
+        .await?;
+    if !config.now_playing() {
         return Ok(());
     }
 
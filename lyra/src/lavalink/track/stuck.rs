@@ -1,6 +1,117 @@
-use lavalink_rs::{client::LavalinkClient, model::events::TrackStuck};
+use std::time::Duration;
 
-use crate::{core::model::HttpAware, error::lavalink::ProcessResult, lavalink::UnwrappedData};
+use lavalink_rs::{
+    client::LavalinkClient,
+    error::LavalinkResult,
+    model::{events::TrackStuck, track::TrackData},
+    player_context::PlayerContext,
+};
+use lyra_ext::pretty::duration_display::DurationDisplay;
+use twilight_model::id::Id;
+
+use crate::{
+    command::require::cleanup_now_playing_message_and_play,
+    core::model::{CacheAware, DatabaseAware, HttpAware},
+    error::lavalink::ProcessResult,
+    lavalink::{CorrectTrackInfo, PlayerDataWrite, RecoveryAttempt, RecoveryStep, UnwrappedData},
+};
+
+use super::fallback;
+
+/// Replays the current track from `timestamp`.
+async fn retry(
+    player: &PlayerContext,
+    data_w: &mut PlayerDataWrite<'_>,
+    timestamp: Duration,
+) -> LavalinkResult<()> {
+    data_w.seek_to(timestamp);
+
+    // CORRECTNESS: the current track is about to be replaced by itself, so it is ending.
+    data_w.queue().disable_advancing();
+    data_w.resume_on(player, None).await
+}
+
+/// Skips the current track, playing the next track if there is one.
+pub(super) async fn skip(
+    player: &PlayerContext,
+    cx: &(impl CacheAware + Sync),
+    data_w: &mut PlayerDataWrite<'_>,
+) -> LavalinkResult<()> {
+    let queue = data_w.queue_mut();
+    queue.downgrade_repeat_mode();
+
+    // CORRECTNESS: the current track is about to be replaced or stopped, so it is ending.
+    queue.disable_advancing();
+
+    queue.advance();
+    if let Some(index) = queue.current_index() {
+        cleanup_now_playing_message_and_play(player, cx, index, data_w).await?;
+    } else {
+        player.stop_now().await?;
+        data_w.delete_now_playing_message().await;
+    }
+    Ok(())
+}
+
+/// Takes `step` to recover the current track after it got stuck for the `failures`-th time,
+/// returning the line to report in the status message and the detail to record.
+async fn apply_step(
+    player: &PlayerContext,
+    cx: &(impl CacheAware + Sync),
+    data_w: &mut PlayerDataWrite<'_>,
+    step: RecoveryStep,
+    substitute: Option<TrackData>,
+    timestamp: Duration,
+    failures: u8,
+) -> LavalinkResult<(String, Option<String>)> {
+    let applied = match (step, substitute) {
+        (RecoveryStep::Skip, _) => {
+            skip(player, cx, data_w).await?;
+            (
+                format!("-# ⏭️ Skipped after getting stuck `{failures}` time(s)."),
+                None,
+            )
+        }
+        (RecoveryStep::Fallback, Some(substitute)) => {
+            let detail = format!(
+                "{} from {}",
+                substitute.info.corrected_title(),
+                fallback::source_name(&substitute)
+            );
+            let line = format!(
+                "-# 🔀 Substituted with `{}` from **{}**, from `{}`.",
+                substitute.info.corrected_title(),
+                fallback::source_name(&substitute),
+                timestamp.pretty_display(),
+            );
+            data_w
+                .queue_mut()
+                .current_mut()
+                .expect("current track must exist")
+                .replace_track(substitute);
+            retry(player, data_w, timestamp).await?;
+            (line, Some(detail))
+        }
+        (RecoveryStep::Fallback, None) => {
+            retry(player, data_w, timestamp).await?;
+            (
+                format!(
+                    "-# 🔁 No substitute found from other sources; Retrying from `{}`.",
+                    timestamp.pretty_display()
+                ),
+                Some(String::from("No substitute found")),
+            )
+        }
+        (RecoveryStep::Retry, _) => {
+            retry(player, data_w, timestamp).await?;
+            (
+                format!("-# 🔁 Retrying from `{}`.", timestamp.pretty_display()),
+                None,
+            )
+        }
+    };
+    Ok(applied)
+}
 
 #[tracing::instrument(err, skip_all, name = "track_stuck")]
 pub(super) async fn impl_stuck(
@@ -14,14 +125,98 @@ pub(super) async fn impl_stuck(
     let Some(player) = lavalink.get_player_context(guild_id) else {
         return Ok(());
     };
+    let cdata = lavalink.data_unwrapped();
+    let policy = cdata
+        .guild_configs()
+        .get(Id::new(guild_id.0))
+        .await?
+        .recovery();
+
+    let data = player.data_unwrapped();
+    let mut data_w = data.write().await;
+    let (Some(current), position) = data_w.queue().current_and_position() else {
+        return Ok(());
+    };
+    let track = current.data().clone();
+    // the track may have already been skipped or replaced since it got stuck
+    if track.encoded != event.track.encoded {
+        return Ok(());
+    }
+    let failures = data_w.stuck_recovery_at(position).fail();
+    drop(data_w);
+
+    let step = policy.step_for(failures);
+    let title = track.info.corrected_title();
+    tracing::info!(failures, step = step.name(), "recovering stuck track");
+
+    let substitute = if step == RecoveryStep::Fallback {
+        fallback::resolve(&lavalink, guild_id, &track)
+            .await
+            .unwrap_or_else(|error| {
+                tracing::warn!(?error, "resolving substitute track failed");
+                None
+            })
+    } else {
+        None
+    };
+    let timestamp = Duration::from_millis(player.get_player().await?.state.position);
+
+    let mut data_w = data.write().await;
+    // the queue may have been changed while the substitute track was being resolved
+    if data_w
+        .queue()
+        .current()
+        .is_none_or(|t| t.data().encoded != track.encoded)
+    {
+        return Ok(());
+    }
+
+    let (line, detail) = apply_step(
+        &player,
+        &*cdata,
+        &mut data_w,
+        step,
+        substitute,
+        timestamp,
+        failures,
+    )
+    .await?;
+
+    let channel_id = data_w.text_channel_id();
+    let recovery = data_w.stuck_recovery_at(position);
+    if failures == 1 {
+        recovery.report(format!("🌀 Playback of `{title}` got stuck."));
+    }
+    let content = recovery.report(line);
+    let message_id = recovery.message_id();
 
-    let channel_id = player.data_unwrapped().read().await.text_channel_id();
-    lavalink
-        .data_unwrapped()
-        .http()
-        .create_message(channel_id)
-        .content("🌀 Playback interrupted. Please wait or try using the bot again later.")
-        .await?;
+    let http = cdata.http();
+    if let Some(message_id) = message_id {
+        http.update_message(channel_id, message_id)
+            .content(Some(&content))
+            .await?;
+    } else {
+        let message = http
+            .create_message(channel_id)
+            .content(&content)
+            .await?
+            .model()
+            .await?;
+        recovery.set_message_id(message.id);
+    }
+    if step == RecoveryStep::Skip {
+        data_w.end_stuck_recovery();
+    }
+    drop(data_w);
 
+    RecoveryAttempt::record(
+        cdata.db(),
+        Id::new(guild_id.0),
+        title,
+        failures,
+        step,
+        detail.as_deref(),
+    )
+    .await?;
     Ok(())
 }
@@ -6,7 +6,9 @@ use std::{
     },
 };
 
-use lavalink_rs::{client::LavalinkClient, model::client::NodeDistributionStrategy};
+use lavalink_rs::{
+    client::LavalinkClient, model::client::NodeDistributionStrategy, node::NodeBuilder,
+};
 use log::LevelFilter;
 use sqlx::{
     ConnectOptions, migrate,
@@ -25,13 +27,19 @@ use twilight_model::{
         payload::outgoing::update_presence::UpdatePresencePayload,
         presence::{Activity, ActivityType, MinimalActivity, Status},
     },
-    id::{Id, marker::UserMarker},
 };
 
 use crate::{
     LavalinkAware,
-    core::banner::banner,
-    lavalink::{ClientData, handlers},
+    component::{config::GuildConfigCache, tuning::EqualiserPresets},
+    core::{
+        banner::banner,
+        konst::misc::{
+            EQUALISER_PRESET_FILES_RELOAD_INTERVAL, LAVALINK_NODE_HEALTH_CHECK_INTERVAL,
+            PLAYER_SNAPSHOT_INTERVAL,
+        },
+    },
+    lavalink::{ClientData, handlers, select_node},
 };
 
 use super::{
@@ -80,7 +88,7 @@ fn build_shard_config(token: String) -> ShardConfig {
 pub async fn start() -> Result<(), StartError> {
     tracing::debug!("began starting the bot");
 
-    let mut config = Config::new();
+    let mut config = Config::new()?;
     let db = PgPoolOptions::new()
         .max_connections(20)
         .connect_with(
@@ -94,22 +102,44 @@ pub async fn start() -> Result<(), StartError> {
     let http = build_http_client(token.clone());
 
     let cache = Arc::new(InMemoryCache::new());
-    let data = ClientData::new(http.clone(), cache.clone(), db.clone());
+    let guild_configs = GuildConfigCache::new(db.clone());
+    let equaliser_presets = EqualiserPresets::load();
     let user_id = http.current_user().await?.model().await?.id;
 
-    let (lavalink_host, lavalink_pwd) = config.into_lavalink_host_and_pwd();
-    let lavalink = build_lavalink_client(lavalink_host, lavalink_pwd, user_id, data).await;
+    let (nodes, node_regions) = config
+        .into_lavalink_nodes()
+        .into_iter()
+        .map(|n| n.into_builder_and_regions(user_id))
+        .unzip::<_, _, Vec<_>, Vec<_>>();
+    let data = ClientData::new(
+        http.clone(),
+        cache.clone(),
+        db.clone(),
+        guild_configs.clone(),
+        node_regions.into(),
+    );
+    let lavalink = build_lavalink_client(nodes, data).await;
 
     let shards = build_and_split_shards(token, &http).await?;
     let shards_len = shards.len();
     let mut senders = Vec::with_capacity(shards_len);
     let mut tasks = Vec::with_capacity(shards_len);
-    let bot = Arc::new(BotState::new(db, http, cache, lavalink));
+    let bot = Arc::new(BotState::new(
+        db,
+        http,
+        cache,
+        lavalink,
+        guild_configs,
+        equaliser_presets,
+    ));
 
     for shard in shards {
         senders.push(shard.sender());
         tasks.push(tokio::spawn(handle_gateway_events(shard, bot.clone())));
     }
+    tokio::spawn(save_player_snapshots(bot.clone()));
+    tokio::spawn(check_lavalink_node_health(bot.clone()));
+    tokio::spawn(reload_equaliser_presets(bot.clone()));
 
     println!("{}", banner());
     Ok(wait_until_shutdown(senders, tasks, &bot).await?)
@@ -127,22 +157,9 @@ async fn build_and_split_shards(
 }
 
 #[tracing::instrument(skip_all, name = "lavalink")]
-async fn build_lavalink_client(
-    hostname: String,
-    password: String,
-    user_id: Id<UserMarker>,
-    data: ClientData,
-) -> Lavalink {
+async fn build_lavalink_client(nodes: Vec<NodeBuilder>, data: ClientData) -> Lavalink {
     let events = handlers();
-
-    let nodes = Vec::from([lavalink_rs::node::NodeBuilder {
-        hostname,
-        password,
-        user_id: user_id.into(),
-        ..Default::default()
-    }]);
-
-    let strategy = NodeDistributionStrategy::new();
+    let strategy = NodeDistributionStrategy::custom(select_node);
     let client = LavalinkClient::new_with_data(events, nodes, strategy, data.into()).await;
     client.into()
 }
@@ -182,6 +199,33 @@ fn process_gateway_events(shard: &Shard, event: Event, bot: Arc<BotState>) {
     ));
 }
 
+#[tracing::instrument(skip_all, name = "snapshot")]
+async fn save_player_snapshots(bot: Arc<BotState>) {
+    let mut interval = tokio::time::interval(PLAYER_SNAPSHOT_INTERVAL);
+    while !SHUTDOWN.load(Ordering::Relaxed) {
+        interval.tick().await;
+        bot.lavalink().save_all_snapshots().await;
+    }
+}
+
+#[tracing::instrument(skip_all, name = "nodes")]
+async fn check_lavalink_node_health(bot: Arc<BotState>) {
+    let mut interval = tokio::time::interval(LAVALINK_NODE_HEALTH_CHECK_INTERVAL);
+    while !SHUTDOWN.load(Ordering::Relaxed) {
+        interval.tick().await;
+        bot.lavalink().check_node_health().await;
+    }
+}
+
+#[tracing::instrument(skip_all, name = "presets")]
+async fn reload_equaliser_presets(bot: Arc<BotState>) {
+    let mut interval = tokio::time::interval(EQUALISER_PRESET_FILES_RELOAD_INTERVAL);
+    while !SHUTDOWN.load(Ordering::Relaxed) {
+        interval.tick().await;
+        bot.equaliser_presets().reload_if_changed().await;
+    }
+}
+
 #[tracing::instrument]
 async fn wait_for_signal() -> Result<(), WaitForSignalError> {
     #[cfg(target_family = "unix")]
@@ -217,6 +261,9 @@ async fn wait_until_shutdown(
     SHUTDOWN.store(true, Ordering::Relaxed);
     tracing::info!("gracefully shutting down...");
 
+    tracing::debug!("saving all player snapshots...");
+    bot.lavalink().save_all_snapshots().await;
+
     tracing::debug!("deleting all now playing messages...");
     for data in bot.lavalink().iter_player_data() {
         data.write().await.delete_now_playing_message().await;
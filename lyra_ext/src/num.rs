@@ -301,7 +301,7 @@ mod test {
     #[case(0xFFFF_FFFF, hexf64!("0xFFFF_FFFF.p0"))]
     fn usize_as_f64_trivial(#[case] input: usize, #[case] expected: f64) {
         let l = usize_as_f64(input);
-        assert!((l - expected).abs() < f64::EPSILON, "l={l}\nr={expected}",);
+        assert!((l - expected).abs() < f64::EPSILON, "l={l}\nr={expected}");
     }
 
     #[cfg(target_pointer_width = "64")]
@@ -310,7 +310,7 @@ mod test {
     #[case(0x20_0000_0000_0000, hexf64!("0x20_0000_0000_0000.p0"))]
     fn usize_as_f64_trivial_x64(#[case] input: usize, #[case] expected: f64) {
         let l = usize_as_f64(input);
-        assert!((l - expected).abs() < f64::EPSILON, "l={l}\nr={expected}",);
+        assert!((l - expected).abs() < f64::EPSILON, "l={l}\nr={expected}");
     }
 
     // tests `clippy::cast_precision_loss`
@@ -327,7 +327,7 @@ mod test {
     #[case(0xFFFF_FFFF_FFFF_FFFF, hexf64!("0x8000_0000_0000_0000.p1"))]
     fn usize_as_f64_precision_losing_x64(#[case] input: usize, #[case] expected: f64) {
         let l = usize_as_f64(input);
-        assert!((l - expected).abs() < f64::EPSILON, "l={l}\nr={expected}",);
+        assert!((l - expected).abs() < f64::EPSILON, "l={l}\nr={expected}");
     }
 
     #[rstest]
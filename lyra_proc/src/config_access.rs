@@ -22,7 +22,7 @@ pub fn impl_view_access_ids(Args(categories): &Args) -> TokenStream {
 
     let access_queries = column_names
         .clone()
-        .map(|t| format!("SELECT id FROM {t} WHERE guild = $1;",));
+        .map(|t| format!("SELECT id FROM {t} WHERE guild = $1;"));
 
     let mode_queries = format!(
         "SELECT {} FROM guild_configs WHERE id = $1",
@@ -2,7 +2,6 @@ extern crate proc_macro;
 
 mod command;
 mod config_access;
-mod equaliser_preset;
 mod model;
 
 use proc_macro::TokenStream;
@@ -41,10 +40,3 @@ pub fn view_access_ids(attr: TokenStream) -> TokenStream {
 
     config_access::impl_view_access_ids(&args)
 }
-
-#[proc_macro]
-pub fn read_equaliser_presets_as(ty: TokenStream) -> TokenStream {
-    let ty = syn::parse_macro_input!(ty as syn::Ident);
-
-    equaliser_preset::impl_read_equaliser_presets_as(&ty)
-}
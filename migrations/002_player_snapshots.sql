@@ -0,0 +1,35 @@
+CREATE TABLE IF NOT EXISTS player_snapshots (
+    guild bigint primary key references guild_configs(id) ON DELETE CASCADE,
+    voice_channel bigint NOT NULL,
+    text_channel bigint NOT NULL,
+    queue_index bigint NOT NULL,
+    repeat_mode smallint NOT NULL,
+    indexer smallint NOT NULL,
+    fair_starting_index bigint,
+    indexer_mapping bigint[],
+    volume integer NOT NULL,
+    pitch_multiplier double precision NOT NULL,
+    pitch_half_tone_shifts bigint NOT NULL,
+    speed double precision NOT NULL,
+    filters text,
+    paused boolean NOT NULL,
+    track_timestamp bigint NOT NULL
+);
+CREATE TABLE IF NOT EXISTS player_snapshot_playlists (
+    guild bigint references player_snapshots(guild) ON DELETE CASCADE,
+    id integer NOT NULL,
+    uri text NOT NULL,
+    name text NOT NULL,
+    selected_track bigint,
+    plugin_info text,
+    PRIMARY KEY (guild, id)
+);
+CREATE TABLE IF NOT EXISTS player_snapshot_tracks (
+    guild bigint references player_snapshots(guild) ON DELETE CASCADE,
+    position bigint NOT NULL,
+    encoded text NOT NULL,
+    requester bigint NOT NULL,
+    enqueued bigint NOT NULL,
+    playlist integer,
+    PRIMARY KEY (guild, position)
+);
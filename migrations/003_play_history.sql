@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS play_history (
+    id bigserial primary key,
+    guild bigint NOT NULL references guild_configs(id) ON DELETE CASCADE,
+    encoded text NOT NULL,
+    title text NOT NULL,
+    author text NOT NULL,
+    uri text,
+    length bigint NOT NULL,
+    requester bigint NOT NULL,
+    started bigint NOT NULL,
+    skipped boolean NOT NULL DEFAULT false
+);
+CREATE INDEX IF NOT EXISTS play_history_guild_idx ON play_history (guild, id DESC);
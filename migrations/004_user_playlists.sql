@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS user_playlists (
+    id bigserial primary key,
+    owner bigint NOT NULL,
+    name text NOT NULL,
+    UNIQUE (owner, name)
+);
+CREATE TABLE IF NOT EXISTS user_playlist_tracks (
+    playlist bigint NOT NULL references user_playlists(id) ON DELETE CASCADE,
+    position integer NOT NULL,
+    uri text NOT NULL,
+    title text NOT NULL,
+    length bigint NOT NULL,
+    PRIMARY KEY (playlist, position)
+);
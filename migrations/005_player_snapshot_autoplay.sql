@@ -0,0 +1 @@
+ALTER TABLE player_snapshots ADD COLUMN IF NOT EXISTS autoplay boolean NOT NULL DEFAULT false;
@@ -0,0 +1,4 @@
+ALTER TABLE guild_configs ADD COLUMN IF NOT EXISTS max_tracks_per_requester integer;
+ALTER TABLE guild_configs ADD COLUMN IF NOT EXISTS max_queue_length integer;
+ALTER TABLE guild_configs ADD COLUMN IF NOT EXISTS max_track_length bigint;
+ALTER TABLE guild_configs ADD COLUMN IF NOT EXISTS allow_streams boolean NOT NULL DEFAULT true;
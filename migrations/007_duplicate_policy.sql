@@ -0,0 +1 @@
+ALTER TABLE guild_configs ADD COLUMN IF NOT EXISTS duplicates smallint NOT NULL DEFAULT 0;
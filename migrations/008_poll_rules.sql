@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS poll_rules (
+    guild bigint NOT NULL references guild_configs(id) ON DELETE CASCADE,
+    topic smallint NOT NULL,
+    threshold_kind smallint NOT NULL DEFAULT 0,
+    threshold_value smallint NOT NULL DEFAULT 0,
+    timeout bigint NOT NULL DEFAULT 30000,
+    dj_override boolean NOT NULL DEFAULT true,
+    requester_double boolean NOT NULL DEFAULT false,
+    PRIMARY KEY (guild, topic)
+);
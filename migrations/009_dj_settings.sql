@@ -0,0 +1,6 @@
+ALTER TABLE guild_configs ADD COLUMN IF NOT EXISTS dj_when_alone boolean NOT NULL DEFAULT false;
+CREATE TABLE IF NOT EXISTS dj_roles (
+    guild bigint NOT NULL references guild_configs(id) ON DELETE CASCADE,
+    id bigint NOT NULL,
+    PRIMARY KEY (guild, id)
+);
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS command_access (
+    guild bigint NOT NULL references guild_configs(id) ON DELETE CASCADE,
+    command text NOT NULL,
+    category smallint NOT NULL,
+    id bigint NOT NULL,
+    allow boolean NOT NULL,
+    PRIMARY KEY (guild, command, id)
+);
@@ -0,0 +1,12 @@
+ALTER TABLE guild_configs ADD COLUMN IF NOT EXISTS stuck_max_failures smallint NOT NULL DEFAULT 3;
+ALTER TABLE guild_configs ADD COLUMN IF NOT EXISTS stuck_fallback boolean NOT NULL DEFAULT true;
+CREATE TABLE IF NOT EXISTS track_recoveries (
+    id bigserial primary key,
+    guild bigint NOT NULL references guild_configs(id) ON DELETE CASCADE,
+    title text NOT NULL,
+    attempt smallint NOT NULL,
+    step smallint NOT NULL,
+    detail text,
+    at bigint NOT NULL
+);
+CREATE INDEX IF NOT EXISTS track_recoveries_guild_idx ON track_recoveries (guild, id DESC);
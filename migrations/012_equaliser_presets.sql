@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS equaliser_presets (
+    id bigserial primary key,
+    guild bigint NOT NULL references guild_configs(id) ON DELETE CASCADE,
+    owner bigint,
+    name text NOT NULL,
+    gains double precision[] NOT NULL CHECK (cardinality(gains) = 15),
+    UNIQUE NULLS NOT DISTINCT (guild, owner, name)
+);
@@ -0,0 +1,2 @@
+ALTER TABLE player_snapshots ADD COLUMN IF NOT EXISTS effect_profile smallint;
+ALTER TABLE player_snapshots ADD COLUMN IF NOT EXISTS effect_previous_filters text;